@@ -0,0 +1,448 @@
+//! Unified `harald` CLI.
+//!
+//! Consolidates what used to be several disconnected binaries (schema
+//! validate/generate, single-character ingest, MarvelAI ingest, JSON
+//! formatting, naming validation) into one entrypoint with a shared HTTP
+//! client, a shared config-loading path, and structured `tracing` output
+//! in place of ad-hoc `println!("✅ …")` status lines.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use harald::ingest::{bench, embed, marvelai_ingest, query, single_character_ingest};
+use harald::utils::json_tools::{format_json, validate_json_schema};
+use harald::utils::validation::naming::{validate_naming_conventions, ValidationConfig};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+/// HARALD: ingestion, embedding, and validation toolkit.
+#[derive(Parser)]
+#[command(
+    name = "harald",
+    author,
+    version,
+    about = "HARALD ingestion, embedding, and validation toolkit"
+)]
+struct Cli {
+    /// Increase log verbosity (-v info, -vv debug, -vvv trace).
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Log output format.
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+/// `--compress` values, mapped onto [`harald::ingest::compression::Compression`].
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CompressOpt {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl From<CompressOpt> for harald::ingest::compression::Compression {
+    fn from(opt: CompressOpt) -> Self {
+        match opt {
+            CompressOpt::None => Self::None,
+            CompressOpt::Gzip => Self::Gzip,
+            CompressOpt::Zstd => Self::Zstd,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run an ingest pipeline.
+    Ingest {
+        #[command(subcommand)]
+        target: IngestTarget,
+    },
+    /// Validate or generate a JSON Schema.
+    Schema {
+        #[command(subcommand)]
+        action: SchemaAction,
+    },
+    /// Generate an embedding vector for ad-hoc text.
+    Embed {
+        /// Text to embed.
+        text: String,
+
+        /// Model name to request.
+        #[arg(long, default_value = "harald-phi4")]
+        model: String,
+
+        /// Embedding API endpoint (defaults to the local Ollama endpoint).
+        #[arg(long)]
+        endpoint: Option<String>,
+
+        /// Bearer token for an OpenAI-compatible endpoint. Implies the
+        /// OpenAI backend instead of Ollama.
+        #[arg(long)]
+        openai_token: Option<String>,
+    },
+    /// Validate file and directory naming conventions.
+    ValidateNaming {
+        /// Path to validate (defaults to current directory).
+        path: Option<PathBuf>,
+
+        /// Apply suggested fixes.
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Format or validate a JSON file.
+    Format {
+        /// Input JSON file path.
+        input: PathBuf,
+
+        /// Output file path (default: overwrite input).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Pretty-print indentation width (0 for compact).
+        #[arg(short, long, default_value_t = 2)]
+        indent: usize,
+
+        /// Validate only, don't write formatted output.
+        #[arg(long)]
+        validate_only: bool,
+
+        /// Treat `input` as JSONL (one JSON value per line) instead of a
+        /// single JSON document, validating and formatting each line
+        /// independently.
+        #[arg(long)]
+        jsonl: bool,
+
+        /// With `--jsonl`, drop lines that fail to parse instead of
+        /// erroring out, logging them to a `<input>.rejected.jsonl`
+        /// sidecar.
+        #[arg(long, requires = "jsonl")]
+        skip_invalid: bool,
+
+        /// With `--jsonl`, sort every record's object keys recursively so
+        /// regenerated ingest files diff deterministically.
+        #[arg(long, requires = "jsonl")]
+        canonicalize: bool,
+    },
+    /// Run a JSON-defined embedding workload and report latency percentiles.
+    Bench {
+        /// Path to the workload JSON file.
+        workload: PathBuf,
+
+        /// Write the JSON report here instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Dashboard endpoint to POST the report to.
+        #[arg(long)]
+        dashboard_url: Option<String>,
+
+        /// Label attached to the dashboard submission (e.g. a commit SHA or
+        /// release tag).
+        #[arg(long, default_value = "manual")]
+        reason: String,
+    },
+    /// Run a retrieval-augmented query against an already-ingested index.
+    Query {
+        /// The question or search text.
+        text: String,
+
+        /// Directory containing the `data/` folder with the built index.
+        #[arg(long)]
+        root_dir: Option<PathBuf>,
+
+        /// Number of context chunks to retrieve (defaults to the query
+        /// module's own default).
+        #[arg(long)]
+        num_results: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IngestTarget {
+    /// Ingest `MarvelAIs.json`, converting it to JSONL first if needed.
+    Marvel {
+        /// Path to the MarvelAIs.json (or .jsonl) file.
+        #[arg(
+            short,
+            long,
+            default_value = "personality-archetypes/pop-culture/marvel/MarvelAIs.json"
+        )]
+        input: PathBuf,
+
+        /// Maximum characters to read per file.
+        #[arg(long, default_value_t = 800)]
+        max_chars: usize,
+
+        /// Maximum tokens for embedding requests.
+        #[arg(long, default_value_t = 600)]
+        max_tokens: usize,
+
+        /// Maximum number of files to process concurrently.
+        #[arg(long)]
+        max_concurrent_files: Option<usize>,
+
+        /// Write a versioned, structured ingest report to this path.
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// Compression for the converted JSONL intermediate.
+        #[arg(long, default_value = "none")]
+        compress: CompressOpt,
+    },
+    /// Ingest a single-character fixture for pipeline validation.
+    SingleCharacter {
+        /// Path to the single character JSON file (array of objects).
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Characters of overlap between consecutive chunks of the same
+        /// field. `0` disables overlap and chunks as before.
+        #[arg(long, default_value_t = 0)]
+        overlap: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchemaAction {
+    /// Validate a JSON file against a JSON Schema file.
+    Validate {
+        /// JSON file to validate.
+        json_file: PathBuf,
+
+        /// JSON schema file.
+        #[arg(short, long)]
+        schema: PathBuf,
+    },
+    /// Infer a JSON Schema from an example JSON file.
+    Generate {
+        /// JSON file to infer a schema from.
+        json_file: PathBuf,
+
+        /// Output file for the generated schema.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn init_tracing(verbose: u8, format: LogFormat) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    init_tracing(cli.verbose, cli.log_format);
+
+    match cli.command {
+        Command::Ingest { target } => run_ingest(target).await?,
+        Command::Schema { action } => run_schema(action)?,
+        Command::Embed {
+            text,
+            model,
+            endpoint,
+            openai_token,
+        } => run_embed(text, model, endpoint, openai_token).await?,
+        Command::ValidateNaming { path, fix } => run_validate_naming(path, fix)?,
+        Command::Format {
+            input,
+            output,
+            indent,
+            validate_only,
+            jsonl,
+            skip_invalid,
+            canonicalize,
+        } => {
+            if jsonl {
+                format_json::format_jsonl_file(
+                    &input,
+                    output,
+                    skip_invalid,
+                    canonicalize,
+                    validate_only,
+                )?
+            } else {
+                format_json::format_file(&input, output, indent, validate_only)?
+            }
+        }
+        Command::Bench {
+            workload,
+            output,
+            dashboard_url,
+            reason,
+        } => run_bench(workload, output, dashboard_url, reason).await?,
+        Command::Query {
+            text,
+            root_dir,
+            num_results,
+        } => run_query(text, root_dir, num_results).await?,
+    }
+
+    Ok(())
+}
+
+async fn run_ingest(target: IngestTarget) -> Result<()> {
+    match target {
+        IngestTarget::Marvel {
+            input,
+            max_chars,
+            max_tokens,
+            max_concurrent_files,
+            report,
+            compress,
+        } => {
+            marvelai_ingest::run(marvelai_ingest::MarvelIngestOptions {
+                input,
+                max_chars,
+                max_tokens,
+                max_concurrent_files,
+                report,
+                compress: compress.into(),
+            })
+            .await
+        }
+        IngestTarget::SingleCharacter { input, overlap } => {
+            single_character_ingest::run(input, overlap)
+        }
+    }
+}
+
+fn run_schema(action: SchemaAction) -> Result<()> {
+    match action {
+        SchemaAction::Validate { json_file, schema } => {
+            let valid = validate_json_schema::validate_against_schema(&json_file, &schema)?;
+            if !valid {
+                std::process::exit(1);
+            }
+        }
+        SchemaAction::Generate { json_file, output } => {
+            validate_json_schema::generate_schema_file(&json_file, output)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_embed(
+    text: String,
+    model: String,
+    endpoint: Option<String>,
+    openai_token: Option<String>,
+) -> Result<()> {
+    let mut config = embed::EmbedConfig {
+        model,
+        ..embed::EmbedConfig::default()
+    };
+    if let Some(endpoint) = endpoint {
+        config.endpoint = endpoint;
+    }
+    if let Some(token) = openai_token {
+        config.backend = embed::Backend::OpenAi;
+        config.auth_token = Some(token);
+    }
+
+    let client = reqwest::Client::new();
+    let embedding = embed::embed_with_config(&text, 600, &client, config).await?;
+    info!(dimensions = embedding.len(), "embedding generated");
+    println!("{}", serde_json::to_string(&embedding)?);
+    Ok(())
+}
+
+async fn run_bench(
+    workload: PathBuf,
+    output: Option<PathBuf>,
+    dashboard_url: Option<String>,
+    reason: String,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let report = bench::run(&workload, &client).await?;
+    let json = serde_json::to_string_pretty(&report)?;
+
+    match output {
+        Some(path) => std::fs::write(&path, &json)?,
+        None => println!("{json}"),
+    }
+
+    if let Some(dashboard_url) = dashboard_url {
+        bench::publish(&client, &dashboard_url, &reason, &report).await?;
+        info!(dashboard_url, "bench report published");
+    }
+
+    info!(
+        chunks = report.metrics.chunk_count,
+        errors = report.metrics.error_count,
+        p50_ms = report.metrics.p50_ms,
+        p99_ms = report.metrics.p99_ms,
+        "bench run complete"
+    );
+    Ok(())
+}
+
+async fn run_query(
+    text: String,
+    root_dir: Option<PathBuf>,
+    num_results: Option<usize>,
+) -> Result<()> {
+    let mut config = query::QueryConfig::default();
+    if let Some(root_dir) = root_dir {
+        config.root_dir = root_dir;
+    }
+    if let Some(num_results) = num_results {
+        config.num_results = num_results;
+    }
+
+    let result = query::run_with_config(&text, config).await?;
+    println!("{}", result.response);
+    info!(
+        context_chunks = result.num_context_docs,
+        "query answered from retrieved context"
+    );
+    Ok(())
+}
+
+fn run_validate_naming(path: Option<PathBuf>, fix: bool) -> Result<()> {
+    let config = ValidationConfig {
+        target_path: path.unwrap_or_else(|| {
+            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+        }),
+        fix_issues: fix,
+        verbose: false,
+    };
+
+    let result = validate_naming_conventions(&config)?;
+    if result.issues.is_empty() {
+        info!("all naming conventions are compliant");
+    } else {
+        info!(
+            issues = result.issues.len(),
+            fixed = result.fixed_count,
+            errors = result.error_count,
+            "naming convention issues found"
+        );
+        if !fix {
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
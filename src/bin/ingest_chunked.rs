@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 use clap::{Arg, Command};
-use harald::ingest::chunked_ingest::{ChunkedIngestConfig, process_file};
+use harald::ingest::chunked_ingest_new::{process_file, ChunkedIngestConfig};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -29,18 +29,32 @@ async fn main() -> Result<()> {
                 .help("Ollama model to use for embeddings")
                 .default_value("harald-phi4"),
         )
+        .arg(
+            Arg::new("store")
+                .long("store")
+                .value_name("ADDR")
+                .help("Vector store to index embedded chunks into (e.g. hnsw:./data/chunked, memory:). Omit to discard embeddings after reporting on them."),
+        )
         .get_matches();
 
     let file_path = matches.get_one::<String>("file").unwrap();
     let model = matches.get_one::<String>("model").unwrap();
+    let store = matches.get_one::<String>("store").cloned();
 
     println!("🚀 Starting chunked ingestion process...");
     println!("   File: {}", file_path);
     println!("   Model: {}", model);
 
     let config = ChunkedIngestConfig {
-        model_name: model.to_string(),
+        provider: std::sync::Arc::new(harald::ingest::embed::OllamaProvider::new(
+            reqwest::Client::new(),
+            harald::ingest::embed::EmbedConfig {
+                model: model.to_string(),
+                ..Default::default()
+            },
+        )),
         max_chunk_size: 250,
+        store_addr: store,
         ..Default::default()
     };
 
@@ -50,6 +64,7 @@ async fn main() -> Result<()> {
             println!("   Characters processed: {}", result.characters_processed);
             println!("   Chunks created: {}", result.chunks_created);
             println!("   Embeddings generated: {}", result.embeddings_generated);
+            println!("   Chunks indexed into store: {}", result.indexed_chunks);
             println!("   Processing time: {:.2}s", result.processing_time_secs);
         }
         Err(e) => {
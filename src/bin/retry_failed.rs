@@ -0,0 +1,105 @@
+//! CLI for replaying `failed_chunks.log` files left behind by a partial
+//! ingest, via [`harald::ingest::retry_failed`].
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use harald::ingest::embed::{Backend, EmbedConfig};
+use harald::ingest::retry_failed::{retry_log, RetryConfig};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+/// Re-embeds chunks recorded in one or more `failed_chunks.log` files,
+/// rewriting each in place and promoting repeatedly-failing chunks to a
+/// sibling `dead_letter.log`.
+#[derive(Parser)]
+#[command(name = "retry_failed", author, version, about)]
+struct Cli {
+    /// One or more `failed_chunks.log` files to retry.
+    logs: Vec<PathBuf>,
+
+    /// Model name to request embeddings for.
+    #[arg(long, default_value = "harald-phi4")]
+    model: String,
+
+    /// Embedding API endpoint (defaults to the local Ollama endpoint).
+    #[arg(long)]
+    endpoint: Option<String>,
+
+    /// Bearer token for an OpenAI-compatible endpoint. Implies the
+    /// OpenAI backend instead of Ollama.
+    #[arg(long)]
+    openai_token: Option<String>,
+
+    /// Maximum embedding attempts per chunk within this pass. Defaults to
+    /// the `EMBED_MAX_RETRIES` env var (and then 3), matching the knob
+    /// the original ingest used.
+    #[arg(long)]
+    max_retries: Option<u64>,
+
+    /// Base delay (seconds) between attempts; actual backoff is
+    /// `retry_delay_secs * attempt`. Defaults to the
+    /// `EMBED_RETRY_DELAY_SECS` env var (and then 5).
+    #[arg(long)]
+    retry_delay_secs: Option<u64>,
+
+    /// Total attempts (summed across every retry pass) a chunk may
+    /// accumulate before it's moved to `dead_letter.log`.
+    #[arg(long, default_value_t = 5)]
+    max_total_cycles: u32,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let cli = Cli::parse();
+
+    let mut embed_config = EmbedConfig {
+        model: cli.model,
+        ..EmbedConfig::default()
+    };
+    if let Some(endpoint) = cli.endpoint {
+        embed_config.endpoint = endpoint;
+    }
+
+    if let Some(token) = cli.openai_token {
+        embed_config.backend = Backend::OpenAi;
+        embed_config.auth_token = Some(token);
+    }
+
+    let client = reqwest::Client::new();
+    let provider = embed_config.build_provider(client);
+
+    let mut config = RetryConfig {
+        provider,
+        ..RetryConfig::default()
+    };
+    if let Some(max_retries) = cli.max_retries {
+        config.max_retries = max_retries;
+    }
+    if let Some(retry_delay_secs) = cli.retry_delay_secs {
+        config.retry_delay_secs = retry_delay_secs;
+    }
+    config.max_total_cycles = cli.max_total_cycles;
+
+    if cli.logs.is_empty() {
+        anyhow::bail!("No failed_chunks.log files given");
+    }
+
+    for log_path in &cli.logs {
+        let report = retry_log(log_path, &config).await?;
+        info!(
+            path = %log_path.display(),
+            recovered = report.recovered,
+            still_failing = report.still_failing,
+            dead_lettered = report.dead_lettered,
+            "retry pass complete"
+        );
+    }
+
+    Ok(())
+}
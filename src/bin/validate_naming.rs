@@ -10,9 +10,33 @@ use std::path::PathBuf;
 
 // Import the library functions
 use harald::utils::validation::naming::{
-    validate_naming_conventions, ValidationConfig, ValidationResult, NamingIssue
+    validate_naming_conventions, IssueType, NamingIssue, ValidationConfig, ValidationResult,
 };
 
+/// The stable diagnostic code every naming issue is reported under,
+/// regardless of which naming construct triggered it.
+const RULE_ID: &str = "incorrect-name-case";
+
+/// Machine-readable diagnostic formats `--format` can select, alongside
+/// the default human-readable one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl OutputFormat {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+}
+
 fn main() -> Result<()> {
     let matches = Command::new("validate_naming")
         .about("Validates naming conventions for HARALD project files")
@@ -22,6 +46,12 @@ fn main() -> Result<()> {
                 .help("Automatically fix naming issues")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .help("With --fix, print the planned renames/rewrites without touching disk")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("verbose")
                 .short('v')
@@ -36,14 +66,26 @@ fn main() -> Result<()> {
                 .help("Path to validate (defaults to current directory)")
                 .value_name("PATH"),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .help("Diagnostic output format: human, json, or sarif")
+                .value_name("FORMAT")
+                .default_value("human"),
+        )
         .get_matches();
 
+    let format_str = matches.get_one::<String>("format").unwrap();
+    let format = OutputFormat::parse(format_str)
+        .ok_or_else(|| anyhow::anyhow!("Unknown --format '{}' (expected human, json, or sarif)", format_str))?;
+
     let config = ValidationConfig {
         target_path: matches
             .get_one::<String>("path")
             .map(PathBuf::from)
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))),
         fix_issues: matches.get_flag("fix"),
+        dry_run: matches.get_flag("dry-run"),
         verbose: matches.get_flag("verbose"),
     };
 
@@ -51,23 +93,161 @@ fn main() -> Result<()> {
     let result = validate_naming_conventions(&config)?;
 
     // Display results
-    display_results(&result, &config);
+    match format {
+        OutputFormat::Human => display_results(&result, &config),
+        OutputFormat::Json => println!("{}", render_json(&result)?),
+        OutputFormat::Sarif => println!("{}", render_sarif(&result)?),
+    }
+
+    if format == OutputFormat::Human && config.fix_issues && config.dry_run && !result.planned_fixes.is_empty() {
+        println!();
+        log_info("Dry run - no changes were made. Planned fixes:");
+        for planned in &result.planned_fixes {
+            println!("  {}", planned);
+        }
+        for conflict in &result.conflicts {
+            log_warning(&conflict.to_string());
+        }
+    }
 
     // Exit with appropriate code
     if result.issues.is_empty() {
-        log_success("All naming conventions are compliant!");
+        if format == OutputFormat::Human {
+            log_success("All naming conventions are compliant!");
+        }
         Ok(())
-    } else if config.fix_issues && result.fixed_count > 0 {
-        log_info(&format!(
-            "Fixed {} issues, {} errors occurred",
-            result.fixed_count, result.error_count
-        ));
+    } else if config.fix_issues && !config.dry_run && result.fixed_count > 0 {
+        if format == OutputFormat::Human {
+            log_info(&format!(
+                "Fixed {} issues, {} errors occurred",
+                result.fixed_count, result.error_count
+            ));
+            for conflict in &result.conflicts {
+                log_warning(&conflict.to_string());
+            }
+        }
+        Ok(())
+    } else if config.fix_issues && config.dry_run {
         Ok(())
     } else {
         std::process::exit(1);
     }
 }
 
+/// The issue type as a SARIF/JSON-friendly identifier, distinct from the
+/// human-readable label `display_issue` uses.
+fn issue_type_id(issue_type: &IssueType) -> &'static str {
+    match issue_type {
+        IssueType::DirectoryNaming => "directory",
+        IssueType::RustFileNaming => "rust-file",
+        IssueType::MarkdownFileNaming => "markdown-file",
+        IssueType::JsonFileNaming => "json-file",
+        IssueType::RustIdentifierNaming => "rust-identifier",
+    }
+}
+
+/// A `(start, end)` byte span as a SARIF `region`, or `null` for issues
+/// (file/directory renames) with no in-file span.
+fn span_json(issue: &NamingIssue) -> serde_json::Value {
+    match &issue.replacement {
+        Some(replacement) => serde_json::json!({
+            "start": replacement.span.0,
+            "end": replacement.span.1,
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+fn render_json(result: &ValidationResult) -> Result<String> {
+    let issues: Vec<serde_json::Value> = result
+        .issues
+        .iter()
+        .map(|issue| {
+            serde_json::json!({
+                "code": RULE_ID,
+                "severity": "warning",
+                "issueType": issue_type_id(&issue.issue_type),
+                "path": issue.path,
+                "currentName": issue.current_name,
+                "suggestedName": issue.suggested_name,
+                "description": issue.description,
+                "span": span_json(issue),
+            })
+        })
+        .collect();
+
+    let report = serde_json::json!({
+        "issues": issues,
+        "fixedCount": result.fixed_count,
+        "errorCount": result.error_count,
+    });
+
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+fn render_sarif(result: &ValidationResult) -> Result<String> {
+    let results: Vec<serde_json::Value> = result
+        .issues
+        .iter()
+        .map(|issue| {
+            let uri = issue.path.to_string_lossy();
+            let mut physical_location = serde_json::json!({
+                "artifactLocation": { "uri": uri },
+            });
+
+            if let Some(replacement) = &issue.replacement {
+                let (start, end) = replacement.span;
+                physical_location["region"] = serde_json::json!({
+                    "charOffset": start,
+                    "charLength": end - start,
+                });
+            }
+
+            let mut sarif_result = serde_json::json!({
+                "ruleId": RULE_ID,
+                "level": "warning",
+                "message": { "text": issue.description },
+                "locations": [{ "physicalLocation": physical_location }],
+            });
+
+            if let Some(replacement) = &issue.replacement {
+                let (start, end) = replacement.span;
+                sarif_result["fixes"] = serde_json::json!([{
+                    "description": { "text": format!("Rename to '{}'", replacement.suggested_text) },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri },
+                        "replacements": [{
+                            "deletedRegion": { "charOffset": start, "charLength": end - start },
+                            "insertedContent": { "text": replacement.suggested_text },
+                        }],
+                    }],
+                }]);
+            }
+
+            sarif_result
+        })
+        .collect();
+
+    let report = serde_json::json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "validate_naming",
+                    "rules": [{
+                        "id": RULE_ID,
+                        "shortDescription": { "text": "Identifier or file name uses the wrong case" },
+                    }],
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
 fn display_results(result: &ValidationResult, config: &ValidationConfig) {
     if result.issues.is_empty() {
         return;
@@ -92,6 +272,7 @@ fn display_issue(issue: &NamingIssue, verbose: bool) {
         harald::utils::validation::naming::IssueType::RustFileNaming => "Rust File",
         harald::utils::validation::naming::IssueType::MarkdownFileNaming => "Markdown File",
         harald::utils::validation::naming::IssueType::JsonFileNaming => "JSON File",
+        harald::utils::validation::naming::IssueType::RustIdentifierNaming => "Rust Identifier",
     };
 
     println!("  {} {}", 
@@ -35,6 +35,18 @@ struct Cli {
     /// Model to use for embeddings
     #[arg(long, default_value = "harald-phi4")]
     model: String,
+
+    /// Maximum retries for transient embedding request failures
+    #[arg(long, default_value = "3")]
+    max_retries: usize,
+
+    /// Maximum number of chunk embedding requests to have in flight at once
+    #[arg(long, default_value = "4")]
+    concurrency: usize,
+
+    /// Model context window in tokens, sent as `options.num_ctx`
+    #[arg(long, default_value = "4096")]
+    num_ctx: usize,
 }
 
 /// Available commands for the embedding tool
@@ -102,7 +114,19 @@ fn main() -> Result<()> {
     let rt = Runtime::new()?;
 
     // Create API client
-    let client = OllamaApiClient::new(&cli.base_url).with_timeout(cli.timeout);
+    let client = OllamaApiClient::new(&cli.base_url)
+        .with_timeout(cli.timeout)
+        .with_max_retries(cli.max_retries)
+        .with_concurrency(cli.concurrency)
+        .with_num_ctx(cli.num_ctx);
+
+    // Every command but CheckStatus actually generates embeddings, so
+    // verify the requested model is pulled up front rather than
+    // discovering a typo'd --model partway through a long-running file or
+    // TestSizes run as an opaque 404. CheckStatus reports this itself.
+    if !matches!(cli.command, Commands::CheckStatus {}) {
+        rt.block_on(ensure_model_available(&client, &cli.model))?;
+    }
 
     match cli.command {
         Commands::CheckStatus {} => {
@@ -154,19 +178,43 @@ async fn check_status(client: OllamaApiClient) -> Result<()> {
     println!("🔍 Checking Ollama API status...");
 
     match client.check_status().await {
-        Ok(true) => {
-            println!("✅ Ollama API is available");
-            Ok(())
-        }
+        Ok(true) => println!("✅ Ollama API is available"),
         Ok(false) => {
             println!("❌ Ollama API is not responding properly");
             anyhow::bail!("API returned unsuccessful status")
         }
         Err(e) => {
             println!("❌ Failed to connect to Ollama API: {}", e);
-            Err(e)
+            return Err(e);
+        }
+    }
+
+    println!("🔍 Listing installed models...");
+    let models = client.list_models().await?;
+    if models.is_empty() {
+        println!("   No models are pulled.");
+    } else {
+        for model in &models {
+            println!("     - {}", model.name);
         }
     }
+
+    Ok(())
+}
+
+/// Fails fast with the list of installed models if `model` isn't pulled in
+/// Ollama, instead of letting every subsequent embedding request fail with
+/// the same opaque 404.
+async fn ensure_model_available(client: &OllamaApiClient, model: &str) -> Result<()> {
+    let models = client.list_models().await?;
+    if !models.iter().any(|m| m.name == model) {
+        anyhow::bail!(
+            "model '{}' not found; available: [{}]",
+            model,
+            models.iter().map(|m| m.name.as_str()).collect::<Vec<_>>().join(", ")
+        );
+    }
+    Ok(())
 }
 
 async fn generate_embedding(
@@ -191,19 +239,25 @@ async fn generate_embedding(
             .generate_embedding_chunked(&text, &model, max_chunk_size)
             .await?;
 
+        let elapsed = start.elapsed();
         println!(
-            "✅ Success - Generated {} chunk embeddings in {:?}",
+            "✅ Success - Generated {} chunk embeddings in {:?} ({:.1} chunks/sec)",
             embeddings.len(),
-            start.elapsed()
+            elapsed,
+            embeddings.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
         );
 
         for (i, embedding) in embeddings.iter().enumerate() {
             println!("   Chunk {}: Vector dimensions: {}", i + 1, embedding.len());
         }
     } else {
-        let embedding = client.generate_embedding(&text, &model).await?;
+        let (embedding, retries) = client.generate_embedding_with_retry(&text, &model).await?;
 
-        println!("✅ Success - Embedding generated in {:?}", start.elapsed());
+        println!(
+            "✅ Success - Embedding generated in {:?} ({} retries)",
+            start.elapsed(),
+            retries
+        );
         println!("   Vector dimensions: {}", embedding.len());
     }
 
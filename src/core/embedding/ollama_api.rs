@@ -4,22 +4,258 @@
 //! particularly for generating embeddings and checking the API status.
 
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::time::Duration;
 
-/// Response from the Ollama embedding API
+/// Response from the Ollama `/api/tags` endpoint.
 #[derive(Debug, Deserialize)]
-pub struct EmbeddingResponse {
-    pub embedding: Vec<f32>,
+struct TagsResponse {
+    models: Vec<ModelInfo>,
 }
 
-/// Request to the Ollama embedding API
-#[derive(Debug, Serialize)]
-pub struct EmbeddingRequest<'a> {
-    pub model: &'a str,
-    pub prompt: &'a str,
+/// One model Ollama has pulled, as reported by `/api/tags`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ModelInfo {
+    pub name: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default, rename = "modified_at")]
+    pub modified_at: String,
+    #[serde(default)]
+    pub digest: String,
 }
 
+/// One step navigating a REST response's JSON to find the embedding
+/// vector - a `Key` into an object or an `Index` into an array. Ollama's
+/// `{"embedding": [...]}` needs just `[Key("embedding")]`; an OpenAI-style
+/// batch response `{"data": [{"embedding": [...]}]}` needs
+/// `[Key("data"), Index(0), Key("embedding")]`.
+#[derive(Debug, Clone)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Whether a REST embedding endpoint accepts one text per request body, or
+/// an array of texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputType {
+    Single,
+    Batch,
+}
+
+/// Describes a REST embedding endpoint's request/response shape, so
+/// [`RestEmbedder`] can target Ollama, OpenAI-compatible, or any other
+/// compatible HTTP embedding API through the same request/response
+/// handling instead of each provider needing its own client.
+#[derive(Debug, Clone)]
+pub struct RestEmbedderDescriptor {
+    /// Full URL of the embedding endpoint.
+    pub url: String,
+    /// Model name to request.
+    pub model: String,
+    /// Extra fields merged into every request body alongside `model` and
+    /// the input text, e.g. provider-specific options.
+    pub query_template: serde_json::Value,
+    /// Name(s) of the request field the input text is written to -
+    /// `["prompt"]` for Ollama, `["input"]` for OpenAI-style APIs.
+    pub input_fields: Vec<String>,
+    /// Path to the embedding vector within the response JSON.
+    pub response_path: Vec<JsonPathSegment>,
+    /// Bearer token for providers that require authentication.
+    pub api_key: Option<String>,
+    pub input_type: InputType,
+}
+
+impl RestEmbedderDescriptor {
+    /// Descriptor for Ollama's `/api/embeddings`: `{model, prompt, options:
+    /// {num_ctx}}` in, `{embedding: [...]}` out. Ollama exposes no API to
+    /// query a model's max tokens, so `num_ctx` has to be set explicitly
+    /// rather than discovered.
+    pub fn ollama(base_url: &str, model: &str, num_ctx: usize) -> Self {
+        Self {
+            url: format!("{}/api/embeddings", base_url),
+            model: model.to_string(),
+            query_template: serde_json::json!({ "options": { "num_ctx": num_ctx } }),
+            input_fields: vec!["prompt".to_string()],
+            response_path: vec![JsonPathSegment::Key("embedding".to_string())],
+            api_key: None,
+            input_type: InputType::Single,
+        }
+    }
+
+    /// Descriptor for an OpenAI-compatible `/embeddings` endpoint:
+    /// `{model, input: [text]}` in, `{data: [{embedding: [...]}]}` out.
+    pub fn openai_compatible(url: &str, model: &str, api_key: Option<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            model: model.to_string(),
+            query_template: serde_json::json!({}),
+            input_fields: vec!["input".to_string()],
+            response_path: vec![
+                JsonPathSegment::Key("data".to_string()),
+                JsonPathSegment::Index(0),
+                JsonPathSegment::Key("embedding".to_string()),
+            ],
+            api_key,
+            input_type: InputType::Batch,
+        }
+    }
+
+    fn request_body(&self, text: &str) -> serde_json::Value {
+        let mut body = self.query_template.clone();
+        let obj = body
+            .as_object_mut()
+            .expect("query_template must be a JSON object");
+        obj.insert("model".to_string(), serde_json::Value::String(self.model.clone()));
+        let text_value = match self.input_type {
+            InputType::Single => serde_json::Value::String(text.to_string()),
+            InputType::Batch => serde_json::json!([text]),
+        };
+        for field in &self.input_fields {
+            obj.insert(field.clone(), text_value.clone());
+        }
+        body
+    }
+}
+
+/// Navigates `value` following `path`, failing with context naming the
+/// segment that couldn't be found.
+fn extract_embedding(value: &serde_json::Value, path: &[JsonPathSegment]) -> Result<Vec<f32>> {
+    let mut current = value;
+    for segment in path {
+        current = match segment {
+            JsonPathSegment::Key(key) => current
+                .get(key)
+                .with_context(|| format!("Response JSON missing field '{}'", key))?,
+            JsonPathSegment::Index(i) => current
+                .get(i)
+                .with_context(|| format!("Response JSON missing index {}", i))?,
+        };
+    }
+    serde_json::from_value(current.clone()).context("Embedding field was not a numeric array")
+}
+
+/// Raw outcome of a [`RestEmbedder`] request, before any provider-specific
+/// reclassification - see `OllamaApiClient::try_generate_embedding`, which
+/// turns an `Api` failure here into a specific [`OllamaError`] variant.
+enum RestEmbedError {
+    Connect,
+    Timeout,
+    Api {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    Other(anyhow::Error),
+}
+
+/// Generic REST embedding request/response handling, configured per call
+/// by a [`RestEmbedderDescriptor`] so the same code path can target any
+/// HTTP embedding endpoint rather than being hardwired to one provider's
+/// JSON shape.
+struct RestEmbedder<'a> {
+    client: &'a reqwest::Client,
+    timeout: Duration,
+}
+
+impl<'a> RestEmbedder<'a> {
+    fn new(client: &'a reqwest::Client, timeout: Duration) -> Self {
+        Self { client, timeout }
+    }
+
+    /// Sends one embedding request per `descriptor` and extracts the
+    /// resulting vector, classifying connection/timeout/status failures so
+    /// callers can decide whether to retry.
+    async fn try_embed(
+        &self,
+        descriptor: &RestEmbedderDescriptor,
+        text: &str,
+    ) -> std::result::Result<Vec<f32>, (FailureKind, RestEmbedError)> {
+        let body = descriptor.request_body(text);
+        let mut request = self.client.post(&descriptor.url).timeout(self.timeout).json(&body);
+        if let Some(key) = &descriptor.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = match request.send().await {
+            Ok(r) => r,
+            Err(e) if e.is_timeout() => return Err((FailureKind::Retryable, RestEmbedError::Timeout)),
+            Err(e) if e.is_connect() => return Err((FailureKind::Retryable, RestEmbedError::Connect)),
+            Err(e) => {
+                return Err((
+                    FailureKind::Fatal,
+                    RestEmbedError::Other(anyhow::Error::new(e).context("Failed to send embedding request")),
+                ))
+            }
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "No response body".to_string());
+            let kind = if status.is_server_error() {
+                FailureKind::Retryable
+            } else {
+                FailureKind::Fatal
+            };
+            return Err((kind, RestEmbedError::Api { status, body }));
+        }
+
+        let value: serde_json::Value = match response.json().await {
+            Ok(v) => v,
+            Err(e) => {
+                return Err((
+                    FailureKind::Fatal,
+                    RestEmbedError::Other(anyhow::Error::new(e).context("Failed to parse embedding response")),
+                ))
+            }
+        };
+
+        extract_embedding(&value, &descriptor.response_path)
+            .map_err(|e| (FailureKind::Fatal, RestEmbedError::Other(e)))
+    }
+
+    /// Embeds `text` via `descriptor`, collapsing any failure to a plain
+    /// `anyhow::Error`. For callers (like Ollama-compatible endpoints) that
+    /// need provider-specific error classification, match on
+    /// [`Self::try_embed`]'s `RestEmbedError` instead.
+    pub async fn embed(&self, descriptor: &RestEmbedderDescriptor, text: &str) -> Result<Vec<f32>> {
+        self.try_embed(descriptor, text).await.map_err(|(_, err)| err.into())
+    }
+}
+
+impl From<RestEmbedError> for anyhow::Error {
+    fn from(err: RestEmbedError) -> Self {
+        match err {
+            RestEmbedError::Connect => anyhow::anyhow!("connection to embedding endpoint failed"),
+            RestEmbedError::Timeout => anyhow::anyhow!("embedding request timed out"),
+            RestEmbedError::Api { status, body } => anyhow::anyhow!("API error ({}): {}", status, body),
+            RestEmbedError::Other(err) => err,
+        }
+    }
+}
+
+/// Default number of retry attempts for [`OllamaApiClient::generate_embedding_with_retry`].
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// Default base delay between retries, before exponential backoff and jitter.
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Default number of chunk embedding requests [`OllamaApiClient::generate_embedding_chunked`]
+/// keeps in flight at once. Kept small since a local Ollama server has
+/// finite parallelism - too high a value just queues requests server-side
+/// instead of actually speeding anything up.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default `options.num_ctx` sent with every embedding request. Ollama
+/// exposes no API to query a model's max tokens, so a sensible default has
+/// to be chosen rather than discovered.
+const DEFAULT_NUM_CTX: usize = 4096;
+
 /// Client for interacting with the Ollama API.
 pub struct OllamaApiClient {
     /// Base URL of the Ollama API
@@ -28,8 +264,105 @@ pub struct OllamaApiClient {
     /// Timeout for API requests in seconds
     timeout: Duration,
 
+    /// Maximum retry attempts for [`generate_embedding_with_retry`](Self::generate_embedding_with_retry).
+    max_retries: usize,
+
+    /// Base delay before exponential backoff and jitter between retries.
+    retry_base_delay: Duration,
+
+    /// Maximum chunk embedding requests [`generate_embedding_chunked`](Self::generate_embedding_chunked)
+    /// keeps in flight at once.
+    concurrency: usize,
+
+    /// Runtime context window (`options.num_ctx`) sent with every
+    /// embedding request.
+    num_ctx: usize,
+
     /// HTTP client for making requests
     client: reqwest::Client,
+
+    /// Caches [`model_dimensions`](Self::model_dimensions) results per model
+    /// name, so repeated calls don't re-probe the API.
+    dimension_cache: Mutex<HashMap<String, usize>>,
+}
+
+/// Fixed probe text embedded by [`OllamaApiClient::model_dimensions`] to
+/// discover a model's output vector length.
+const DIMENSION_PROBE_TEXT: &str = "test";
+
+/// Errors from Ollama API requests, distinguishing failure causes a caller
+/// might want to act on differently - e.g. skip an oversized chunk rather
+/// than abort the whole ingestion run, or retry a busy server but not a
+/// missing model.
+#[derive(Debug, thiserror::Error)]
+pub enum OllamaError {
+    /// The requested model isn't pulled into Ollama.
+    #[error("model '{model}' not found")]
+    ModelNotFound { model: String },
+    /// The server didn't respond, refused the connection, or returned a
+    /// 5xx indicating it's temporarily unable to serve requests.
+    #[error("Ollama server is unavailable")]
+    ServerUnavailable,
+    /// The request exceeded `timeout` without a response.
+    #[error("request timed out after {secs}s")]
+    Timeout { secs: u64 },
+    /// The input text was too large for the model's context window.
+    #[error("request text too large for the model's context window")]
+    RequestTooLarge,
+    /// Any other non-success response, verbatim.
+    #[error("API error ({status}): {body}")]
+    Api { status: u16, body: String },
+}
+
+/// Classifies a non-success `/api/embeddings` response into a specific
+/// [`OllamaError`] variant. `model` is the model that was requested -
+/// Ollama's own 404 body names it too, but using the value the caller
+/// already passed in is simpler and doesn't depend on the exact wording of
+/// Ollama's error message.
+fn classify_api_error(status: reqwest::StatusCode, body: &str, model: &str) -> OllamaError {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.to_string());
+
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return OllamaError::ModelNotFound { model: model.to_string() };
+    }
+    if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+        return OllamaError::ServerUnavailable;
+    }
+    let lower = message.to_lowercase();
+    if lower.contains("too large") || lower.contains("context length") || lower.contains("context window") {
+        return OllamaError::RequestTooLarge;
+    }
+
+    OllamaError::Api { status: status.as_u16(), body: message }
+}
+
+/// Whether a failed embedding request is worth retrying, used by
+/// [`OllamaApiClient::generate_embedding_with_retry`].
+enum FailureKind {
+    /// Connection errors, timeouts, or HTTP 5xx - e.g. Ollama still loading
+    /// the model into memory on first inference - likely to clear up on a
+    /// later attempt.
+    Retryable,
+    /// Malformed request or other 4xx - retrying the same request won't
+    /// change the outcome.
+    Fatal,
+}
+
+/// Cheap pseudo-random jitter in `[0, max_ms)`, derived from the current
+/// time so concurrent retries don't all wake up in lockstep. Not
+/// cryptographic - only meant to desynchronize retries.
+pub(crate) fn jitter_ms(max_ms: u64) -> u64 {
+    if max_ms == 0 {
+        return 0;
+    }
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    nanos % max_ms
 }
 
 impl OllamaApiClient {
@@ -38,7 +371,12 @@ impl OllamaApiClient {
         Self {
             base_url: base_url.to_string(),
             timeout: Duration::from_secs(30),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            concurrency: DEFAULT_CONCURRENCY,
+            num_ctx: DEFAULT_NUM_CTX,
             client: reqwest::Client::new(),
+            dimension_cache: Mutex::new(HashMap::new()),
         }
     }
 
@@ -48,6 +386,34 @@ impl OllamaApiClient {
         self
     }
 
+    /// Set the maximum number of retries [`generate_embedding_with_retry`](Self::generate_embedding_with_retry)
+    /// makes before giving up. Defaults to `3`.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the base delay between retries, before exponential backoff and
+    /// jitter are applied. Defaults to 500ms.
+    pub fn with_retry_base_delay(mut self, retry_base_delay: Duration) -> Self {
+        self.retry_base_delay = retry_base_delay;
+        self
+    }
+
+    /// Set how many chunk embedding requests [`generate_embedding_chunked`](Self::generate_embedding_chunked)
+    /// keeps in flight at once. Defaults to `4`.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set the runtime context window (`options.num_ctx`) sent with every
+    /// embedding request. Defaults to `4096`.
+    pub fn with_num_ctx(mut self, num_ctx: usize) -> Self {
+        self.num_ctx = num_ctx;
+        self
+    }
+
     /// Check if the Ollama API is available.
     pub async fn check_status(&self) -> Result<bool> {
         let url = format!("{}/api/version", self.base_url);
@@ -62,22 +428,20 @@ impl OllamaApiClient {
         Ok(response.status().is_success())
     }
 
-    /// Generate an embedding for the given text using the specified model.
-    pub async fn generate_embedding(&self, text: &str, model: &str) -> Result<Vec<f32>> {
-        let url = format!("{}/api/embeddings", self.base_url);
-        let request = EmbeddingRequest {
-            model,
-            prompt: text,
-        };
-
+    /// Lists the models Ollama currently has pulled, by querying
+    /// `/api/tags`. Doubles as an availability check: a model missing from
+    /// this list will fail every embedding request, so callers should
+    /// check it before starting a batch run rather than discovering the
+    /// problem one doomed request at a time.
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/api/tags", self.base_url);
         let response = self
             .client
-            .post(&url)
+            .get(&url)
             .timeout(self.timeout)
-            .json(&request)
             .send()
             .await
-            .context("Failed to send embedding request")?;
+            .context("Failed to fetch model list from Ollama API")?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -88,19 +452,112 @@ impl OllamaApiClient {
             anyhow::bail!("API error ({}): {}", status, text);
         }
 
-        let embedding_response: EmbeddingResponse = response
+        let tags: TagsResponse = response
             .json()
             .await
-            .context("Failed to parse embedding response")?;
+            .context("Failed to parse model list response")?;
+
+        Ok(tags.models)
+    }
+
+    /// Convenience check for whether `name` is among the models
+    /// [`list_models`](Self::list_models) reports as pulled.
+    pub async fn has_model(&self, name: &str) -> Result<bool> {
+        let models = self.list_models().await?;
+        Ok(models.iter().any(|m| m.name == name))
+    }
+
+    /// Returns the embedding vector length `model` produces, so callers
+    /// don't have to generate a real embedding and inspect `.len()`
+    /// themselves. Ollama exposes no API to query this directly, so the
+    /// first call per model embeds a short fixed probe string and caches
+    /// the resulting dimension; later calls for the same model are free.
+    pub async fn model_dimensions(&self, model: &str) -> Result<usize> {
+        if let Some(&dims) = self.dimension_cache.lock().unwrap().get(model) {
+            return Ok(dims);
+        }
+
+        let (embedding, _retries) = self
+            .generate_embedding_with_retry(DIMENSION_PROBE_TEXT, model)
+            .await?;
+        let dims = embedding.len();
 
-        Ok(embedding_response.embedding)
+        self.dimension_cache.lock().unwrap().insert(model.to_string(), dims);
+        Ok(dims)
+    }
+
+    /// Generate an embedding for the given text using the specified model.
+    pub async fn generate_embedding(&self, text: &str, model: &str) -> Result<Vec<f32>> {
+        self.try_generate_embedding(text, model).await.map_err(|(_, err)| err)
+    }
+
+    /// Generates an embedding, retrying retryable failures (timeouts,
+    /// connection errors, HTTP 5xx) up to `self.max_retries` times (see
+    /// [`with_max_retries`](Self::with_max_retries)) with exponentially
+    /// increasing delay from `self.retry_base_delay` plus a small random
+    /// jitter. Fatal failures (e.g. HTTP 4xx) return immediately without
+    /// consuming a retry. Returns the embedding together with the number of
+    /// retries it took, so callers can report per-chunk retry counts.
+    pub async fn generate_embedding_with_retry(
+        &self,
+        text: &str,
+        model: &str,
+    ) -> Result<(Vec<f32>, usize)> {
+        let mut attempt = 0;
+        loop {
+            match self.try_generate_embedding(text, model).await {
+                Ok(embedding) => return Ok((embedding, attempt)),
+                Err((FailureKind::Fatal, err)) => return Err(err),
+                Err((FailureKind::Retryable, err)) => {
+                    if attempt >= self.max_retries {
+                        return Err(err.context(format!(
+                            "Exhausted {} retries ({} attempts total)",
+                            self.max_retries,
+                            attempt + 1
+                        )));
+                    }
+                    let delay = self.retry_base_delay * 2u32.pow(attempt as u32);
+                    let jitter = Duration::from_millis(jitter_ms((delay.as_millis() as u64 / 4).max(1)));
+                    tokio::time::sleep(delay + jitter).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Single embedding attempt, classifying any failure as retryable or
+    /// fatal so `generate_embedding_with_retry` knows whether to try again.
+    /// Delegates the actual request/response handling to [`RestEmbedder`]
+    /// against an Ollama [`RestEmbedderDescriptor`], then reclassifies
+    /// generic REST failures into the more specific [`OllamaError`]
+    /// variants Ollama callers expect.
+    async fn try_generate_embedding(
+        &self,
+        text: &str,
+        model: &str,
+    ) -> std::result::Result<Vec<f32>, (FailureKind, anyhow::Error)> {
+        let descriptor = RestEmbedderDescriptor::ollama(&self.base_url, model, self.num_ctx);
+        RestEmbedder::new(&self.client, self.timeout)
+            .try_embed(&descriptor, text)
+            .await
+            .map_err(|(kind, err)| {
+                let err = match err {
+                    RestEmbedError::Connect => OllamaError::ServerUnavailable.into(),
+                    RestEmbedError::Timeout => OllamaError::Timeout { secs: self.timeout.as_secs() }.into(),
+                    RestEmbedError::Api { status, body } => classify_api_error(status, &body, model).into(),
+                    RestEmbedError::Other(err) => err,
+                };
+                (kind, err)
+            })
     }
 
     /// Generate an embedding with automatic chunking for long text.
     ///
     /// This function will automatically break down long text into smaller chunks
-    /// and generate embeddings for each chunk. It's useful for handling text that
-    /// might exceed the model's context window.
+    /// and generate embeddings for each chunk, dispatching up to
+    /// [`with_concurrency`](Self::with_concurrency) requests at once rather
+    /// than one at a time, while preserving the original chunk order in the
+    /// returned vector.
     pub async fn generate_embedding_chunked(
         &self,
         text: &str,
@@ -108,10 +565,11 @@ impl OllamaApiClient {
         max_chunk_size: usize,
     ) -> Result<Vec<Vec<f32>>> {
         use crate::utils::chunking::{chunk_text, ChunkerOptions, ChunkingStrategy};
+        use futures::stream::{self, StreamExt};
 
         // If text is under the limit, just generate a single embedding
         if text.len() <= max_chunk_size {
-            let embedding = self.generate_embedding(text, model).await?;
+            let (embedding, _retries) = self.generate_embedding_with_retry(text, model).await?;
             return Ok(vec![embedding]);
         }
 
@@ -122,14 +580,18 @@ impl OllamaApiClient {
         };
 
         let chunks = chunk_text(text, options);
-        let mut embeddings = Vec::with_capacity(chunks.len());
-
-        for chunk in chunks {
-            let embedding = self.generate_embedding(&chunk, model).await?;
-            embeddings.push(embedding);
-        }
 
-        Ok(embeddings)
+        stream::iter(chunks)
+            .map(|chunk| async move {
+                self.generate_embedding_with_retry(&chunk, model)
+                    .await
+                    .map(|(embedding, _retries)| embedding)
+            })
+            .buffered(self.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect()
     }
 }
 
@@ -149,5 +611,305 @@ mod tests {
         assert_eq!(client.timeout, std::time::Duration::from_secs(60));
     }
 
+    #[tokio::test]
+    async fn test_client_with_max_retries() {
+        let client = OllamaApiClient::new("http://localhost:11434").with_max_retries(5);
+        assert_eq!(client.max_retries, 5);
+    }
+
+    #[tokio::test]
+    async fn test_client_defaults_to_three_max_retries() {
+        let client = OllamaApiClient::new("http://localhost:11434");
+        assert_eq!(client.max_retries, DEFAULT_MAX_RETRIES);
+    }
+
+    #[tokio::test]
+    async fn test_client_with_concurrency() {
+        let client = OllamaApiClient::new("http://localhost:11434").with_concurrency(8);
+        assert_eq!(client.concurrency, 8);
+    }
+
+    #[tokio::test]
+    async fn test_client_defaults_to_four_concurrency() {
+        let client = OllamaApiClient::new("http://localhost:11434");
+        assert_eq!(client.concurrency, DEFAULT_CONCURRENCY);
+    }
+
+    /// Spawns a mock `/api/tags` endpoint listing `models` by name.
+    async fn spawn_mock_tags_server(models: &'static [&'static str]) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let models_json: Vec<_> = models
+                    .iter()
+                    .map(|name| serde_json::json!({ "name": name, "size": 123, "modified_at": "2024-01-01T00:00:00Z", "digest": "abc" }))
+                    .collect();
+                let body = serde_json::json!({ "models": models_json }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_list_models_parses_name_size_modified_and_digest() {
+        let endpoint = spawn_mock_tags_server(&["harald-phi4"]).await;
+        let client = OllamaApiClient::new(&endpoint);
+
+        let models = client.list_models().await.unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "harald-phi4");
+        assert_eq!(models[0].size, 123);
+        assert_eq!(models[0].modified_at, "2024-01-01T00:00:00Z");
+        assert_eq!(models[0].digest, "abc");
+    }
+
+    #[tokio::test]
+    async fn test_has_model_true_for_pulled_model() {
+        let endpoint = spawn_mock_tags_server(&["harald-phi4", "llama3"]).await;
+        let client = OllamaApiClient::new(&endpoint);
+
+        assert!(client.has_model("llama3").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_has_model_false_for_missing_model() {
+        let endpoint = spawn_mock_tags_server(&["harald-phi4"]).await;
+        let client = OllamaApiClient::new(&endpoint);
+
+        assert!(!client.has_model("missing-model").await.unwrap());
+    }
+
+    #[test]
+    fn test_jitter_ms_stays_within_bound() {
+        for _ in 0..10 {
+            assert!(jitter_ms(50) < 50);
+        }
+        assert_eq!(jitter_ms(0), 0);
+    }
+
+    /// Spawns a one-shot mock `/api/embeddings` endpoint that always
+    /// replies with `dims` zeros, for probing [`OllamaApiClient::model_dimensions`].
+    async fn spawn_mock_embedding_server(dims: usize) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let body = serde_json::json!({ "embedding": vec![0.0; dims] }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_model_dimensions_probes_then_caches() {
+        let endpoint = spawn_mock_embedding_server(4).await;
+        let client = OllamaApiClient::new(&endpoint);
+
+        assert_eq!(client.model_dimensions("some-model").await.unwrap(), 4);
+        assert_eq!(client.dimension_cache.lock().unwrap().get("some-model"), Some(&4));
+        // Cached, so this must not depend on the mock server still running.
+        assert_eq!(client.model_dimensions("some-model").await.unwrap(), 4);
+    }
+
+    #[test]
+    fn test_classify_api_error_maps_404_to_model_not_found() {
+        let err = classify_api_error(reqwest::StatusCode::NOT_FOUND, "{\"error\": \"model not found\"}", "harald-phi4");
+        assert!(matches!(err, OllamaError::ModelNotFound { model } if model == "harald-phi4"));
+    }
+
+    #[test]
+    fn test_classify_api_error_maps_503_to_server_unavailable() {
+        let err = classify_api_error(reqwest::StatusCode::SERVICE_UNAVAILABLE, "{\"error\": \"loading model\"}", "m");
+        assert!(matches!(err, OllamaError::ServerUnavailable));
+    }
+
+    #[test]
+    fn test_classify_api_error_maps_context_length_message_to_request_too_large() {
+        let err = classify_api_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            "{\"error\": \"prompt exceeds context length\"}",
+            "m",
+        );
+        assert!(matches!(err, OllamaError::RequestTooLarge));
+    }
+
+    #[tokio::test]
+    async fn test_generate_embedding_surfaces_model_not_found_as_typed_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let body = serde_json::json!({"error": "model 'missing-model' not found"}).to_string();
+                let response = format!(
+                    "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let client = OllamaApiClient::new(&format!("http://{}", addr));
+        let err = client.generate_embedding("hi", "missing-model").await.unwrap_err();
+        let ollama_err = err.downcast_ref::<OllamaError>().expect("expected a typed OllamaError");
+        assert!(matches!(ollama_err, OllamaError::ModelNotFound { model } if model == "missing-model"));
+    }
+
+    /// Spawns a mock `/api/embeddings` endpoint that replies with the
+    /// character length of the requested `prompt` as the embedding's only
+    /// element, so a test can verify `generate_embedding_chunked` preserves
+    /// chunk order even though chunks are embedded concurrently and may
+    /// complete out of order.
+    async fn spawn_mock_echo_prompt_length_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let prompt_start = request.find("\"prompt\":\"").map(|i| i + "\"prompt\":\"".len());
+                let length = prompt_start
+                    .and_then(|start| request[start..].find('"').map(|end| request[start..start + end].chars().count()))
+                    .unwrap_or(0);
+                let body = serde_json::json!({ "embedding": [length as f32] }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_generate_embedding_chunked_preserves_order_with_concurrency() {
+        use crate::utils::chunking::{chunk_text, ChunkerOptions, ChunkingStrategy};
+
+        let endpoint = spawn_mock_echo_prompt_length_server().await;
+        let client = OllamaApiClient::new(&endpoint).with_concurrency(4);
+
+        let text = "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu nu xi omicron pi";
+        let max_chunk_size = 20;
+        let expected_chunks = chunk_text(
+            text,
+            ChunkerOptions {
+                strategy: ChunkingStrategy::Character(max_chunk_size),
+                ..Default::default()
+            },
+        );
+        assert!(expected_chunks.len() > 1, "test text should actually need chunking");
+
+        let embeddings = client.generate_embedding_chunked(text, "m", max_chunk_size).await.unwrap();
+        let actual_lengths: Vec<f32> = embeddings.into_iter().map(|e| e[0]).collect();
+        let expected_lengths: Vec<f32> = expected_chunks.iter().map(|c| c.chars().count() as f32).collect();
+        assert_eq!(actual_lengths, expected_lengths);
+    }
+
+    #[test]
+    fn test_ollama_descriptor_request_body_has_model_prompt_and_num_ctx() {
+        let descriptor = RestEmbedderDescriptor::ollama("http://localhost:11434", "harald-phi4", 8192);
+        let body = descriptor.request_body("hello");
+        assert_eq!(body["model"], "harald-phi4");
+        assert_eq!(body["prompt"], "hello");
+        assert_eq!(body["options"]["num_ctx"], 8192);
+    }
+
+    #[tokio::test]
+    async fn test_client_with_num_ctx() {
+        let client = OllamaApiClient::new("http://localhost:11434").with_num_ctx(8192);
+        assert_eq!(client.num_ctx, 8192);
+    }
+
+    #[tokio::test]
+    async fn test_client_defaults_to_4096_num_ctx() {
+        let client = OllamaApiClient::new("http://localhost:11434");
+        assert_eq!(client.num_ctx, DEFAULT_NUM_CTX);
+    }
+
+    #[test]
+    fn test_openai_compatible_descriptor_request_body_batches_input() {
+        let descriptor =
+            RestEmbedderDescriptor::openai_compatible("https://api.example.com/embeddings", "text-embedding-3-small", None);
+        let body = descriptor.request_body("hello");
+        assert_eq!(body["model"], "text-embedding-3-small");
+        assert_eq!(body["input"], serde_json::json!(["hello"]));
+    }
+
+    #[test]
+    fn test_extract_embedding_follows_nested_path() {
+        let value = serde_json::json!({ "data": [{ "embedding": [1.0, 2.0, 3.0] }] });
+        let path = vec![
+            JsonPathSegment::Key("data".to_string()),
+            JsonPathSegment::Index(0),
+            JsonPathSegment::Key("embedding".to_string()),
+        ];
+        assert_eq!(extract_embedding(&value, &path).unwrap(), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_extract_embedding_errors_on_missing_field() {
+        let value = serde_json::json!({ "unexpected": true });
+        let path = vec![JsonPathSegment::Key("embedding".to_string())];
+        assert!(extract_embedding(&value, &path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rest_embedder_extracts_embedding_via_openai_compatible_descriptor() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let body = serde_json::json!({ "data": [{ "embedding": [0.5, 0.25] }] }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let descriptor =
+            RestEmbedderDescriptor::openai_compatible(&format!("http://{}/embeddings", addr), "some-model", None);
+        let client = reqwest::Client::new();
+        let embedder = RestEmbedder::new(&client, Duration::from_secs(5));
+        let embedding = embedder.embed(&descriptor, "hello").await.unwrap();
+        assert_eq!(embedding, vec![0.5, 0.25]);
+    }
+
     // Additional tests will be implemented when needed
 }
@@ -0,0 +1,280 @@
+//! Benchmark-workload runner for the embedding pipeline.
+//!
+//! Generalizes the ad-hoc `Instant::now()`/`elapsed()` timing in
+//! [`crate::ingest::single_character_ingest`] into a repeatable, JSON-driven
+//! workload: chunk a fixed set of character fixtures, embed every chunk
+//! against a model some number of times, and report latency percentiles
+//! alongside raw throughput so regressions show up as a number, not a vibe.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::ingest::chunking_utils::chunk_entity_fields;
+use crate::ingest::embed::{EmbedConfig, EmbeddingProvider, OllamaProvider};
+
+fn default_chunk_max_len() -> usize {
+    250
+}
+
+fn default_repeat() -> usize {
+    1
+}
+
+/// A benchmark workload: a model, a set of character fixtures to chunk and
+/// embed, and how many times to repeat the run.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workload {
+    /// Model name to request embeddings from.
+    pub model: String,
+
+    /// Embedding API endpoint (defaults to the local Ollama endpoint).
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    /// Single-character JSON fixtures to chunk and embed.
+    pub inputs: Vec<PathBuf>,
+
+    /// Maximum characters per chunk, per [`chunk_entity_fields`].
+    #[serde(default = "default_chunk_max_len")]
+    pub chunk_max_len: usize,
+
+    /// Number of times to repeat the whole workload.
+    #[serde(default = "default_repeat")]
+    pub repeat: usize,
+}
+
+/// Wall-clock latency for one embedded chunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkLatency {
+    pub label: String,
+    pub latency_ms: f64,
+    /// Whether the embedding request succeeded. A failed chunk's
+    /// `latency_ms` still reflects how long it took to fail, but isn't
+    /// counted in [`LatencyMetrics`].
+    pub success: bool,
+}
+
+/// Aggregate latency/throughput metrics rolled up from a run's chunk
+/// latencies.
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyMetrics {
+    pub chunk_count: usize,
+    /// Chunks whose embedding request failed and were excluded from every
+    /// other field here. A nonzero count with otherwise-clean percentiles
+    /// means the run was only partially successful.
+    pub error_count: usize,
+    pub throughput_chunks_per_sec: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+impl LatencyMetrics {
+    fn from_durations(durations: &[Duration], error_count: usize, wall_clock: Duration) -> Self {
+        if durations.is_empty() {
+            return Self {
+                chunk_count: 0,
+                error_count,
+                throughput_chunks_per_sec: 0.0,
+                mean_ms: 0.0,
+                p50_ms: 0.0,
+                p90_ms: 0.0,
+                p95_ms: 0.0,
+                p99_ms: 0.0,
+            };
+        }
+
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+
+        let mean_ms =
+            sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / sorted.len() as f64 * 1000.0;
+        let throughput_chunks_per_sec = if wall_clock.as_secs_f64() > 0.0 {
+            sorted.len() as f64 / wall_clock.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        Self {
+            chunk_count: sorted.len(),
+            error_count,
+            throughput_chunks_per_sec,
+            mean_ms,
+            p50_ms: percentile_ms(&sorted, 0.50),
+            p90_ms: percentile_ms(&sorted, 0.90),
+            p95_ms: percentile_ms(&sorted, 0.95),
+            p99_ms: percentile_ms(&sorted, 0.99),
+        }
+    }
+}
+
+/// Nearest-rank percentile over an ascending-sorted `sorted`: `ceil(q * n) -
+/// 1`, clamped to a valid index. Returns 0 for an empty slice.
+fn percentile_ms(sorted: &[Duration], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((q * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[rank].as_secs_f64() * 1000.0
+}
+
+/// A completed benchmark run: the workload that produced it, the rolled-up
+/// metrics, and each chunk's raw latency for deeper inspection.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub workload: PathBuf,
+    pub timestamp_unix_secs: u64,
+    pub metrics: LatencyMetrics,
+    pub per_chunk: Vec<ChunkLatency>,
+}
+
+/// Loads the workload at `workload_path`, embeds every chunk it describes
+/// against `client`, and returns the resulting [`BenchReport`].
+///
+/// # Errors
+/// Returns an error if the workload file or any referenced input fixture
+/// can't be read/parsed.
+pub async fn run(workload_path: &Path, client: &Client) -> Result<BenchReport> {
+    let workload: Workload = serde_json::from_str(
+        &fs::read_to_string(workload_path)
+            .with_context(|| format!("reading workload {}", workload_path.display()))?,
+    )
+    .with_context(|| format!("parsing workload {}", workload_path.display()))?;
+
+    let mut chunks: Vec<(String, String)> = Vec::new();
+    for input in &workload.inputs {
+        let content = fs::read_to_string(input)
+            .with_context(|| format!("reading input {}", input.display()))?;
+        let entity: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("parsing input {}", input.display()))?;
+        chunks.extend(chunk_entity_fields(&entity, workload.chunk_max_len));
+    }
+
+    let mut config = EmbedConfig {
+        model: workload.model.clone(),
+        ..EmbedConfig::default()
+    };
+    if let Some(endpoint) = &workload.endpoint {
+        config.endpoint = endpoint.clone();
+    }
+    let provider = OllamaProvider::new(client.clone(), config);
+
+    let mut per_chunk = Vec::new();
+    let mut durations = Vec::new();
+    let mut error_count = 0;
+    let wall_clock_start = Instant::now();
+    for round in 0..workload.repeat.max(1) {
+        for (label, text) in &chunks {
+            let label = format!("round{round}:{label}");
+            let start = Instant::now();
+            let result = provider.embed_batch(std::slice::from_ref(text), 600).await;
+            let elapsed = start.elapsed();
+            let success = match result {
+                Ok(_) => {
+                    durations.push(elapsed);
+                    true
+                }
+                Err(err) => {
+                    error_count += 1;
+                    warn!(chunk = %label, error = %err, "chunk embedding failed during bench run");
+                    false
+                }
+            };
+            per_chunk.push(ChunkLatency {
+                label,
+                latency_ms: elapsed.as_secs_f64() * 1000.0,
+                success,
+            });
+        }
+    }
+    let wall_clock = wall_clock_start.elapsed();
+
+    Ok(BenchReport {
+        workload: workload_path.to_path_buf(),
+        timestamp_unix_secs: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        metrics: LatencyMetrics::from_durations(&durations, error_count, wall_clock),
+        per_chunk,
+    })
+}
+
+/// Posts `report` to `dashboard_url` with `reason` attached as a query
+/// parameter, for dashboards that scrape bench results over HTTP.
+///
+/// # Errors
+/// Returns an error if the POST fails or the dashboard responds with a
+/// non-success status.
+pub async fn publish(
+    client: &Client,
+    dashboard_url: &str,
+    reason: &str,
+    report: &BenchReport,
+) -> Result<()> {
+    let response = client
+        .post(dashboard_url)
+        .query(&[("reason", reason)])
+        .json(report)
+        .send()
+        .await
+        .context("posting bench report to dashboard")?;
+    if !response.status().is_success() {
+        anyhow::bail!("dashboard responded with {}", response.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_empty_is_zero() {
+        assert_eq!(percentile_ms(&[], 0.95), 0.0);
+    }
+
+    #[test]
+    fn percentile_nearest_rank_clamps_to_last_index() {
+        let sorted = vec![
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            Duration::from_millis(30),
+        ];
+        assert_eq!(percentile_ms(&sorted, 0.99), 30.0);
+        assert_eq!(percentile_ms(&sorted, 0.50), 20.0);
+    }
+
+    #[test]
+    fn metrics_from_empty_durations_are_all_zero() {
+        let metrics = LatencyMetrics::from_durations(&[], 0, Duration::from_secs(1));
+        assert_eq!(metrics.chunk_count, 0);
+        assert_eq!(metrics.throughput_chunks_per_sec, 0.0);
+        assert_eq!(metrics.mean_ms, 0.0);
+    }
+
+    #[test]
+    fn metrics_compute_throughput_from_wall_clock() {
+        let durations = vec![Duration::from_millis(100), Duration::from_millis(200)];
+        let metrics = LatencyMetrics::from_durations(&durations, 0, Duration::from_secs(2));
+        assert_eq!(metrics.chunk_count, 2);
+        assert_eq!(metrics.throughput_chunks_per_sec, 1.0);
+        assert_eq!(metrics.mean_ms, 150.0);
+    }
+
+    #[test]
+    fn metrics_track_error_count_separately_from_chunk_count() {
+        let durations = vec![Duration::from_millis(100)];
+        let metrics = LatencyMetrics::from_durations(&durations, 2, Duration::from_secs(1));
+        assert_eq!(metrics.chunk_count, 1);
+        assert_eq!(metrics.error_count, 2);
+    }
+}
@@ -0,0 +1,81 @@
+//! Locates workspace binaries built by Cargo, for code that needs to shell
+//! out to another binary in this crate (e.g.
+//! [`crate::ingest::single_character_ingest`] invoking `ingest_chunked`).
+//!
+//! Replaces guessing at a fixed list of `target/{debug,release}` paths -
+//! which breaks under a different profile or a workspace-relative CWD -
+//! by asking Cargo to build the binary and reporting back the exact
+//! executable path it produced. The build result is cached per binary name
+//! so a process that resolves the same binary repeatedly (e.g. a test
+//! suite) only pays for it once.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{bail, Context, Result};
+use escargot::format::Message;
+
+/// Resolves the `ingest_chunked` binary, building it first if needed.
+///
+/// # Errors
+/// Returns an error if `cargo build` fails or doesn't emit a compiler
+/// artifact naming an executable for this target.
+pub fn resolve_ingest_chunked_bin() -> Result<PathBuf> {
+    resolve_bin("ingest_chunked")
+}
+
+/// Per-binary-name resolution cache, shared by every [`resolve_bin`] call in
+/// the process.
+fn cache() -> &'static Mutex<HashMap<String, PathBuf>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolves `bin_name`, building it via `cargo build --bin <bin_name>` on
+/// first use and caching the resulting path for the lifetime of the
+/// process. A build failure is not cached, so a transient failure (e.g. a
+/// momentary lock on the target dir) doesn't poison every later call in
+/// the same process.
+fn resolve_bin(bin_name: &str) -> Result<PathBuf> {
+    if let Some(path) = cache().lock().unwrap().get(bin_name) {
+        return Ok(path.clone());
+    }
+    let path = build_and_locate(bin_name)?;
+    cache()
+        .lock()
+        .unwrap()
+        .insert(bin_name.to_string(), path.clone());
+    Ok(path)
+}
+
+/// Runs `cargo build --message-format=json --bin <bin_name>`, matching the
+/// debug/release profile of the calling binary itself, and scans the
+/// emitted [`Message::CompilerArtifact`] stream for the artifact's
+/// executable path.
+fn build_and_locate(bin_name: &str) -> Result<PathBuf> {
+    let mut build = escargot::CargoBuild::new();
+    build = build.bin(bin_name);
+    if !cfg!(debug_assertions) {
+        build = build.release();
+    }
+    let messages = build
+        .exec()
+        .with_context(|| format!("Failed to run `cargo build --bin {bin_name}`"))?;
+
+    for message in messages {
+        let message = message.context("Failed to read cargo build message")?;
+        if let Message::CompilerArtifact(artifact) = message
+            .decode()
+            .context("Failed to decode cargo build message")?
+        {
+            if artifact.target.name == bin_name {
+                if let Some(executable) = artifact.executable {
+                    return Ok(executable.into_owned());
+                }
+            }
+        }
+    }
+
+    bail!("cargo build --bin {bin_name} did not produce an executable artifact")
+}
@@ -1,13 +1,84 @@
-use crate::ingest::chunking_utils::chunk_entity_fields;
+use crate::ingest::chunking_utils::{chunk_entity_fields, split_by_token_budget};
 use anyhow::{Context, Result};
 use clap::{Arg, Command};
 use serde_json::Value;
 use std::fs;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time;
 
 // Import our existing utilities through the crate
-use crate::core::embedding::ollama_api::OllamaApiClient;
+use crate::core::embedding::ollama_api::{jitter_ms, OllamaApiClient};
+use crate::ingest::embed::{
+    Backend, EmbedConfig, EmbeddingProvider, RestEmbedConfig, RestProvider,
+};
+
+/// Default dimensionality assumed for a `--source rest` endpoint, since
+/// there's no tags API to ask it directly. A mismatch isn't silent: every
+/// response is validated against this by [`RestProvider`] and errors
+/// clearly, so the value only needs adjusting (no flag for it yet) if the
+/// target endpoint doesn't happen to match it.
+const DEFAULT_REST_DIMENSIONS: usize = 1536;
+
+/// Default base URL for a local Ollama instance.
+const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Builds the [`EmbeddingProvider`] selected by `--source`, pointing it at
+/// `--url` (falling back to a sensible per-source default for Ollama) with
+/// `--model` and an optional `--api-key`. Keeping this dispatch in one
+/// place is what lets the rest of the pipeline depend on `EmbeddingProvider`
+/// alone rather than a specific backend.
+fn build_provider(
+    http_client: reqwest::Client,
+    source: &str,
+    url: Option<&str>,
+    model: &str,
+    api_key: Option<&str>,
+    num_ctx: usize,
+) -> Result<Arc<dyn EmbeddingProvider>> {
+    match source {
+        "ollama" => {
+            let mut config = EmbedConfig {
+                model: model.to_string(),
+                endpoint: format!("{}/api/embeddings", url.unwrap_or(DEFAULT_OLLAMA_URL)),
+                backend: Backend::Ollama,
+                ..EmbedConfig::default()
+            }
+            .with_num_ctx(num_ctx);
+            if let Some(key) = api_key {
+                config.auth_token = Some(key.to_string());
+            }
+            Ok(config.build_provider(http_client))
+        }
+        "openai" => {
+            let endpoint = url.context("--source openai requires --url")?;
+            let mut config = EmbedConfig {
+                model: model.to_string(),
+                endpoint: endpoint.to_string(),
+                backend: Backend::OpenAi,
+                ..EmbedConfig::default()
+            };
+            if let Some(key) = api_key {
+                config.auth_token = Some(key.to_string());
+            }
+            Ok(config.build_provider(http_client))
+        }
+        "rest" => {
+            let endpoint = url.context("--source rest requires --url")?;
+            let body_template = format!(r#"{{"model": "{}", "input": {{{{text}}}}}}"#, model);
+            let mut rest_config = RestEmbedConfig::new(endpoint, &body_template, "data.0.embedding")?;
+            if let Some(key) = api_key {
+                rest_config = rest_config.with_auth_token(key);
+            }
+            Ok(Arc::new(RestProvider::new(http_client, rest_config, DEFAULT_REST_DIMENSIONS)))
+        }
+        other => Err(anyhow::anyhow!(
+            "Unknown --source '{}': expected ollama, openai, or rest",
+            other
+        )),
+    }
+}
 
 /// Character data structure for Marvel character processing
 #[derive(Debug, Clone)]
@@ -74,43 +145,297 @@ impl CharacterData {
     // Remove to_chunks; use chunk_entity_fields instead
 }
 
-/// Generate embedding for a text chunk
+/// Destination for generated chunk embeddings. Each successful embedding is
+/// appended to `path` as a JSONL record `{name, label, content, embedding,
+/// dims}`, turning the tool from a connectivity tester into an actual
+/// ingestion pipeline.
+///
+/// The dimensionality of the first embedding written is inferred and locked
+/// in; every later vector is checked against it, so a model/provider
+/// mix-up mid-run errors clearly instead of producing a mismatched index.
+struct EmbeddingSink {
+    writer: std::io::BufWriter<fs::File>,
+    dims: Option<usize>,
+}
+
+impl EmbeddingSink {
+    /// Opens `path` for writing, truncating it unless `append` is set.
+    fn open(path: &str, append: bool) -> Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Failed to open embedding output file: {}", path))?;
+        Ok(Self { writer: std::io::BufWriter::new(file), dims: None })
+    }
+
+    /// Validates `embedding`'s dimensionality against the first vector
+    /// written (inferring it if this is the first) and appends a JSONL
+    /// record for `chunk`.
+    fn write(&mut self, character_name: &str, chunk: &CharacterChunk, embedding: &[f32]) -> Result<()> {
+        match self.dims {
+            None => self.dims = Some(embedding.len()),
+            Some(dims) if dims != embedding.len() => {
+                return Err(anyhow::anyhow!(
+                    "Embedding dimension mismatch: expected {} (inferred from the first embedding written), got {}",
+                    dims,
+                    embedding.len()
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let record = serde_json::json!({
+            "name": character_name,
+            "label": chunk.label,
+            "content": chunk.content,
+            "embedding": embedding,
+            "dims": embedding.len(),
+        });
+        writeln!(self.writer, "{}", record).context("Failed to write embedding record")?;
+        Ok(())
+    }
+}
+
+/// Token-bucket rate limiter shared across concurrent embedding requests.
+/// `acquire` blocks (via async sleep, not a busy loop) until a token is
+/// available, refilling at `rate_per_sec` up to a one-second burst
+/// capacity. Used in place of a blanket per-chunk sleep so throughput
+/// adapts to how fast the backend actually responds instead of being
+/// capped at a fixed interval.
+struct TokenBucket {
+    state: Mutex<TokenBucketState>,
+}
+
+struct TokenBucketState {
+    capacity: f64,
+    tokens: f64,
+    rate_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_per_sec: f64) -> Self {
+        let capacity = rate_per_sec.max(1.0);
+        Self {
+            state: Mutex::new(TokenBucketState {
+                capacity,
+                tokens: capacity,
+                rate_per_sec,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let elapsed = state.last_refill.elapsed().as_secs_f64();
+                state.tokens = (state.tokens + elapsed * state.rate_per_sec).min(state.capacity);
+                state.last_refill = Instant::now();
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    None
+                } else {
+                    Some(Duration::from_secs_f64(
+                        (1.0 - state.tokens) / state.rate_per_sec,
+                    ))
+                }
+            };
+            match wait {
+                None => return,
+                Some(d) => time::sleep(d).await,
+            }
+        }
+    }
+}
+
+/// Retries `provider.embed_batch` for a single text up to `max_retries`
+/// times with exponential backoff plus jitter. Unlike
+/// `OllamaApiClient::generate_embedding_with_retry`, `EmbeddingProvider`
+/// carries no retryable-vs-fatal distinction across its backends, so every
+/// failure here is treated as retryable - a persistently fatal error (e.g.
+/// a bad API key) just spends its retries before surfacing, rather than
+/// failing fast.
+async fn embed_with_retry(
+    provider: &dyn EmbeddingProvider,
+    text: &str,
+    max_tokens: usize,
+    max_retries: usize,
+    retry_base_delay: Duration,
+) -> Result<(Vec<f32>, usize)> {
+    let mut attempt = 0;
+    loop {
+        match provider.embed_batch(std::slice::from_ref(&text.to_string()), max_tokens).await {
+            Ok(mut embeddings) if !embeddings.is_empty() => {
+                return Ok((embeddings.remove(0), attempt));
+            }
+            Ok(_) => return Err(anyhow::anyhow!("Embedding provider returned no vectors")),
+            Err(err) => {
+                if attempt >= max_retries {
+                    return Err(err.context(format!("Exhausted {max_retries} retries")));
+                }
+                let delay = retry_base_delay * 2u32.pow(attempt as u32);
+                let jitter = Duration::from_millis(jitter_ms((delay.as_millis() as u64 / 4).max(1)));
+                time::sleep(delay + jitter).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Generate embedding for a text chunk, persisting it to `sink` on success.
+/// Retries failures up to `max_retries` times with exponential backoff plus
+/// jitter before giving up. Returns whether the chunk ultimately
+/// succeeded, together with how many retries it took, so the caller can
+/// report per-chunk retry counts.
 async fn generate_embedding(
-    client: &OllamaApiClient,
+    provider: &dyn EmbeddingProvider,
     chunk: &CharacterChunk,
-    model: &str,
-) -> Result<bool> {
+    character_name: &str,
+    max_tokens: usize,
+    sink: &Arc<Mutex<EmbeddingSink>>,
+    max_retries: usize,
+    retry_base_delay: Duration,
+) -> Result<(bool, usize)> {
     let text = format!("{}: {}", chunk.label, chunk.content);
 
     println!("🔄 Generating embedding for text ({} chars)", text.len());
 
-    // If text is too large, warn but proceed
-    const MAX_CHUNK_SIZE: usize = 250;
-    if text.len() > MAX_CHUNK_SIZE {
-        println!(
-            "⚠️  Text exceeds recommended size of {} chars",
-            MAX_CHUNK_SIZE
-        );
-        println!("   Will process in smaller chunks");
-    }
-
     let start_time = Instant::now();
 
-    match client.generate_embedding(&text, model).await {
-        Ok(embedding) => {
+    match embed_with_retry(provider, &text, max_tokens, max_retries, retry_base_delay).await {
+        Ok((embedding, retries)) => {
             let elapsed = start_time.elapsed();
             println!(
-                "✅ Success - Embedding generated in {:.2}s",
-                elapsed.as_secs_f64()
+                "✅ Success - Embedding generated in {:.2}s ({} retries)",
+                elapsed.as_secs_f64(),
+                retries
             );
             println!("   Vector dimensions: {}", embedding.len());
-            Ok(true)
+            sink.lock().unwrap().write(character_name, chunk, &embedding)?;
+            Ok((true, retries))
         }
         Err(e) => {
-            println!("❌ Failed - {}", e);
-            Ok(false)
+            println!("❌ Failed after {} retries - {}", max_retries, e);
+            Ok((false, max_retries))
+        }
+    }
+}
+
+/// Dispatches `generate_embedding` over `chunks` with up to `concurrency`
+/// requests in flight at once (via a `tokio::sync::Semaphore`), optionally
+/// throttled by a shared `rate_limiter`. Replaces the old fixed 2-second
+/// sleep between chunks, which capped every run at one request per 2
+/// seconds regardless of how fast the backend could actually respond.
+/// Returns `(successful, failed, total_retries)` tallied as tasks complete,
+/// so the counts stay accurate no matter what order that happens in.
+async fn embed_chunks_concurrently(
+    provider: Arc<dyn EmbeddingProvider>,
+    character_name: &str,
+    chunks: Vec<CharacterChunk>,
+    max_tokens: usize,
+    sink: Arc<Mutex<EmbeddingSink>>,
+    max_retries: usize,
+    retry_base_delay: Duration,
+    concurrency: usize,
+    rate_limiter: Option<Arc<TokenBucket>>,
+) -> (usize, usize, usize) {
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinSet;
+
+    let total = chunks.len();
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let provider = provider.clone();
+        let sink = sink.clone();
+        let semaphore = semaphore.clone();
+        let rate_limiter = rate_limiter.clone();
+        let character_name = character_name.to_string();
+
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+            if let Some(limiter) = &rate_limiter {
+                limiter.acquire().await;
+            }
+            println!("--------------------------------------------");
+            println!(
+                "🔄 Processing chunk {}/{}: {}...",
+                i + 1,
+                total,
+                chunk.content.chars().take(30).collect::<String>()
+            );
+            generate_embedding(
+                provider.as_ref(),
+                &chunk,
+                &character_name,
+                max_tokens,
+                &sink,
+                max_retries,
+                retry_base_delay,
+            )
+            .await
+        });
+    }
+
+    let mut successful = 0;
+    let mut failed = 0;
+    let mut total_retries = 0;
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Ok((true, retries))) => {
+                successful += 1;
+                total_retries += retries;
+            }
+            Ok(Ok((false, retries))) => {
+                failed += 1;
+                total_retries += retries;
+            }
+            Ok(Err(e)) => {
+                println!("❌ Chunk embedding task failed: {}", e);
+                failed += 1;
+            }
+            Err(e) => {
+                println!("❌ Chunk embedding task panicked: {}", e);
+                failed += 1;
+            }
         }
     }
+
+    (successful, failed, total_retries)
+}
+
+/// Splits each of `chunks` further so its content fits within `num_ctx`
+/// tokens (per [`estimate_tokens`]), recursing on sentence/word boundaries
+/// via [`split_by_token_budget`]. A field that needed splitting gets its
+/// label suffixed with `(part i/n)` so the pieces stay distinguishable in
+/// the output. Replaces the old print-only "exceeds recommended size"
+/// warning, which never actually re-split anything.
+fn token_aware_chunks(chunks: Vec<CharacterChunk>, num_ctx: usize) -> Vec<CharacterChunk> {
+    chunks
+        .into_iter()
+        .flat_map(|chunk| {
+            let pieces = split_by_token_budget(&chunk.content, num_ctx);
+            let total = pieces.len();
+            pieces
+                .into_iter()
+                .enumerate()
+                .map(move |(i, content)| {
+                    let label = if total > 1 {
+                        format!("{} (part {}/{})", chunk.label, i + 1, total)
+                    } else {
+                        chunk.label.clone()
+                    };
+                    CharacterChunk { label, content }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
 /// Main entry point for the chunked ingestion tool
@@ -136,10 +461,100 @@ async fn main() -> Result<()> {
                 .help("Ollama model to use for embeddings")
                 .default_value("harald-phi4"),
         )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("JSONL file to write generated embeddings to")
+                .default_value("embeddings.jsonl"),
+        )
+        .arg(
+            Arg::new("append")
+                .long("append")
+                .help("Append to the output file instead of truncating it")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("max-retries")
+                .long("max-retries")
+                .value_name("N")
+                .help("Maximum retries for a retryable embedding failure before giving up")
+                .default_value("3")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("retry-base-delay")
+                .long("retry-base-delay")
+                .value_name("SECONDS")
+                .help("Base delay before the first retry; doubles on each subsequent retry")
+                .default_value("1")
+                .value_parser(clap::value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("concurrency")
+                .long("concurrency")
+                .value_name("N")
+                .help("Maximum number of embedding requests to have in flight at once")
+                .default_value("1")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("rate-per-sec")
+                .long("rate-per-sec")
+                .value_name("RATE")
+                .help("Cap throughput to roughly this many requests per second (unlimited if omitted)")
+                .value_parser(clap::value_parser!(f64)),
+        )
+        .arg(
+            Arg::new("num-ctx")
+                .long("num-ctx")
+                .value_name("TOKENS")
+                .help("Model context window in tokens; chunks are recursively split to fit within it")
+                .default_value("4096")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("source")
+                .long("source")
+                .value_name("SOURCE")
+                .help("Embedding backend to use")
+                .value_parser(["ollama", "openai", "rest"])
+                .default_value("ollama"),
+        )
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .value_name("URL")
+                .help("Embedding endpoint URL (defaults to a local Ollama instance; required for --source openai/rest)"),
+        )
+        .arg(
+            Arg::new("api-key")
+                .long("api-key")
+                .value_name("KEY")
+                .help("Bearer token sent with every embedding request, if the source needs one"),
+        )
         .get_matches();
 
     let file_path = matches.get_one::<String>("file").unwrap();
     let model = matches.get_one::<String>("model").unwrap();
+    let output_path = matches.get_one::<String>("output").unwrap();
+    let append = matches.get_flag("append");
+    let max_retries = *matches.get_one::<usize>("max-retries").unwrap();
+    let retry_base_delay = Duration::from_secs(*matches.get_one::<u64>("retry-base-delay").unwrap());
+    let concurrency = *matches.get_one::<usize>("concurrency").unwrap();
+    let rate_limiter = matches
+        .get_one::<f64>("rate-per-sec")
+        .filter(|rate| **rate > 0.0)
+        .map(|rate| Arc::new(TokenBucket::new(*rate)));
+    let num_ctx = *matches.get_one::<usize>("num-ctx").unwrap();
+    let source = matches.get_one::<String>("source").unwrap();
+    let url = matches.get_one::<String>("url").map(|s| s.as_str());
+    let api_key = matches.get_one::<String>("api-key").map(|s| s.as_str());
+
+    let sink = Arc::new(Mutex::new(EmbeddingSink::open(output_path, append)?));
+    let http_client = reqwest::Client::new();
+    let provider = build_provider(http_client, source, url, model, api_key, num_ctx)?;
 
     // Display start banner
     println!("==================================================");
@@ -151,28 +566,54 @@ async fn main() -> Result<()> {
     );
     println!("==================================================");
 
-    // Initialize Ollama client
-    let client = OllamaApiClient::new("http://localhost:11434");
+    // The Ollama source can check both connectivity and whether the
+    // requested model is actually pulled before we commit to a full
+    // ingestion run - a missing model would otherwise fail every single
+    // chunk with the same opaque error. Other sources have no equivalent
+    // tags API, so they fall through to the generic probe below.
+    if source == "ollama" {
+        let ollama_client = OllamaApiClient::new(url.unwrap_or(DEFAULT_OLLAMA_URL));
 
-    // Check Ollama API status
-    println!("🔍 Checking Ollama API status...");
-    match client.check_status().await {
-        Ok(_) => println!("✅ Ollama API is available"),
-        Err(e) => {
-            println!("❌ Ollama API is not responding: {}", e);
-            println!("Please check if the service is running properly.");
-            return Ok(());
+        println!("🔍 Checking Ollama API status...");
+        match ollama_client.check_status().await {
+            Ok(_) => println!("✅ Ollama API is available"),
+            Err(e) => {
+                println!("❌ Ollama API is not responding: {}", e);
+                println!("Please check if the service is running properly.");
+                return Ok(());
+            }
+        }
+
+        println!("🔍 Checking that model '{}' is available...", model);
+        match ollama_client.list_models().await {
+            Ok(models) => {
+                if !models.iter().any(|m| m.name == *model) {
+                    println!("❌ Model '{}' is not pulled in Ollama.", model);
+                    println!("   Available models:");
+                    for m in &models {
+                        println!("     - {}", m.name);
+                    }
+                    println!("   Run `ollama pull {}` and try again.", model);
+                    return Ok(());
+                }
+                println!("✅ Model '{}' is available", model);
+            }
+            Err(e) => {
+                println!("❌ Failed to fetch model list: {}", e);
+                println!("Please check Ollama service status");
+                return Ok(());
+            }
         }
     }
 
-    // Test API with minimal request
+    // Test the selected provider with a minimal request before committing
+    // to a full run.
     println!("🧪 Testing API with minimal request...");
-
-    match client.generate_embedding("test", model).await {
+    match provider.embed_batch(&["test".to_string()], num_ctx).await {
         Ok(_) => println!("✅ API working correctly"),
         Err(e) => {
             println!("❌ API test failed: {}", e);
-            println!("Please check Ollama service status");
+            println!("Please check the embedding service status");
             return Ok(());
         }
     }
@@ -186,6 +627,7 @@ async fn main() -> Result<()> {
     let mut total_chunks = 0;
     let mut total_successful_chunks = 0;
     let mut total_failed_chunks = 0;
+    let mut total_retries = 0;
     let mut processed_characters = 0;
 
     let mut any_valid_character = false;
@@ -223,28 +665,32 @@ async fn main() -> Result<()> {
             .into_iter()
             .map(|(label, content)| CharacterChunk { label, content })
             .collect();
+        let chunks = token_aware_chunks(chunks, num_ctx);
         println!("🔄 Processing {} chunks for '{}'...", chunks.len(), character.character_name);
         total_chunks += chunks.len();
-        for (i, chunk) in chunks.iter().enumerate() {
-            println!("--------------------------------------------");
-            println!(
-                "🔄 Processing chunk {}/{}: {}...",
-                i + 1,
-                chunks.len(),
-                chunk.content.chars().take(30).collect::<String>()
-            );
-            match generate_embedding(&client, chunk, model).await {
-                Ok(true) => {
-                    total_successful_chunks += 1;
-                }
-                Ok(false) | Err(_) => {
-                    total_failed_chunks += 1;
-                }
-            }
-            time::sleep(Duration::from_secs(2)).await;
-        }
+        let (successful, failed, retries) = embed_chunks_concurrently(
+            provider.clone(),
+            &character.character_name,
+            chunks,
+            num_ctx,
+            sink.clone(),
+            max_retries,
+            retry_base_delay,
+            concurrency,
+            rate_limiter.clone(),
+        )
+        .await;
+        total_successful_chunks += successful;
+        total_failed_chunks += failed;
+        total_retries += retries;
         total_characters += 1;
     }
+    sink.lock()
+        .unwrap()
+        .writer
+        .flush()
+        .context("Failed to flush embedding output file")?;
+
     if !any_valid_character {
         println!("❌ No valid character data found in file. Please check your JSONL input format.");
         return Ok(());
@@ -262,6 +708,8 @@ async fn main() -> Result<()> {
         println!("   Successful chunks: {}", total_successful_chunks);
         println!("   Failed chunks: {}", total_failed_chunks);
         println!("   Total chunks: {}", total_chunks);
+        println!("   Total retries: {}", total_retries);
+        println!("   Embeddings written to: {}", output_path);
         let success_rate = if total_chunks > 0 {
             (total_successful_chunks as f64 / total_chunks as f64) * 100.0
         } else {
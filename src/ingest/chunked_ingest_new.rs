@@ -6,31 +6,67 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 
 use crate::ingest::chunking_utils::chunk_entity_fields;
-use crate::core::embedding::ollama_api::OllamaApiClient;
-
-/// Configuration for chunked ingestion processing
-#[derive(Debug, Clone)]
+use crate::ingest::embed::{EmbedConfig, EmbeddingProvider, OllamaProvider};
+use crate::ingest::vector_store::{self, VectorStore};
+use crate::ingest::ChunkMeta;
+
+/// Number of pending chunks accumulated (across characters) before a
+/// batch is flushed to the embedding provider, absent an explicit
+/// `ChunkedIngestConfig::batch_size`. Mirrors the main ingest pipeline's
+/// `EMBED_BATCH_SIZE` convention.
+const DEFAULT_BATCH_SIZE: usize = 16;
+
+/// Maximum number of batches embedded concurrently, absent an explicit
+/// `ChunkedIngestConfig::max_concurrency`.
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// Configuration for chunked ingestion processing.
+#[derive(Clone)]
 pub struct ChunkedIngestConfig {
-    /// Model name for embeddings
-    pub model_name: String,
-    /// Maximum chunk size in characters
+    /// Maximum chunk size in characters.
     pub max_chunk_size: usize,
-    /// Ollama API endpoint
-    pub api_endpoint: String,
-    /// Timeout for API requests in seconds
-    pub timeout_secs: u64,
+    /// Embedding backend used for every chunk in this run. Defaults to a
+    /// local `OllamaProvider`; swap in `embed::OpenAiProvider` (or any
+    /// other implementor) to embed against a hosted model instead, with
+    /// no changes to `process_file`.
+    pub provider: Arc<dyn EmbeddingProvider>,
+    /// Number of pending chunks accumulated across characters before a
+    /// batch is flushed to the provider.
+    pub batch_size: usize,
+    /// Maximum number of batches embedded concurrently against the
+    /// provider.
+    pub max_concurrency: usize,
+    /// Vector store to index successfully-embedded chunks into, as a
+    /// `from_addr`-style URI (e.g. `hnsw:./data/chunked`, `memory:`).
+    /// `None` discards the embeddings after reporting on them, as before
+    /// this field existed.
+    pub store_addr: Option<String>,
+}
+
+impl std::fmt::Debug for ChunkedIngestConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkedIngestConfig")
+            .field("max_chunk_size", &self.max_chunk_size)
+            .field("provider_dimensions", &self.provider.dimensions())
+            .field("batch_size", &self.batch_size)
+            .field("max_concurrency", &self.max_concurrency)
+            .finish()
+    }
 }
 
 impl Default for ChunkedIngestConfig {
     fn default() -> Self {
         Self {
-            model_name: "harald-phi4".to_string(),
             max_chunk_size: 250,
-            api_endpoint: "http://localhost:11434".to_string(),
-            timeout_secs: 30,
+            provider: Arc::new(OllamaProvider::new(reqwest::Client::new(), EmbedConfig::default())),
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            store_addr: None,
         }
     }
 }
@@ -46,6 +82,9 @@ pub struct ChunkedIngestResult {
     pub embeddings_generated: usize,
     /// Number of failed embedding attempts
     pub failed_embeddings: usize,
+    /// Number of embedded chunks written to `ChunkedIngestConfig::store_addr`.
+    /// Zero when `store_addr` is `None`.
+    pub indexed_chunks: usize,
     /// Processing time in seconds
     pub processing_time_secs: f64,
     /// Success status
@@ -174,23 +213,16 @@ pub struct CharacterChunk {
 /// Returns a `ChunkedIngestResult` with processing statistics and status.
 pub async fn process_file(file_path: &str, config: &ChunkedIngestConfig) -> Result<ChunkedIngestResult> {
     let start_time = Instant::now();
-    
-    // Initialize Ollama client
-    let client = OllamaApiClient::new(&config.api_endpoint);
-
-    // Check API status
-    client.check_status().await
-        .context("Ollama API is not responding")?;
-
-    // Test API with minimal request
-    client.generate_embedding("test", &config.model_name).await
-        .context("API test failed")?;
 
     // Read and process the input file as JSONL
     let file_content = fs::read_to_string(file_path)
         .with_context(|| format!("Failed to read file: {}", file_path))?;
 
     let mut stats = ProcessingStats::new();
+    // Chunks accumulate across every character in the file so that the
+    // embedding step below can flush them in provider-sized batches
+    // instead of one `embed_batch` call per chunk.
+    let mut pending_chunks: Vec<CharacterChunk> = Vec::new();
 
     for (line_num, line) in file_content.lines().enumerate() {
         let line = line.trim();
@@ -222,15 +254,31 @@ pub async fn process_file(file_path: &str, config: &ChunkedIngestConfig) -> Resu
             continue;
         }
 
-        // Process this character
-        let result = process_character(&character_json, &character, &client, config).await?;
-        stats.accumulate(&result);
+        stats.characters_processed += 1;
+        pending_chunks.extend(chunk_character(&character_json, config.max_chunk_size));
     }
 
     if stats.characters_processed == 0 {
         return Err(anyhow::anyhow!("No valid characters found in file"));
     }
 
+    stats.chunks_created += pending_chunks.len();
+    let embedded = embed_chunks_in_batches(
+        config.provider.clone(),
+        &pending_chunks,
+        config.max_chunk_size,
+        config.batch_size,
+        config.max_concurrency,
+    )
+    .await;
+    stats.embeddings_generated += embedded.iter().filter(|e| e.is_some()).count();
+    stats.failed_embeddings += embedded.iter().filter(|e| e.is_none()).count();
+
+    let indexed_chunks = match &config.store_addr {
+        Some(addr) => index_chunks(addr, file_path, &pending_chunks, &embedded)?,
+        None => 0,
+    };
+
     let processing_time = start_time.elapsed().as_secs_f64();
 
     Ok(ChunkedIngestResult {
@@ -238,12 +286,92 @@ pub async fn process_file(file_path: &str, config: &ChunkedIngestConfig) -> Resu
         chunks_created: stats.chunks_created,
         embeddings_generated: stats.embeddings_generated,
         failed_embeddings: stats.failed_embeddings,
+        indexed_chunks,
         processing_time_secs: processing_time,
         success: true,
         error: None,
     })
 }
 
+/// Inserts every successfully-embedded chunk into the vector store at
+/// `addr`, then persists the store and a `meta.json` sidecar (mirroring
+/// [`crate::ingest::ingest::run_with_config`]'s on-disk layout) to the
+/// directory named after `addr`'s scheme-prefix. Returns how many chunks
+/// were actually indexed.
+///
+/// # Errors
+/// Returns an error if `addr` names an unknown backend or the store can't
+/// be persisted.
+fn index_chunks(
+    addr: &str,
+    file_path: &str,
+    chunks: &[CharacterChunk],
+    embedded: &[Option<Vec<f32>>],
+) -> Result<usize> {
+    let mut store = vector_store::from_addr(addr)?;
+    let dir_suffix = addr.splitn(2, ':').nth(1).unwrap_or(addr);
+    let dir = if dir_suffix.is_empty() {
+        PathBuf::from(".")
+    } else {
+        PathBuf::from(dir_suffix)
+    };
+
+    // `chunk_field` splits each source field into contiguous, non-overlapping
+    // char ranges and `chunk_entity_fields` preserves that order, so the
+    // offset of a chunk within its field is just the running length of the
+    // same-labelled chunks seen before it.
+    let mut char_offset = 0;
+    let mut prev_label: Option<&str> = None;
+    let mut offsets = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+        if prev_label != Some(chunk.label.as_str()) {
+            char_offset = 0;
+        }
+        let char_start = char_offset;
+        let char_end = char_start + chunk.content.chars().count();
+        offsets.push((char_start, char_end));
+        char_offset = char_end;
+        prev_label = Some(chunk.label.as_str());
+    }
+
+    // `store.insert` ids must line up with `chunk_metadata`'s final indices,
+    // since query.rs looks up a search hit's metadata as `metadata[id]` -
+    // so the id is assigned post-filter, not from the raw chunk position.
+    let mut chunk_metadata = Vec::new();
+    for ((chunk, embedding), (char_start, char_end)) in
+        chunks.iter().zip(embedded.iter()).zip(offsets.into_iter())
+    {
+        let Some(embedding) = embedding else {
+            continue;
+        };
+        let id = chunk_metadata.len();
+        store.insert(id, embedding)?;
+        chunk_metadata.push(ChunkMeta {
+            path: PathBuf::from(file_path),
+            field: chunk.label.clone(),
+            char_start,
+            char_end,
+            label: Some(chunk.label.clone()),
+            record_index: None,
+        });
+    }
+
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+    store
+        .persist(&dir)
+        .with_context(|| format!("Failed to persist vector store to {}", dir.display()))?;
+    let metadata_path = dir.join("meta.json");
+    fs::write(
+        &metadata_path,
+        serde_json::to_vec(&chunk_metadata)
+            .context("Failed to serialize chunk metadata to JSON")?,
+    )
+    .with_context(|| format!("Failed to write metadata to: {}", metadata_path.display()))?;
+
+    Ok(chunk_metadata.len())
+}
+
 /// Internal statistics tracking
 struct ProcessingStats {
     characters_processed: usize,
@@ -261,55 +389,75 @@ impl ProcessingStats {
             failed_embeddings: 0,
         }
     }
-
-    fn accumulate(&mut self, result: &CharacterProcessingResult) {
-        self.characters_processed += 1;
-        self.chunks_created += result.chunks_created;
-        self.embeddings_generated += result.embeddings_generated;
-        self.failed_embeddings += result.failed_embeddings;
-    }
 }
 
-/// Result of processing a single character
-struct CharacterProcessingResult {
-    chunks_created: usize,
-    embeddings_generated: usize,
-    failed_embeddings: usize,
-}
-
-/// Process a single character entry
-async fn process_character(
-    character_json: &Value,
-    character: &CharacterData,
-    client: &OllamaApiClient,
-    config: &ChunkedIngestConfig,
-) -> Result<CharacterProcessingResult> {
-    // Create chunks for this character
-    let chunks: Vec<CharacterChunk> = chunk_entity_fields(character_json, config.max_chunk_size)
+/// Chunks a single character entry's fields into embeddable spans.
+fn chunk_character(character_json: &Value, max_chunk_size: usize) -> Vec<CharacterChunk> {
+    chunk_entity_fields(character_json, max_chunk_size)
         .into_iter()
         .map(|(label, content)| CharacterChunk { label, content })
-        .collect();
+        .collect()
+}
 
-    let mut result = CharacterProcessingResult {
-        chunks_created: chunks.len(),
-        embeddings_generated: 0,
-        failed_embeddings: 0,
-    };
+/// Embeds `chunks` in `batch_size`-sized groups, dispatching up to
+/// `max_concurrency` batches to the provider at once, and reassembles the
+/// outcomes in the original chunk order. A batch that fails outright
+/// leaves every chunk it covered as `None` without aborting any other
+/// in-flight or still-pending batch.
+async fn embed_chunks_in_batches(
+    provider: Arc<dyn EmbeddingProvider>,
+    chunks: &[CharacterChunk],
+    max_tokens: usize,
+    batch_size: usize,
+    max_concurrency: usize,
+) -> Vec<Option<Vec<f32>>> {
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinSet;
+
+    if chunks.is_empty() {
+        return Vec::new();
+    }
+
+    let batch_size = batch_size.max(1);
+    let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for batch_start in (0..chunks.len()).step_by(batch_size) {
+        let batch_end = (batch_start + batch_size).min(chunks.len());
+        let texts: Vec<String> = chunks[batch_start..batch_end]
+            .iter()
+            .map(|chunk| chunk.content.clone())
+            .collect();
+        let provider = provider.clone();
+        let semaphore = semaphore.clone();
+
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+            let outcome = provider.embed_batch(&texts, max_tokens).await;
+            (batch_start, batch_end, outcome)
+        });
+    }
 
-    // Process each chunk
-    for chunk in &chunks {
-        match client.generate_embedding(&chunk.content, &config.model_name).await {
-            Ok(_) => {
-                result.embeddings_generated += 1;
+    let mut results: Vec<Option<Vec<f32>>> = vec![None; chunks.len()];
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((batch_start, batch_end, outcome)) = joined else {
+            continue;
+        };
+        match outcome {
+            Ok(embeddings) => {
+                for (offset, embedding) in embeddings.into_iter().enumerate() {
+                    if let Some(slot) = results.get_mut(batch_start + offset) {
+                        *slot = Some(embedding);
+                    }
+                }
             }
             Err(e) => {
-                eprintln!("❌ Failed to generate embedding for chunk '{}': {}", chunk.label, e);
-                result.failed_embeddings += 1;
+                eprintln!("❌ Failed to embed batch {}-{}: {}", batch_start, batch_end, e);
             }
         }
     }
 
-    Ok(result)
+    results
 }
 
 /// Validate a character JSON entry
@@ -343,10 +491,99 @@ mod tests {
     #[test]
     fn test_chunked_ingest_config_default() {
         let config = ChunkedIngestConfig::default();
-        assert_eq!(config.model_name, "harald-phi4");
         assert_eq!(config.max_chunk_size, 250);
-        assert_eq!(config.api_endpoint, "http://localhost:11434");
-        assert_eq!(config.timeout_secs, 30);
+        assert!(config.provider.dimensions() > 0);
+        assert_eq!(config.batch_size, DEFAULT_BATCH_SIZE);
+        assert_eq!(config.max_concurrency, DEFAULT_MAX_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn test_embed_chunks_in_batches_reassembles_in_order() {
+        use crate::ingest::embed::DummyProvider;
+
+        let chunks = vec![
+            CharacterChunk { label: "a".to_string(), content: "a".to_string() },
+            CharacterChunk { label: "bb".to_string(), content: "bb".to_string() },
+            CharacterChunk { label: "ccc".to_string(), content: "ccc".to_string() },
+        ];
+        let provider: Arc<dyn EmbeddingProvider> = Arc::new(DummyProvider::new(4));
+
+        // batch_size of 2 forces two batches; max_concurrency of 2 lets
+        // both run at once, so completion order isn't guaranteed.
+        let results = embed_chunks_in_batches(provider, &chunks, 100, 2, 2).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()[0], 1.0);
+        assert_eq!(results[1].as_ref().unwrap()[0], 2.0);
+        assert_eq!(results[2].as_ref().unwrap()[0], 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_process_file_batches_chunks_across_characters() {
+        use crate::ingest::embed::DummyProvider;
+
+        let tmp_dir = std::env::temp_dir();
+        let file_path = tmp_dir.join(format!(
+            "chunked_ingest_new_test_{}.jsonl",
+            std::process::id()
+        ));
+        let content = format!(
+            "{}\n{}\n",
+            json!({"character_name": "Vision", "description": "An android created by Ultron"}),
+            json!({"character_name": "Stratia", "description": "A tactician and strategist"}),
+        );
+        fs::write(&file_path, content).unwrap();
+
+        let config = ChunkedIngestConfig {
+            provider: Arc::new(DummyProvider::new(4)),
+            batch_size: 1,
+            max_concurrency: 2,
+            ..ChunkedIngestConfig::default()
+        };
+
+        let result = process_file(file_path.to_str().unwrap(), &config).await.unwrap();
+        fs::remove_file(&file_path).ok();
+
+        assert_eq!(result.characters_processed, 2);
+        assert_eq!(result.embeddings_generated, result.chunks_created);
+        assert_eq!(result.failed_embeddings, 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_file_indexes_chunks_into_configured_store() {
+        use crate::ingest::embed::DummyProvider;
+
+        let tmp_dir = std::env::temp_dir().join(format!(
+            "chunked_ingest_new_store_test_{}",
+            std::process::id()
+        ));
+        let input_path = tmp_dir.join("input.jsonl");
+        fs::create_dir_all(&tmp_dir).unwrap();
+        fs::write(
+            &input_path,
+            format!(
+                "{}\n",
+                json!({"character_name": "Vision", "description": "An android created by Ultron"})
+            ),
+        )
+        .unwrap();
+
+        let config = ChunkedIngestConfig {
+            provider: Arc::new(DummyProvider::new(4)),
+            store_addr: Some(format!("memory:{}", tmp_dir.display())),
+            ..ChunkedIngestConfig::default()
+        };
+
+        let result = process_file(input_path.to_str().unwrap(), &config)
+            .await
+            .unwrap();
+
+        assert_eq!(result.indexed_chunks, result.embeddings_generated);
+        let metadata: Vec<ChunkMeta> =
+            serde_json::from_str(&fs::read_to_string(tmp_dir.join("meta.json")).unwrap()).unwrap();
+        assert_eq!(metadata.len(), result.indexed_chunks);
+
+        fs::remove_dir_all(&tmp_dir).ok();
     }
 
     #[test]
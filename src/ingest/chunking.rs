@@ -0,0 +1,231 @@
+//! Token-budgeted chunking for the main ingest pipeline.
+//!
+//! Code is split along coarse syntax-node boundaries (blank lines
+//! observed at zero brace depth) and prose is split along paragraph
+//! breaks; either way, adjacent small nodes are then packed greedily up
+//! to a token budget rather than being cut at a fixed character count.
+
+/// A chunk of text ready for embedding, with its char offsets within the
+/// original field/file text it was extracted from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkSpan {
+    /// Start offset, in chars, within the source text.
+    pub char_start: usize,
+    /// End offset, in chars, within the source text.
+    pub char_end: usize,
+    /// The chunk's text.
+    pub text: String,
+}
+
+/// Approximates a real tokenizer's token count by splitting on
+/// whitespace and punctuation runs, mirroring how BPE tokenizers
+/// typically split punctuation from adjacent words. This tracks actual
+/// token budgets far more closely than a raw character count, without
+/// pulling in a full tokenizer implementation.
+pub fn approx_token_count(text: &str) -> usize {
+    let mut count = 0usize;
+    let mut in_word = false;
+    for ch in text.chars() {
+        if ch.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else if ch.is_whitespace() {
+            in_word = false;
+        } else {
+            count += 1;
+            in_word = false;
+        }
+    }
+    count.max(1)
+}
+
+/// Splits `text` into token-budgeted chunks.
+///
+/// Code (`is_code = true`) is split at blank-line boundaries observed at
+/// zero brace depth, approximating top-level syntax node boundaries
+/// without a full parser; prose is split at paragraph breaks. Either
+/// way, adjacent small nodes are merged greedily until the next node
+/// would push a chunk over `max_tokens`, and any single node still too
+/// large on its own is force-split on word boundaries so no emitted
+/// chunk exceeds the budget.
+pub fn chunk_text(text: &str, is_code: bool, max_tokens: usize) -> Vec<ChunkSpan> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return Vec::new();
+    }
+
+    let boundaries = if is_code {
+        code_node_boundaries(&chars)
+    } else {
+        paragraph_boundaries(&chars)
+    };
+
+    pack_chunks(&chars, &boundaries, max_tokens)
+}
+
+/// Boundary offsets between coarse "syntax nodes": a blank line seen
+/// while brace/paren/bracket depth is at or below zero.
+fn code_node_boundaries(chars: &[char]) -> Vec<usize> {
+    let mut boundaries = vec![0usize];
+    let mut depth: i32 = 0;
+    for (i, &ch) in chars.iter().enumerate() {
+        match ch {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            '\n' if depth <= 0 && chars.get(i + 1) == Some(&'\n') => boundaries.push(i + 1),
+            _ => {}
+        }
+    }
+    boundaries.push(chars.len());
+    boundaries.dedup();
+    boundaries
+}
+
+/// Boundary offsets between paragraphs, split on blank lines.
+fn paragraph_boundaries(chars: &[char]) -> Vec<usize> {
+    let mut boundaries = vec![0usize];
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '\n' && chars.get(i + 1) == Some(&'\n') {
+            boundaries.push(i + 1);
+        }
+    }
+    boundaries.push(chars.len());
+    boundaries.dedup();
+    boundaries
+}
+
+/// Greedily merges consecutive `[boundaries[i], boundaries[i + 1])` nodes
+/// into chunks no larger than `max_tokens`, then force-splits any single
+/// node that's still too big on its own.
+fn pack_chunks(chars: &[char], boundaries: &[usize], max_tokens: usize) -> Vec<ChunkSpan> {
+    let mut spans = Vec::new();
+    let mut chunk_start = boundaries[0];
+    let mut chunk_end = boundaries[0];
+
+    for pair in boundaries.windows(2) {
+        let node_end = pair[1];
+        if node_end <= chunk_end {
+            continue;
+        }
+
+        let candidate = chars_slice(chars, chunk_start, node_end);
+        if chunk_end > chunk_start && approx_token_count(&candidate) > max_tokens {
+            spans.push(make_span(chars, chunk_start, chunk_end));
+            chunk_start = chunk_end;
+        }
+        chunk_end = node_end;
+    }
+    if chunk_end > chunk_start {
+        spans.push(make_span(chars, chunk_start, chunk_end));
+    }
+
+    spans.into_iter().flat_map(|span| force_split(chars, span, max_tokens)).collect()
+}
+
+fn chars_slice(chars: &[char], start: usize, end: usize) -> String {
+    chars[start..end].iter().collect()
+}
+
+fn make_span(chars: &[char], start: usize, end: usize) -> ChunkSpan {
+    ChunkSpan { char_start: start, char_end: end, text: chars_slice(chars, start, end) }
+}
+
+/// Splits a single chunk that's still over `max_tokens` on word
+/// boundaries, so no emitted chunk exceeds the embedding model's token
+/// budget (short of a single word alone exceeding it, which can't be
+/// split further without breaking the token).
+fn force_split(chars: &[char], span: ChunkSpan, max_tokens: usize) -> Vec<ChunkSpan> {
+    if approx_token_count(&span.text) <= max_tokens {
+        return vec![span];
+    }
+
+    let mut words: Vec<(usize, usize)> = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for i in span.char_start..span.char_end {
+        if chars[i].is_whitespace() {
+            if let Some(start) = word_start.take() {
+                words.push((start, i));
+            }
+        } else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+    if let Some(start) = word_start {
+        words.push((start, span.char_end));
+    }
+
+    let mut out = Vec::new();
+    let mut chunk_start = span.char_start;
+    let mut chunk_end = span.char_start;
+    for (word_start, word_end) in words {
+        let candidate = chars_slice(chars, chunk_start, word_end);
+        if chunk_end > chunk_start && approx_token_count(&candidate) > max_tokens {
+            out.push(make_span(chars, chunk_start, chunk_end));
+            chunk_start = word_start;
+        }
+        chunk_end = word_end;
+    }
+    if chunk_end > chunk_start {
+        out.push(make_span(chars, chunk_start, chunk_end));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_token_count_words_and_punctuation() {
+        assert_eq!(approx_token_count("hello world"), 2);
+        assert_eq!(approx_token_count("hello, world!"), 4);
+        assert_eq!(approx_token_count(""), 1);
+    }
+
+    #[test]
+    fn test_chunk_text_prose_splits_on_paragraphs() {
+        let text = "First paragraph.\n\nSecond paragraph.\n\nThird paragraph.";
+        let chunks = chunk_text(text, false, 1000);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, text);
+    }
+
+    #[test]
+    fn test_chunk_text_prose_respects_token_budget() {
+        let text = "First paragraph.\n\nSecond paragraph.\n\nThird paragraph.";
+        let chunks = chunk_text(text, false, 3);
+        assert!(chunks.len() >= 3);
+        for chunk in &chunks {
+            assert!(approx_token_count(&chunk.text) <= 4);
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_code_keeps_braces_together() {
+        let code = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        let chunks = chunk_text(code, true, 1000);
+        assert_eq!(chunks.len(), 1);
+
+        let chunks = chunk_text(code, true, 2);
+        assert!(chunks.len() >= 2);
+        assert!(chunks[0].text.contains("fn a()"));
+    }
+
+    #[test]
+    fn test_chunk_text_offsets_reconstruct_source() {
+        let text = "alpha beta\n\ngamma delta";
+        let chars: Vec<char> = text.chars().collect();
+        let chunks = chunk_text(text, false, 2);
+        for chunk in &chunks {
+            let slice: String = chars[chunk.char_start..chunk.char_end].iter().collect();
+            assert_eq!(slice, chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_empty_input() {
+        assert!(chunk_text("", false, 100).is_empty());
+    }
+}
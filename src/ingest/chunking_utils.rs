@@ -2,6 +2,87 @@
 // Shared utilities for text chunking in ingestion pipelines
 
 
+/// Rough token estimate for `text`, using the common ~4-characters-per-token
+/// heuristic. This is not a real tokenizer - it only needs to be close
+/// enough to keep chunks from silently blowing past a model's context
+/// window.
+pub fn estimate_tokens(text: &str) -> usize {
+    (text.chars().count() + 3) / 4
+}
+
+/// Recursively splits `text` so every returned piece stays within
+/// `max_tokens` (per [`estimate_tokens`]), preferring sentence boundaries
+/// and falling back to word boundaries when a single sentence is still too
+/// large on its own. Returns `text` unsplit if it already fits, if
+/// `max_tokens` is 0 (unbounded), or if there's no whitespace left to split
+/// on.
+pub fn split_by_token_budget(text: &str, max_tokens: usize) -> Vec<String> {
+    if max_tokens == 0 || estimate_tokens(text) <= max_tokens {
+        return vec![text.to_string()];
+    }
+
+    let sentences = split_on_sentence_boundaries(text);
+    if sentences.len() > 1 {
+        return sentences
+            .into_iter()
+            .flat_map(|sentence| split_by_token_budget(&sentence, max_tokens))
+            .collect();
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() <= 1 {
+        return vec![text.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for word in words {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if estimate_tokens(&candidate) > max_tokens && !current.is_empty() {
+            pieces.push(current);
+            current = word.to_string();
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Splits `text` on `.`/`!`/`?` followed by whitespace or end-of-string,
+/// keeping the punctuation attached to the sentence it ends.
+fn split_on_sentence_boundaries(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for i in 0..chars.len() {
+        let at_boundary = matches!(chars[i], '.' | '!' | '?')
+            && (i + 1 == chars.len() || chars[i + 1].is_whitespace());
+        if at_boundary {
+            let sentence: String = chars[start..=i].iter().collect();
+            let sentence = sentence.trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            start = i + 1;
+        }
+    }
+    if start < chars.len() {
+        let rest: String = chars[start..].iter().collect();
+        let rest = rest.trim();
+        if !rest.is_empty() {
+            sentences.push(rest.to_string());
+        }
+    }
+    sentences
+}
+
 /// Chunk a text field for embedding, using character-based chunking if needed.
 /// Returns a Vec of chunk strings, each <= max_len chars.
 pub fn chunk_field(text: &str, max_len: usize) -> Vec<String> {
@@ -29,58 +110,140 @@ pub fn chunk_field(text: &str, max_len: usize) -> Vec<String> {
     chunks
 }
 
-/// Chunk all relevant fields in a character/entity JSON object for embedding.
-/// Returns a Vec of (field_name, chunk_text) pairs.
-pub fn chunk_entity_fields(obj: &serde_json::Value, max_len: usize) -> Vec<(String, String)> {
-    let mut fields = Vec::new();
-    if let Some(name) = obj.get("character_name").and_then(|v| v.as_str()) {
-        for chunk in chunk_field(name, max_len) {
-            fields.push(("character_name".to_string(), chunk));
-        }
+/// Where [`chunk_field_windowed`] is allowed to cut a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkBoundary {
+    /// Cut exactly at `max_len` characters, even mid-sentence.
+    Character,
+    /// Prefer the last `.`/`!`/`?`/newline within [`SENTENCE_SLACK_FRACTION`]
+    /// of `max_len` characters before the nominal cut, falling back to
+    /// [`ChunkBoundary::Character`] if none is found in that range.
+    Sentence,
+}
+
+/// Fraction of `max_len` searched backward from the nominal cut point for a
+/// sentence boundary, when splitting with [`ChunkBoundary::Sentence`].
+const SENTENCE_SLACK_FRACTION: f64 = 0.2;
+
+/// Splits `text` into overlapping windows of at most `max_len` chars. Each
+/// chunk's end becomes the next chunk's start minus `overlap` chars (rather
+/// than `max_len` chars, as [`chunk_field`] advances), so consecutive
+/// chunks share `overlap` chars of context - useful when the chunks will be
+/// embedded separately and a query might land on what would otherwise be a
+/// hard boundary. Stepping from the actual end of each chunk (not the
+/// nominal `max_len` cut) keeps this gap-free even when
+/// `ChunkBoundary::Sentence` cuts a chunk short.
+///
+/// `overlap` is clamped below `max_len` so the window always advances, even
+/// if the caller passes `overlap >= max_len`.
+pub fn chunk_field_windowed(
+    text: &str,
+    max_len: usize,
+    overlap: usize,
+    boundary: ChunkBoundary,
+) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let text_len = chars.len();
+    if max_len == 0 || text_len <= max_len {
+        return vec![text.to_string()];
     }
-    if let Some(desc) = obj.get("description").and_then(|v| v.as_str()) {
-        for chunk in chunk_field(desc, max_len) {
-            fields.push(("description".to_string(), chunk));
+
+    let overlap = overlap.min(max_len.saturating_sub(1));
+    let slack = ((max_len as f64) * SENTENCE_SLACK_FRACTION) as usize;
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < text_len {
+        let mut end = usize::min(start + max_len, text_len);
+        if boundary == ChunkBoundary::Sentence && end < text_len {
+            if let Some(cut) = last_sentence_boundary(&chars, start, end, slack) {
+                end = cut;
+            }
         }
-    }
-    if let Some(aff) = obj.get("affiliations").and_then(|v| v.as_array()) {
-        let joined = aff
-            .iter()
-            .filter_map(|v| v.as_str())
-            .collect::<Vec<_>>()
-            .join(", ");
-        for chunk in chunk_field(&joined, max_len) {
-            fields.push(("affiliations".to_string(), chunk));
+        chunks.push(chars[start..end].iter().collect());
+        if end >= text_len {
+            break;
         }
+        // Step from where this chunk actually ended (not the nominal
+        // `max_len` cut), so a sentence-boundary cut short of `max_len`
+        // can't leave a gap of un-chunked text before the next window.
+        start = end.saturating_sub(overlap).max(start + 1);
+    }
+    chunks
+}
+
+/// Scans backward from `end` (exclusive) to `end - slack` (never before
+/// `start`) for the last `.`/`!`/`?`/newline, returning the index just past
+/// it so the terminator stays in the chunk it ends. `None` if no
+/// terminator falls in that range.
+fn last_sentence_boundary(chars: &[char], start: usize, end: usize, slack: usize) -> Option<usize> {
+    let floor = start.max(end.saturating_sub(slack));
+    (floor..end)
+        .rev()
+        .find(|&i| matches!(chars[i], '.' | '!' | '?' | '\n'))
+        .map(|i| i + 1)
+}
+
+/// Extracts the chunkable `(field_name, text)` pairs from a character/entity
+/// JSON object - a single string field verbatim, or a string array joined
+/// with `", "` - shared by [`chunk_entity_fields`] and
+/// [`chunk_entity_fields_windowed`] so the two can't drift on which fields
+/// they cover.
+fn entity_field_texts(obj: &serde_json::Value) -> Vec<(&'static str, String)> {
+    let mut texts = Vec::new();
+    if let Some(name) = obj.get("character_name").and_then(|v| v.as_str()) {
+        texts.push(("character_name", name.to_string()));
     }
-    if let Some(attrs) = obj.get("core_attributes").and_then(|v| v.as_array()) {
-        let joined = attrs
-            .iter()
-            .filter_map(|v| v.as_str())
-            .collect::<Vec<_>>()
-            .join(", ");
-        for chunk in chunk_field(&joined, max_len) {
-            fields.push(("core_attributes".to_string(), chunk));
+    if let Some(desc) = obj.get("description").and_then(|v| v.as_str()) {
+        texts.push(("description", desc.to_string()));
+    }
+    for (field, key) in [
+        ("affiliations", "affiliations"),
+        ("core_attributes", "core_attributes"),
+        ("inspirational_themes", "inspirational_themes"),
+        ("traits", "traits"),
+    ] {
+        if let Some(values) = obj.get(key).and_then(|v| v.as_array()) {
+            let joined = values
+                .iter()
+                .filter_map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            texts.push((field, joined));
         }
     }
-    if let Some(themes) = obj.get("inspirational_themes").and_then(|v| v.as_array()) {
-        let joined = themes
-            .iter()
-            .filter_map(|v| v.as_str())
-            .collect::<Vec<_>>()
-            .join(", ");
-        for chunk in chunk_field(&joined, max_len) {
-            fields.push(("inspirational_themes".to_string(), chunk));
+    texts
+}
+
+/// Chunk all relevant fields in a character/entity JSON object for embedding.
+/// Returns a Vec of (field_name, chunk_text) pairs.
+pub fn chunk_entity_fields(obj: &serde_json::Value, max_len: usize) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    for (field, text) in entity_field_texts(obj) {
+        for chunk in chunk_field(&text, max_len) {
+            fields.push((field.to_string(), chunk));
         }
     }
-    if let Some(traits) = obj.get("traits").and_then(|v| v.as_array()) {
-        let joined = traits
-            .iter()
-            .filter_map(|v| v.as_str())
-            .collect::<Vec<_>>()
-            .join(", ");
-        for chunk in chunk_field(&joined, max_len) {
-            fields.push(("traits".to_string(), chunk));
+    fields
+}
+
+/// Like [`chunk_entity_fields`], but splits each field with
+/// [`chunk_field_windowed`] so ingestion callers can request overlapping
+/// chunks, and attaches `(chunk_index, total_chunks)` to every returned
+/// chunk - both counted per field, resetting at each new field - so a
+/// downstream vector record can reconstruct a field's chunk ordering.
+pub fn chunk_entity_fields_windowed(
+    obj: &serde_json::Value,
+    max_len: usize,
+    overlap: usize,
+    boundary: ChunkBoundary,
+) -> Vec<(String, String, usize, usize)> {
+    let mut fields = Vec::new();
+    for (field, text) in entity_field_texts(obj) {
+        let chunks = chunk_field_windowed(&text, max_len, overlap, boundary);
+        let total_chunks = chunks.len();
+        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+            fields.push((field.to_string(), chunk, chunk_index, total_chunks));
         }
     }
     fields
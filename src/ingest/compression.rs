@@ -0,0 +1,225 @@
+//! Optional streaming compression for JSONL intermediates and embedding
+//! output, so large character corpora don't leave an uncompressed
+//! `.jsonl` footprint on disk.
+//!
+//! Readers are transparent: [`read_possibly_compressed`] picks gzip or
+//! zstd decompression based on the file's extension, falling back to a
+//! plain read otherwise.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use tokio::io::{AsyncBufRead, AsyncReadExt, AsyncWriteExt, BufReader, Lines};
+
+/// Compression to apply to JSONL intermediates and embedding output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Write plain, uncompressed `.jsonl`.
+    #[default]
+    None,
+    /// Write gzip-compressed `.jsonl.gz`.
+    Gzip,
+    /// Write zstd-compressed `.jsonl.zst`.
+    Zstd,
+}
+
+impl Compression {
+    /// Parses a `--compress` value (`none`, `gzip`, or `zstd`).
+    ///
+    /// # Errors
+    /// Returns an error if `value` isn't one of the recognized names.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "none" => Ok(Self::None),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            other => anyhow::bail!("Unknown compression '{other}', expected none|gzip|zstd"),
+        }
+    }
+
+    /// File extension suffix to append to a `.jsonl` path for this
+    /// compression (empty for [`Compression::None`]).
+    pub fn extension_suffix(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Gzip => ".gz",
+            Self::Zstd => ".zst",
+        }
+    }
+
+    /// Returns `path` with this compression's extension suffix appended.
+    pub fn apply_extension(self, path: &Path) -> PathBuf {
+        let mut path = path.as_os_str().to_owned();
+        path.push(self.extension_suffix());
+        PathBuf::from(path)
+    }
+}
+
+/// Strips a trailing `.gz`/`.zst` suffix from `path`, returning the
+/// detected compression and the path with the suffix removed.
+pub fn detect_compression(path: &Path) -> (Compression, PathBuf) {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => (Compression::Gzip, path.with_extension("")),
+        Some("zst") => (Compression::Zstd, path.with_extension("")),
+        _ => (Compression::None, path.to_path_buf()),
+    }
+}
+
+/// Writes `content` to `path` with `compression` applied, streaming the
+/// encoder rather than buffering the compressed output in memory.
+///
+/// # Errors
+/// Returns an error if `path` can't be created or the stream can't be
+/// written or flushed.
+pub async fn write_compressed(path: &Path, content: &[u8], compression: Compression) -> Result<()> {
+    let file = tokio::fs::File::create(path)
+        .await
+        .with_context(|| format!("Failed to create file: {}", path.display()))?;
+
+    match compression {
+        Compression::None => {
+            let mut file = file;
+            file.write_all(content)
+                .await
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+        }
+        Compression::Gzip => {
+            let mut encoder = GzipEncoder::new(file);
+            encoder.write_all(content).await.context("Failed to gzip-compress output")?;
+            encoder.shutdown().await.context("Failed to finalize gzip stream")?;
+        }
+        Compression::Zstd => {
+            let mut encoder = ZstdEncoder::new(file);
+            encoder.write_all(content).await.context("Failed to zstd-compress output")?;
+            encoder.shutdown().await.context("Failed to finalize zstd stream")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads `path` as UTF-8 text, transparently decompressing it first if its
+/// extension is `.gz` or `.zst`.
+///
+/// # Errors
+/// Returns an error if `path` can't be opened, the stream can't be
+/// decompressed, or the decompressed content isn't valid UTF-8.
+pub async fn read_possibly_compressed(path: &Path) -> Result<String> {
+    let (compression, _) = detect_compression(path);
+    let file = tokio::fs::File::open(path)
+        .await
+        .with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let reader = BufReader::new(file);
+
+    let mut content = String::new();
+    match compression {
+        Compression::None => {
+            let mut reader = reader;
+            reader
+                .read_to_string(&mut content)
+                .await
+                .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        }
+        Compression::Gzip => {
+            GzipDecoder::new(reader)
+                .read_to_string(&mut content)
+                .await
+                .context("Failed to gunzip input")?;
+        }
+        Compression::Zstd => {
+            ZstdDecoder::new(reader)
+                .read_to_string(&mut content)
+                .await
+                .context("Failed to un-zstd input")?;
+        }
+    }
+
+    Ok(content)
+}
+
+/// Opens `path` for line-by-line reading, transparently decompressing it
+/// first if its extension is `.gz` or `.zst`, without ever reading the
+/// whole file into memory. Unlike [`read_possibly_compressed`], this is
+/// the path for files too large to hold entirely in memory.
+///
+/// # Errors
+/// Returns an error if `path` can't be opened.
+pub async fn open_lines_possibly_compressed(
+    path: &Path,
+) -> Result<Lines<Box<dyn AsyncBufRead + Unpin + Send>>> {
+    let (compression, _) = detect_compression(path);
+    let file = tokio::fs::File::open(path)
+        .await
+        .with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let reader = BufReader::new(file);
+
+    let boxed: Box<dyn AsyncBufRead + Unpin + Send> = match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(BufReader::new(GzipDecoder::new(reader))),
+        Compression::Zstd => Box::new(BufReader::new(ZstdDecoder::new(reader))),
+    };
+
+    Ok(tokio::io::AsyncBufReadExt::lines(boxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Compression::parse("none").unwrap(), Compression::None);
+        assert_eq!(Compression::parse("gzip").unwrap(), Compression::Gzip);
+        assert_eq!(Compression::parse("zstd").unwrap(), Compression::Zstd);
+        assert!(Compression::parse("bogus").is_err());
+    }
+
+    #[test]
+    fn test_apply_extension() {
+        let path = PathBuf::from("MarvelAIs.jsonl");
+        assert_eq!(Compression::None.apply_extension(&path), path);
+        assert_eq!(
+            Compression::Gzip.apply_extension(&path),
+            PathBuf::from("MarvelAIs.jsonl.gz")
+        );
+        assert_eq!(
+            Compression::Zstd.apply_extension(&path),
+            PathBuf::from("MarvelAIs.jsonl.zst")
+        );
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(
+            detect_compression(Path::new("MarvelAIs.jsonl.gz")),
+            (Compression::Gzip, PathBuf::from("MarvelAIs.jsonl"))
+        );
+        assert_eq!(
+            detect_compression(Path::new("MarvelAIs.jsonl.zst")),
+            (Compression::Zstd, PathBuf::from("MarvelAIs.jsonl"))
+        );
+        assert_eq!(
+            detect_compression(Path::new("MarvelAIs.jsonl")),
+            (Compression::None, PathBuf::from("MarvelAIs.jsonl"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_lines_possibly_compressed_reads_line_by_line() {
+        use tokio::io::AsyncBufReadExt;
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("records.jsonl");
+        tokio::fs::write(&path, "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n").await.unwrap();
+
+        let mut lines = open_lines_possibly_compressed(&path).await.unwrap();
+        let mut collected = Vec::new();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            collected.push(line);
+        }
+
+        assert_eq!(collected, vec!["{\"a\":1}", "{\"a\":2}", "{\"a\":3}"]);
+    }
+}
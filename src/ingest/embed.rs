@@ -1,22 +1,27 @@
 //! Embedding generation module for converting text to vector representations.
 //!
-//! This module handles communication with a local embedding API to convert text
-//! into high-dimensional vectors suitable for semantic similarity search. It uses
-//! the Harald-Phi4 model running locally via Ollama to generate embeddings that
-//! capture the semantic meaning of text content.
+//! This module handles communication with an embedding API to convert text
+//! into high-dimensional vectors suitable for semantic similarity search. It
+//! supports both a local Harald-Phi4 model served by Ollama and any
+//! OpenAI-compatible hosted embeddings endpoint, selected via
+//! [`EmbedConfig::backend`].
 //!
 //! # Module Structure
 //! This is a "module source file" that defines the embed module:
-//! - Loaded via `mod embed;` in main.rs/lib.rs  
+//! - Loaded via `mod embed;` in main.rs/lib.rs
 //! - Functions accessed as `embed::embed()` from other modules
 //! - Core utility module used by both ingest and query modules
 //! - Handles the critical text-to-vector conversion for semantic search
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use reqwest::Client;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use reqwest::{Client, Response};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// Default embedding model name.
 ///
@@ -40,6 +45,41 @@ const DEFAULT_TIMEOUT_SECS: u64 = 60;
 /// Maximum retry attempts for failed embedding requests.
 const MAX_RETRY_ATTEMPTS: usize = 3;
 
+/// Default Ollama `options.num_ctx` sent with every request, so the
+/// runtime context window is always explicit rather than left to whatever
+/// the model's own default happens to be.
+const DEFAULT_NUM_CTX: usize = 4096;
+
+/// Default cap on in-flight embedding requests for [`OllamaProvider`] and
+/// [`OpenAiProvider`]'s `embed_batch`. Matches
+/// [`OllamaApiClient`](crate::core::embedding::ollama_api::OllamaApiClient)'s
+/// own default concurrency.
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Short input [`OllamaProvider::probe_dimensions`] and
+/// [`OpenAiProvider::probe_dimensions`] embed once to discover the
+/// model's real output dimensionality.
+const DIMENSION_PROBE_TEXT: &str = "test";
+
+/// Environment variable `EmbedConfig::default()` reads a bearer token from,
+/// so hosted-backend credentials never have to land in argv.
+const AUTH_TOKEN_ENV_VAR: &str = "HARALD_EMBED_AUTH_TOKEN";
+
+/// Which embedding provider an [`EmbedConfig`] talks to.
+///
+/// Both variants speak HTTP but differ in request/response shape, so each
+/// gets its own [`EmbeddingBackend`] implementation rather than branching
+/// inline in `attempt_embedding`. Authentication is configured separately
+/// via [`EmbedConfig::auth_token`], since it's orthogonal to request shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Local Ollama instance, e.g. `harald-phi4` served on `:11434`.
+    #[default]
+    Ollama,
+    /// An OpenAI-compatible `/v1/embeddings` endpoint.
+    OpenAi,
+}
+
 /// Configuration for embedding generation.
 #[derive(Debug, Clone)]
 pub struct EmbedConfig {
@@ -51,6 +91,32 @@ pub struct EmbedConfig {
     pub timeout_secs: u64,
     /// Maximum retry attempts for failed requests.
     pub max_retries: usize,
+    /// Which provider to send requests to.
+    pub backend: Backend,
+    /// `Authorization: Bearer <token>` value sent with every request, when
+    /// set. Defaults to the `HARALD_EMBED_AUTH_TOKEN` environment variable
+    /// so hosted-backend credentials don't have to be passed on argv.
+    pub auth_token: Option<String>,
+    /// Ollama `truncate` request option: whether the server should
+    /// truncate input exceeding the model's context length.
+    pub truncate: Option<bool>,
+    /// Ollama `keep_alive` request option: how long to keep the model
+    /// loaded in memory after this request (e.g. `"5m"`).
+    pub keep_alive: Option<String>,
+    /// Ollama `options.num_ctx` request option: the runtime context window
+    /// in tokens. Ollama exposes no API to query a model's max tokens, so
+    /// this has to be set explicitly rather than discovered - defaults to
+    /// [`DEFAULT_NUM_CTX`].
+    pub num_ctx: Option<usize>,
+    /// Additional fields spliced into the request body as-is, for
+    /// provider-specific options (e.g. `temperature`) with no dedicated
+    /// field here.
+    pub extra: Map<String, Value>,
+    /// Maximum number of embedding requests [`OllamaProvider`] and
+    /// [`OpenAiProvider`]'s `embed_batch` keep in flight at once. Bounds
+    /// load on the target server instead of firing every request in a
+    /// batch simultaneously.
+    pub max_concurrent_requests: usize,
 }
 
 impl Default for EmbedConfig {
@@ -60,13 +126,122 @@ impl Default for EmbedConfig {
             endpoint: DEFAULT_ENDPOINT.to_string(),
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             max_retries: MAX_RETRY_ATTEMPTS,
+            backend: Backend::Ollama,
+            auth_token: std::env::var(AUTH_TOKEN_ENV_VAR).ok(),
+            truncate: None,
+            keep_alive: None,
+            num_ctx: Some(DEFAULT_NUM_CTX),
+            extra: Map::new(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+impl EmbedConfig {
+    /// Sets the Ollama runtime context window (`options.num_ctx`).
+    pub fn with_num_ctx(mut self, num_ctx: usize) -> Self {
+        self.num_ctx = Some(num_ctx);
+        self
+    }
+
+    /// Sets the cap on in-flight `embed_batch` requests.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+
+    /// Builds the [`EmbeddingProvider`] matching `self.backend`, so callers
+    /// that already have an `EmbedConfig` don't need their own
+    /// `Backend::Ollama | Backend::OpenAi` match arm. [`RestProvider`] isn't
+    /// reachable from here since it's driven by a separate
+    /// [`RestEmbedConfig`] rather than `Backend`.
+    pub fn build_provider(self, client: Client) -> Arc<dyn EmbeddingProvider> {
+        match self.backend {
+            Backend::Ollama => Arc::new(OllamaProvider::new(client, self)),
+            Backend::OpenAi => Arc::new(OpenAiProvider::new(client, self)),
+        }
+    }
+}
+
+/// Converts text to an embedding vector, given a backend-specific request.
+///
+/// Implemented once per provider so `attempt_embedding` can stay agnostic
+/// to the request/response shape of whichever backend `EmbedConfig` selects.
+trait EmbeddingBackend {
+    async fn embed(&self, client: &Client, config: &EmbedConfig, text: &str) -> Result<Vec<f32>, EmbedFailure>;
+}
+
+/// Whether a failed embedding request is worth retrying, and how long to
+/// wait before the next attempt. Lets `embed_with_config`'s retry loop
+/// distinguish a transient failure from one retrying can't fix, and honor
+/// a rate limiter's own timing instead of guessing via backoff.
+enum EmbedFailure {
+    /// Connection errors, timeouts, or HTTP 5xx - retried with the
+    /// existing exponential backoff.
+    Retryable(anyhow::Error),
+    /// HTTP 429. `retry_after` is the parsed `Retry-After` header value
+    /// when present and parseable; falls back to exponential backoff
+    /// otherwise.
+    RateLimited {
+        retry_after: Option<Duration>,
+        err: anyhow::Error,
+    },
+    /// Any other 4xx, or a response that failed to parse - retrying the
+    /// same request can't change the outcome.
+    Fatal(anyhow::Error),
+}
+
+/// Sends `request`, classifying any transport error or non-success
+/// response into an [`EmbedFailure`] so callers know whether (and how
+/// long) to wait before retrying.
+async fn send_embed_request(request: reqwest::RequestBuilder) -> Result<Response, EmbedFailure> {
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            let retryable = e.is_timeout() || e.is_connect();
+            let err = anyhow::Error::new(e).context("Failed to send embedding request");
+            return Err(if retryable { EmbedFailure::Retryable(err) } else { EmbedFailure::Fatal(err) });
         }
+    };
+
+    if response.status().is_success() {
+        return Ok(response);
     }
+
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after);
+    let body = response.text().await.unwrap_or_else(|_| "No response body".to_string());
+    let err = anyhow::anyhow!("Embedding API returned {}: {}", status, body);
+
+    Err(if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        EmbedFailure::RateLimited { retry_after, err }
+    } else if status.is_server_error() {
+        EmbedFailure::Retryable(err)
+    } else {
+        EmbedFailure::Fatal(err)
+    })
 }
 
-/// Request payload for the embedding API.
+/// Parses a `Retry-After` header value, which per RFC 9110 is either a
+/// number of seconds or an HTTP-date. Returns `Duration::ZERO` for a date
+/// that's already passed rather than `None`, since that means "retry now".
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let target = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let remaining = target.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(remaining.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Request payload for the Ollama embedding API.
 #[derive(Debug, Serialize)]
-struct EmbedRequest<'a> {
+struct OllamaEmbedRequest<'a> {
     /// Model name to use for embedding generation.
     model: &'a str,
     /// Text content to convert to embedding.
@@ -75,13 +250,122 @@ struct EmbedRequest<'a> {
     stream: bool,
 }
 
-/// Response payload from the embedding API.
+/// Response payload from the Ollama embedding API.
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    /// The generated embedding vector.
+    embedding: Vec<f32>,
+}
+
+/// Talks to a local Ollama instance's `/api/embeddings` endpoint.
+struct OllamaBackend;
+
+impl EmbeddingBackend for OllamaBackend {
+    async fn embed(&self, client: &Client, config: &EmbedConfig, text: &str) -> Result<Vec<f32>, EmbedFailure> {
+        let request_body = OllamaEmbedRequest {
+            model: &config.model,
+            prompt: text,
+            stream: false,
+        };
+        let mut body = serde_json::to_value(&request_body)
+            .context("Failed to build embedding request body")
+            .map_err(EmbedFailure::Fatal)?;
+        {
+            let body = body.as_object_mut().expect("request body is always an object");
+            if let Some(truncate) = config.truncate {
+                body.insert("truncate".to_string(), Value::Bool(truncate));
+            }
+            if let Some(keep_alive) = &config.keep_alive {
+                body.insert("keep_alive".to_string(), Value::String(keep_alive.clone()));
+            }
+            if let Some(num_ctx) = config.num_ctx {
+                body.insert("options".to_string(), serde_json::json!({ "num_ctx": num_ctx }));
+            }
+            body.extend(config.extra.clone());
+        }
+
+        let mut request = client
+            .post(&config.endpoint)
+            .json(&body)
+            .timeout(Duration::from_secs(config.timeout_secs));
+        if let Some(token) = &config.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response: OllamaEmbedResponse = send_embed_request(request)
+            .await?
+            .json()
+            .await
+            .context("Failed to parse embedding response")
+            .map_err(EmbedFailure::Fatal)?;
+
+        Ok(response.embedding)
+    }
+}
+
+/// Request payload for an OpenAI-compatible `/v1/embeddings` endpoint.
+#[derive(Debug, Serialize)]
+struct OpenAiEmbedRequest<'a> {
+    /// Model name to use for embedding generation.
+    model: &'a str,
+    /// Text content to convert to embedding.
+    input: &'a str,
+}
+
+/// Response payload from an OpenAI-compatible `/v1/embeddings` endpoint.
+#[derive(Debug, Deserialize)]
+struct OpenAiEmbedResponse {
+    /// One embedding entry per input; only single-input requests are sent.
+    data: Vec<OpenAiEmbedData>,
+}
+
+/// A single embedding entry in an OpenAI-style response.
 #[derive(Debug, Deserialize)]
-struct EmbedResponse {
+struct OpenAiEmbedData {
     /// The generated embedding vector.
     embedding: Vec<f32>,
 }
 
+/// Talks to a hosted, OpenAI-compatible embeddings endpoint.
+struct OpenAiBackend;
+
+impl EmbeddingBackend for OpenAiBackend {
+    async fn embed(&self, client: &Client, config: &EmbedConfig, text: &str) -> Result<Vec<f32>, EmbedFailure> {
+        let request_body = OpenAiEmbedRequest {
+            model: &config.model,
+            input: text,
+        };
+        let mut body = serde_json::to_value(&request_body)
+            .context("Failed to build embedding request body")
+            .map_err(EmbedFailure::Fatal)?;
+        body.as_object_mut()
+            .expect("request body is always an object")
+            .extend(config.extra.clone());
+
+        let mut request = client
+            .post(&config.endpoint)
+            .json(&body)
+            .timeout(Duration::from_secs(config.timeout_secs));
+        if let Some(token) = &config.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response: OpenAiEmbedResponse = send_embed_request(request)
+            .await?
+            .json()
+            .await
+            .context("Failed to parse embedding response")
+            .map_err(EmbedFailure::Fatal)?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .ok_or_else(|| EmbedFailure::Fatal(anyhow::anyhow!("Embedding API returned no data entries")))
+    }
+}
+
 /// Converts text to an embedding vector using the default configuration.
 ///
 /// This is the primary interface for generating embeddings from text.
@@ -146,14 +430,27 @@ pub async fn embed_with_config(
                 validate_embedding(&embedding)?;
                 return Ok(embedding);
             }
-            Err(e) => {
-                last_error = Some(e);
+            // 4xx other than 429: retrying the same request can't change
+            // the outcome, so fail immediately without consuming retries.
+            Err(EmbedFailure::Fatal(err)) => return Err(err),
+            // Connection errors, timeouts, HTTP 5xx: exponential backoff.
+            Err(EmbedFailure::Retryable(err)) => {
+                last_error = Some(err);
                 if attempt < config.max_retries {
-                    // Exponential backoff: wait 2^attempt seconds
                     let delay = Duration::from_secs(2_u64.pow(attempt as u32));
                     tokio::time::sleep(delay).await;
                 }
             }
+            // HTTP 429: honor the server's own `Retry-After` delay instead
+            // of guessing via backoff, falling back to backoff if the
+            // header was missing or unparseable.
+            Err(EmbedFailure::RateLimited { retry_after, err }) => {
+                last_error = Some(err);
+                if attempt < config.max_retries {
+                    let delay = retry_after.unwrap_or_else(|| Duration::from_secs(2_u64.pow(attempt as u32)));
+                    tokio::time::sleep(delay).await;
+                }
+            }
         }
     }
 
@@ -176,28 +473,13 @@ fn validate_input(text: &str) -> Result<()> {
     Ok(())
 }
 
-/// Attempts to generate an embedding for the given text.
-async fn attempt_embedding(text: &str, client: &Client, config: &EmbedConfig) -> Result<Vec<f32>> {
-    let request_body = EmbedRequest {
-        model: &config.model,
-        prompt: text,
-        stream: false,
-    };
-
-    let response: EmbedResponse = client
-        .post(&config.endpoint)
-        .json(&request_body)
-        .timeout(Duration::from_secs(config.timeout_secs))
-        .send()
-        .await
-        .context("Failed to send embedding request")?
-        .error_for_status()
-        .context("Embedding API returned error status")?
-        .json()
-        .await
-        .context("Failed to parse embedding response")?;
-
-    Ok(response.embedding)
+/// Attempts to generate an embedding for the given text, dispatching to
+/// whichever backend `config.backend` selects.
+async fn attempt_embedding(text: &str, client: &Client, config: &EmbedConfig) -> Result<Vec<f32>, EmbedFailure> {
+    match config.backend {
+        Backend::Ollama => OllamaBackend.embed(client, config, text).await,
+        Backend::OpenAi => OpenAiBackend.embed(client, config, text).await,
+    }
 }
 
 /// Simple wrapper function for embedding with default configuration.
@@ -220,19 +502,43 @@ pub async fn embed(text: &str, max_tokens: usize, client: &Client) -> Result<Vec
     embed_with_config(text, max_tokens, client, config).await
 }
 
+/// Embeds [`DIMENSION_PROBE_TEXT`] via `config` once and returns the
+/// resulting vector's length. Shared by [`OllamaProvider::probe_dimensions`]
+/// and [`OpenAiProvider::probe_dimensions`], which each cache the result
+/// on their own instance.
+async fn probe_dimensions(client: &Client, config: &EmbedConfig) -> Result<usize> {
+    let embedding = embed_with_config(DIMENSION_PROBE_TEXT, 0, client, config.clone()).await?;
+    Ok(embedding.len())
+}
+
+/// Checks every embedding in `embeddings` has length `expected`, so a model
+/// swap or misconfigured endpoint producing a different-sized vector mid-run
+/// fails clearly instead of silently corrupting an index sized off the probe.
+fn check_dimensions_match(embeddings: &[Vec<f32>], expected: usize) -> Result<()> {
+    for embedding in embeddings {
+        if embedding.len() != expected {
+            return Err(anyhow::anyhow!(
+                "Embedding dimension changed mid-run: expected {} (from probe), got {} - the backing model may have changed",
+                expected,
+                embedding.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Validates the generated embedding vector.
+///
+/// Doesn't assume any particular dimensionality - models vary widely, and a
+/// fixed minimum both rejects legitimately small models and fails to catch
+/// wrong-sized vectors from a larger one. Callers that know what dimension
+/// to expect (e.g. [`OllamaProvider::probe_dimensions`]) check that
+/// separately.
 fn validate_embedding(embedding: &[f32]) -> Result<()> {
     if embedding.is_empty() {
         return Err(anyhow::anyhow!("Received empty embedding vector"));
     }
 
-    if embedding.len() < 100 {
-        return Err(anyhow::anyhow!(
-            "Embedding dimension too small: {}",
-            embedding.len()
-        ));
-    }
-
     // Check for NaN or infinite values
     if embedding.iter().any(|&x| !x.is_finite()) {
         return Err(anyhow::anyhow!(
@@ -255,8 +561,471 @@ pub fn create_config(model: &str, endpoint: &str) -> EmbedConfig {
     EmbedConfig {
         model: model.to_string(),
         endpoint: endpoint.to_string(),
-        timeout_secs: DEFAULT_TIMEOUT_SECS,
-        max_retries: MAX_RETRY_ATTEMPTS,
+        backend: Backend::Ollama,
+        ..EmbedConfig::default()
+    }
+}
+
+/// Creates an embedding configuration for a hosted OpenAI-compatible backend.
+///
+/// # Arguments
+/// * `model` - Model name to use for embedding generation
+/// * `endpoint` - `/v1/embeddings` endpoint URL
+/// * `auth_token` - Bearer token sent in the `Authorization` header
+///
+/// # Returns
+/// Returns a configured `EmbedConfig` instance targeting the OpenAI backend.
+pub fn create_openai_config(model: &str, endpoint: &str, auth_token: &str) -> EmbedConfig {
+    EmbedConfig {
+        model: model.to_string(),
+        endpoint: endpoint.to_string(),
+        backend: Backend::OpenAi,
+        auth_token: Some(auth_token.to_string()),
+        ..EmbedConfig::default()
+    }
+}
+
+/// Default dimensionality reported by [`OllamaProvider`] for an
+/// unrecognized local model. Local models vary widely, so this is only a
+/// best-effort sizing hint - override it with
+/// [`OllamaProvider::with_dimensions`] for anything other than
+/// `harald-phi4`.
+const DEFAULT_OLLAMA_DIMENSIONS: usize = 4096;
+
+/// Default dimensionality reported by [`OpenAiProvider`] for a model not
+/// in [`known_openai_dimensions`].
+const DEFAULT_OPENAI_DIMENSIONS: usize = 1536;
+
+/// Dimensions for OpenAI's published embedding models, so
+/// [`OpenAiProvider::new`] can size an index correctly without a round
+/// trip first.
+fn known_openai_dimensions(model: &str) -> Option<usize> {
+    match model {
+        "text-embedding-3-small" | "text-embedding-ada-002" => Some(1536),
+        "text-embedding-3-large" => Some(3072),
+        _ => None,
+    }
+}
+
+/// A pluggable source of text embeddings, selected once via
+/// `IngestConfig::provider` and shared across a whole ingestion run. Lets
+/// indexing run fully offline against a local model, or swap in a hosted
+/// backend per run, without the ingestion pipeline depending on which.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embeds each of `texts` in turn, returning one vector per input in
+    /// the same order. `max_tokens` bounds request size the same way
+    /// `EmbedConfig`'s callers already rely on.
+    async fn embed_batch(&self, texts: &[String], max_tokens: usize) -> Result<Vec<Vec<f32>>>;
+
+    /// The dimensionality of vectors this provider returns, so callers
+    /// can size an HNSW index before the first embedding comes back.
+    fn dimensions(&self) -> usize;
+}
+
+/// Embeds text against a local Ollama instance (default `harald-phi4` on
+/// `:11434`), so indexing can run fully offline with no per-call API cost.
+pub struct OllamaProvider {
+    client: Client,
+    config: EmbedConfig,
+    dimensions: usize,
+    probed_dimensions: tokio::sync::OnceCell<usize>,
+}
+
+impl OllamaProvider {
+    /// Builds a provider from an [`EmbedConfig`] already pointed at
+    /// `Backend::Ollama`; `config.backend` is forced to `Ollama` so
+    /// callers can't accidentally mix this provider with OpenAI settings.
+    pub fn new(client: Client, config: EmbedConfig) -> Self {
+        Self {
+            client,
+            config: EmbedConfig {
+                backend: Backend::Ollama,
+                ..config
+            },
+            dimensions: DEFAULT_OLLAMA_DIMENSIONS,
+            probed_dimensions: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Overrides the dimensionality reported by `dimensions()` until the
+    /// first real probe runs, for local models whose output size differs
+    /// from the default.
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// Embeds [`DIMENSION_PROBE_TEXT`] once to discover this model's real
+    /// output dimensionality, caching the result so repeated calls (and
+    /// `embed_batch`'s own consistency check) are free after the first.
+    pub async fn probe_dimensions(&self) -> Result<usize> {
+        self.probed_dimensions
+            .get_or_try_init(|| probe_dimensions(&self.client, &self.config))
+            .await
+            .map(|dimensions| *dimensions)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaProvider {
+    async fn embed_batch(&self, texts: &[String], max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let expected_dimensions = self.probe_dimensions().await?;
+        let concurrency = self.config.max_concurrent_requests.max(1);
+        let embeddings: Vec<Vec<f32>> = stream::iter(texts.to_vec())
+            .map(|text| {
+                let client = self.client.clone();
+                let config = self.config.clone();
+                async move { embed_with_config(&text, max_tokens, &client, config).await }
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        check_dimensions_match(&embeddings, expected_dimensions)?;
+        Ok(embeddings)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.probed_dimensions.get().copied().unwrap_or(self.dimensions)
+    }
+}
+
+/// Embeds text against a hosted, OpenAI-compatible `/v1/embeddings`
+/// endpoint.
+pub struct OpenAiProvider {
+    client: Client,
+    config: EmbedConfig,
+    dimensions: usize,
+    probed_dimensions: tokio::sync::OnceCell<usize>,
+}
+
+impl OpenAiProvider {
+    /// Builds a provider from an [`EmbedConfig`] already pointed at
+    /// `Backend::OpenAi`; `config.backend` is forced to `OpenAi` so
+    /// callers can't accidentally mix this provider with Ollama settings.
+    pub fn new(client: Client, config: EmbedConfig) -> Self {
+        let dimensions = known_openai_dimensions(&config.model).unwrap_or(DEFAULT_OPENAI_DIMENSIONS);
+        Self {
+            client,
+            config: EmbedConfig {
+                backend: Backend::OpenAi,
+                ..config
+            },
+            dimensions,
+            probed_dimensions: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Overrides the dimensionality reported by `dimensions()` until the
+    /// first real probe runs, for models not covered by
+    /// [`known_openai_dimensions`].
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// Embeds [`DIMENSION_PROBE_TEXT`] once to discover this model's real
+    /// output dimensionality, caching the result so repeated calls (and
+    /// `embed_batch`'s own consistency check) are free after the first.
+    pub async fn probe_dimensions(&self) -> Result<usize> {
+        self.probed_dimensions
+            .get_or_try_init(|| probe_dimensions(&self.client, &self.config))
+            .await
+            .map(|dimensions| *dimensions)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiProvider {
+    async fn embed_batch(&self, texts: &[String], max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let expected_dimensions = self.probe_dimensions().await?;
+        let concurrency = self.config.max_concurrent_requests.max(1);
+        let embeddings: Vec<Vec<f32>> = stream::iter(texts.to_vec())
+            .map(|text| {
+                let client = self.client.clone();
+                let config = self.config.clone();
+                async move { embed_with_config(&text, max_tokens, &client, config).await }
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        check_dimensions_match(&embeddings, expected_dimensions)?;
+        Ok(embeddings)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.probed_dimensions.get().copied().unwrap_or(self.dimensions)
+    }
+}
+
+/// Configuration for [`RestProvider`], a generic REST embedder driven
+/// entirely by a request-body template and a response path, so services
+/// with no dedicated [`EmbeddingProvider`] impl - self-hosted embedding
+/// servers, proxies, anything OpenAI- or Ollama-shaped but not quite - can
+/// still be targeted purely through configuration.
+#[derive(Debug, Clone)]
+pub struct RestEmbedConfig {
+    /// Endpoint URL every request is POSTed to.
+    pub url: String,
+    /// Extra HTTP headers sent with every request, beyond `auth_token`'s
+    /// `Authorization: Bearer`.
+    pub headers: Vec<(String, String)>,
+    /// `Authorization: Bearer <token>` value sent with every request, when set.
+    pub auth_token: Option<String>,
+    /// Request body template. Exactly one of `{{text}}` (replaced with the
+    /// single input, JSON-encoded) or `{{texts}}` (replaced with a JSON
+    /// array of the whole batch) must appear where a JSON value belongs -
+    /// no surrounding quotes, since the substituted value is already valid
+    /// JSON. E.g. `{"model": "m", "input": {{text}}}`.
+    pub body_template: String,
+    /// Dot-separated path locating the embedding vector (or, for a
+    /// `{{texts}}` template, the array of vectors) in the response body,
+    /// e.g. `data.0.embedding`. Numeric segments index into arrays.
+    pub response_path: String,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+}
+
+impl RestEmbedConfig {
+    /// Builds a config, validating `url` up front so a malformed endpoint
+    /// is reported immediately rather than on the first embedding request.
+    pub fn new(url: &str, body_template: &str, response_path: &str) -> Result<Self> {
+        reqwest::Url::parse(url).with_context(|| format!("Invalid REST embedder URL: {url}"))?;
+        Ok(Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            auth_token: None,
+            body_template: body_template.to_string(),
+            response_path: response_path.to_string(),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        })
+    }
+
+    /// Adds a header sent with every request.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the `Authorization: Bearer` token sent with every request.
+    pub fn with_auth_token(mut self, token: &str) -> Self {
+        self.auth_token = Some(token.to_string());
+        self
+    }
+}
+
+/// Fills `template`'s `{{text}}`/`{{texts}}` placeholder and parses the
+/// result as JSON, ready to send as a request body.
+fn fill_rest_template(template: &str, texts: &[String]) -> Result<Value> {
+    let rendered = if template.contains("{{texts}}") {
+        let batch = serde_json::to_string(texts).context("Failed to serialize batch texts")?;
+        template.replace("{{texts}}", &batch)
+    } else if template.contains("{{text}}") {
+        let single = serde_json::to_string(&texts[0]).context("Failed to serialize input text")?;
+        template.replace("{{text}}", &single)
+    } else {
+        return Err(anyhow::anyhow!(
+            "body_template must contain a {{{{text}}}} or {{{{texts}}}} placeholder"
+        ));
+    };
+
+    serde_json::from_str(&rendered)
+        .with_context(|| format!("Rendered REST embedder request body is not valid JSON: {rendered}"))
+}
+
+/// Walks `path`'s dot-separated segments into `value`, treating a segment
+/// that parses as a number as an array index and anything else as an
+/// object key.
+fn walk_response_path<'a>(value: &'a Value, path: &str) -> Result<&'a Value> {
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let next = match segment.parse::<usize>() {
+            Ok(index) => current.get(index),
+            Err(_) => current.get(segment),
+        };
+        current = next.ok_or_else(|| {
+            anyhow::anyhow!("Response path '{path}' not found in reply: missing segment '{segment}'")
+        })?;
+    }
+    Ok(current)
+}
+
+/// Reads a JSON array of numbers into an embedding vector.
+fn extract_rest_vector(value: &Value) -> Result<Vec<f32>> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Response path did not point at a vector (JSON array)"))?
+        .iter()
+        .map(|entry| {
+            entry
+                .as_f64()
+                .map(|n| n as f32)
+                .ok_or_else(|| anyhow::anyhow!("Embedding vector contains a non-numeric value"))
+        })
+        .collect()
+}
+
+/// Embeds text against an arbitrary HTTP embedding API, configured purely
+/// through [`RestEmbedConfig`]'s request-body template and response path.
+/// Interoperates with OpenAI-style, Ollama, and self-hosted embedding
+/// services with no code changes, only configuration.
+pub struct RestProvider {
+    client: Client,
+    config: RestEmbedConfig,
+    dimensions: usize,
+}
+
+impl RestProvider {
+    /// Builds a provider from a [`RestEmbedConfig`]. `dimensions` is the
+    /// expected vector size; every response is validated against it so a
+    /// misconfigured `response_path` or mismatched index fails clearly
+    /// instead of silently corrupting the index.
+    pub fn new(client: Client, config: RestEmbedConfig, dimensions: usize) -> Self {
+        Self { client, config, dimensions }
+    }
+
+    async fn send(&self, body: &Value) -> Result<Value> {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .json(body)
+            .timeout(Duration::from_secs(self.config.timeout_secs));
+        if let Some(token) = &self.config.auth_token {
+            request = request.bearer_auth(token);
+        }
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        request
+            .send()
+            .await
+            .context("Failed to send embedding request")?
+            .error_for_status()
+            .context("Embedding API returned error status")?
+            .json()
+            .await
+            .context("Failed to parse embedding response")
+    }
+
+    fn validate_dimensions(&self, embedding: &[f32]) -> Result<()> {
+        if embedding.len() != self.dimensions {
+            return Err(anyhow::anyhow!(
+                "REST embedder returned a {}-dimensional vector, expected {}",
+                embedding.len(),
+                self.dimensions
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for RestProvider {
+    async fn embed_batch(&self, texts: &[String], _max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let embeddings = if self.config.body_template.contains("{{texts}}") {
+            let body = fill_rest_template(&self.config.body_template, texts)?;
+            let response = self.send(&body).await?;
+            walk_response_path(&response, &self.config.response_path)?
+                .as_array()
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Response path '{}' did not point at an array of vectors",
+                        self.config.response_path
+                    )
+                })?
+                .iter()
+                .map(extract_rest_vector)
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for text in texts {
+                let body = fill_rest_template(&self.config.body_template, std::slice::from_ref(text))?;
+                let response = self.send(&body).await?;
+                embeddings.push(extract_rest_vector(walk_response_path(
+                    &response,
+                    &self.config.response_path,
+                )?)?);
+            }
+            embeddings
+        };
+
+        if embeddings.len() != texts.len() {
+            return Err(anyhow::anyhow!(
+                "Expected {} embeddings, REST embedder returned {}",
+                texts.len(),
+                embeddings.len()
+            ));
+        }
+        for embedding in &embeddings {
+            self.validate_dimensions(embedding)?;
+        }
+
+        Ok(embeddings)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+/// An in-process [`EmbeddingProvider`] that performs no network I/O,
+/// returning a deterministic vector derived from each input's length.
+/// Exists so code that depends on `EmbeddingProvider` - [`QueryConfig`]
+/// and the chunked-ingest pipeline included - can be exercised in tests
+/// without a running Ollama/OpenAI endpoint.
+///
+/// [`QueryConfig`]: crate::ingest::query::QueryConfig
+pub struct DummyProvider {
+    dimensions: usize,
+}
+
+impl DummyProvider {
+    /// Builds a provider reporting `dimensions`-sized vectors.
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+}
+
+impl Default for DummyProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_OLLAMA_DIMENSIONS)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for DummyProvider {
+    async fn embed_batch(&self, texts: &[String], _max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let mut vector = vec![0.0_f32; self.dimensions];
+                vector[0] = text.len() as f32;
+                vector
+            })
+            .collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
     }
 }
 
@@ -304,9 +1073,10 @@ mod tests {
         // Empty embedding
         assert!(validate_embedding(&[]).is_err());
 
-        // Too small embedding
+        // Small embeddings are no longer rejected - dimensionality is
+        // model-dependent and checked per-provider instead.
         let small_embedding = vec![0.1; 50];
-        assert!(validate_embedding(&small_embedding).is_err());
+        assert!(validate_embedding(&small_embedding).is_ok());
 
         // Invalid values
         let invalid_embedding = vec![f32::NAN; 384];
@@ -317,8 +1087,8 @@ mod tests {
     }
 
     #[test]
-    fn test_embed_request_serialization() {
-        let request = EmbedRequest {
+    fn test_ollama_embed_request_serialization() {
+        let request = OllamaEmbedRequest {
             model: "test-model",
             prompt: "test text",
             stream: false,
@@ -331,12 +1101,224 @@ mod tests {
     }
 
     #[test]
-    fn test_embed_response_deserialization() {
+    fn test_ollama_embed_response_deserialization() {
         let json = r#"{"embedding": [0.1, 0.2, 0.3]}"#;
-        let response: EmbedResponse = serde_json::from_str(json).unwrap();
+        let response: OllamaEmbedResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.embedding, vec![0.1, 0.2, 0.3]);
     }
 
+    #[test]
+    fn test_openai_embed_request_serialization() {
+        let request = OpenAiEmbedRequest {
+            model: "text-embedding-3-small",
+            input: "test text",
+        };
+
+        let serialized = serde_json::to_string(&request).unwrap();
+        assert!(serialized.contains("text-embedding-3-small"));
+        assert!(serialized.contains("test text"));
+    }
+
+    #[test]
+    fn test_openai_embed_response_deserialization() {
+        let json = r#"{"data": [{"embedding": [0.1, 0.2, 0.3]}]}"#;
+        let response: OpenAiEmbedResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.data[0].embedding, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_create_openai_config() {
+        let config = create_openai_config(
+            "text-embedding-3-small",
+            "https://api.openai.com/v1/embeddings",
+            "sk-test",
+        );
+        assert_eq!(config.model, "text-embedding-3-small");
+        assert_eq!(config.backend, Backend::OpenAi);
+        assert_eq!(config.auth_token.as_deref(), Some("sk-test"));
+    }
+
+    #[test]
+    fn test_create_config_extra_params_not_set_by_default() {
+        let config = create_config("custom-model", "http://custom:8080/api");
+        assert!(config.extra.is_empty());
+        assert_eq!(config.truncate, None);
+        assert_eq!(config.keep_alive, None);
+    }
+
+    #[test]
+    fn test_embed_config_defaults_num_ctx() {
+        let config = EmbedConfig::default();
+        assert_eq!(config.num_ctx, Some(DEFAULT_NUM_CTX));
+    }
+
+    #[test]
+    fn test_with_num_ctx_overrides_default() {
+        let config = EmbedConfig::default().with_num_ctx(8192);
+        assert_eq!(config.num_ctx, Some(8192));
+    }
+
+    #[test]
+    fn test_embed_config_defaults_max_concurrent_requests() {
+        let config = EmbedConfig::default();
+        assert_eq!(config.max_concurrent_requests, DEFAULT_MAX_CONCURRENT_REQUESTS);
+    }
+
+    #[test]
+    fn test_with_max_concurrent_requests_overrides_default() {
+        let config = EmbedConfig::default().with_max_concurrent_requests(8);
+        assert_eq!(config.max_concurrent_requests, 8);
+    }
+
+    #[test]
+    fn test_ollama_provider_default_dimensions() {
+        let provider = OllamaProvider::new(Client::new(), EmbedConfig::default());
+        assert_eq!(provider.dimensions(), DEFAULT_OLLAMA_DIMENSIONS);
+
+        let provider = provider.with_dimensions(768);
+        assert_eq!(provider.dimensions(), 768);
+    }
+
+    #[test]
+    fn test_ollama_provider_dimensions_prefers_probed_value() {
+        let provider = OllamaProvider::new(Client::new(), EmbedConfig::default());
+        provider.probed_dimensions.set(777).unwrap();
+        assert_eq!(provider.dimensions(), 777);
+    }
+
+    #[test]
+    fn test_check_dimensions_match() {
+        assert!(check_dimensions_match(&[vec![0.0; 4], vec![0.0; 4]], 4).is_ok());
+        assert!(check_dimensions_match(&[vec![0.0; 4], vec![0.0; 5]], 4).is_err());
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(parse_retry_after("30"), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_in_the_past_is_zero() {
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_retry_after_rejects_garbage() {
+        assert_eq!(parse_retry_after("not a delay"), None);
+    }
+
+    #[test]
+    fn test_openai_provider_known_model_dimensions() {
+        let config = create_openai_config(
+            "text-embedding-3-small",
+            "https://api.openai.com/v1/embeddings",
+            "sk-test",
+        );
+        let provider = OpenAiProvider::new(Client::new(), config);
+        assert_eq!(provider.dimensions(), 1536);
+    }
+
+    #[test]
+    fn test_openai_provider_unknown_model_falls_back_to_default() {
+        let config = create_openai_config(
+            "some-future-model",
+            "https://api.openai.com/v1/embeddings",
+            "sk-test",
+        );
+        let provider = OpenAiProvider::new(Client::new(), config);
+        assert_eq!(provider.dimensions(), DEFAULT_OPENAI_DIMENSIONS);
+    }
+
+    #[test]
+    fn test_openai_provider_dimensions_prefers_probed_value() {
+        let config = create_openai_config(
+            "text-embedding-3-small",
+            "https://api.openai.com/v1/embeddings",
+            "sk-test",
+        );
+        let provider = OpenAiProvider::new(Client::new(), config);
+        provider.probed_dimensions.set(42).unwrap();
+        assert_eq!(provider.dimensions(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_dummy_provider_embed_batch() {
+        let provider = DummyProvider::new(8);
+        let embeddings = provider
+            .embed_batch(&["hi".to_string(), "hello".to_string()], 100)
+            .await
+            .unwrap();
+
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[0].len(), 8);
+        assert_eq!(embeddings[0][0], 2.0);
+        assert_eq!(embeddings[1][0], 5.0);
+        assert_eq!(provider.dimensions(), 8);
+    }
+
+    #[test]
+    fn test_dummy_provider_default_dimensions() {
+        assert_eq!(DummyProvider::default().dimensions(), DEFAULT_OLLAMA_DIMENSIONS);
+    }
+
+    #[test]
+    fn test_rest_embed_config_rejects_malformed_url() {
+        assert!(RestEmbedConfig::new("not a url", "{{text}}", "embedding").is_err());
+    }
+
+    #[test]
+    fn test_rest_embed_config_builder() {
+        let config = RestEmbedConfig::new(
+            "https://api.example.com/embed",
+            r#"{"input": {{text}}}"#,
+            "embedding",
+        )
+        .unwrap()
+        .with_header("X-Custom", "value")
+        .with_auth_token("sk-test");
+
+        assert_eq!(config.headers, vec![("X-Custom".to_string(), "value".to_string())]);
+        assert_eq!(config.auth_token.as_deref(), Some("sk-test"));
+    }
+
+    #[test]
+    fn test_fill_rest_template_single() {
+        let body = fill_rest_template(r#"{"model": "m", "input": {{text}}}"#, &["hi".to_string()]).unwrap();
+        assert_eq!(body["model"], "m");
+        assert_eq!(body["input"], "hi");
+    }
+
+    #[test]
+    fn test_fill_rest_template_batch() {
+        let texts = vec!["a".to_string(), "b".to_string()];
+        let body = fill_rest_template(r#"{"inputs": {{texts}}}"#, &texts).unwrap();
+        assert_eq!(body["inputs"], serde_json::json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_fill_rest_template_missing_placeholder_errors() {
+        assert!(fill_rest_template(r#"{"model": "m"}"#, &["hi".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_walk_response_path_nested_with_array_index() {
+        let response = serde_json::json!({"data": [{"embedding": [0.1, 0.2]}]});
+        let value = walk_response_path(&response, "data.0.embedding").unwrap();
+        assert_eq!(value, &serde_json::json!([0.1, 0.2]));
+    }
+
+    #[test]
+    fn test_walk_response_path_missing_segment_errors() {
+        let response = serde_json::json!({"data": []});
+        assert!(walk_response_path(&response, "data.0.embedding").is_err());
+    }
+
+    #[test]
+    fn test_extract_rest_vector_rejects_non_numeric_entries() {
+        let value = serde_json::json!([0.1, "oops", 0.3]);
+        assert!(extract_rest_vector(&value).is_err());
+    }
+
     // Integration tests would require a running Ollama instance
     #[cfg(feature = "integration-tests")]
     mod integration {
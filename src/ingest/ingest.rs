@@ -0,0 +1,1010 @@
+//! File ingestion module for semantic search indexing.
+//!
+//! This module handles the ingestion of files into a searchable vector index.
+//! It processes files, generates embeddings, and builds an HNSW index for semantic search
+//! using the HNSW algorithm for efficient nearest neighbor search in high-dimensional spaces.
+//! This creates a searchable database of file contents based on their semantic meaning.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fs::File, path::PathBuf, sync::Arc, time::Instant};
+use walkdir::WalkDir;
+
+use super::chunking;
+use super::embed::{self, EmbeddingProvider, OllamaProvider};
+use super::report::{FileRecord, IngestError};
+use super::vector_store::{self, VectorStore};
+
+/// Directories to skip during file traversal.
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".venv",
+    ".cargo",
+    ".github",
+    ".vscode",
+    "target",
+    "node_modules",
+    "build",
+    "dist",
+    "docs/api",
+    "rust_ingest/target",
+    "rust_ingest/Cargo.lock",
+];
+
+/// Maximum number of characters to read from each file for embedding.
+const MAX_FILE_CHARS: usize = 800;
+
+/// Maximum number of tokens for embedding API requests.
+const MAX_EMBEDDING_TOKENS: usize = 600;
+
+/// Progress reporting interval (number of files).
+const PROGRESS_INTERVAL: usize = 10;
+
+/// Supported file extensions for semantic indexing.
+const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "md", "json", "jsonl", "rs", "py", "js", "ts", "go", "java", "rb", "c", "cpp", "h", "hpp", "sh",
+];
+
+/// Extensions chunked with the code-aware splitter (syntax-node
+/// boundaries) rather than the prose splitter (paragraph boundaries).
+const CODE_EXTENSIONS: &[&str] =
+    &["rs", "py", "js", "ts", "go", "java", "rb", "c", "cpp", "h", "hpp", "sh"];
+
+/// Sidecar file, next to the index, recording each source file's digest
+/// and the chunks it produced, so incremental runs can skip unchanged
+/// files instead of re-embedding them.
+const INCREMENTAL_CACHE_FILE: &str = "incremental_cache.json";
+
+/// Default `.jsonl` streaming threshold: files at or above 20 MiB are
+/// streamed line-by-line rather than read whole.
+const JSONL_STREAMING_THRESHOLD_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Configuration for the ingestion process.
+#[derive(Clone)]
+pub struct IngestConfig {
+    /// Root directory to start ingestion from.
+    pub root_dir: PathBuf,
+    /// Maximum characters to read per file.
+    pub max_chars: usize,
+    /// Maximum tokens for embedding requests.
+    pub max_tokens: usize,
+    /// Maximum number of files to process concurrently.
+    /// If None, defaults to the number of CPU cores.
+    pub max_concurrent_files: Option<usize>,
+    /// Embedding backend used for every file in this run. Defaults to a
+    /// local `OllamaProvider`, so ingestion works offline out of the box;
+    /// swap in `embed::OpenAiProvider` (or any other implementor) to
+    /// index against a hosted model instead.
+    pub provider: Arc<dyn EmbeddingProvider>,
+    /// When true, reuse chunks from the incremental cache for files whose
+    /// content digest hasn't changed since the last run instead of
+    /// re-embedding the whole corpus. Defaults to `false` so a plain
+    /// `run()` always produces a full, from-scratch index.
+    pub incremental: bool,
+    /// URI-style address selecting the vector store backend, e.g.
+    /// `hnsw:./data` (the production on-disk index) or `memory:` (an
+    /// in-memory store with no persistence, for fast tests). Defaults to
+    /// `hnsw:`, matching this pipeline's historical on-disk index.
+    pub vector_store_addr: String,
+    /// `.jsonl` files at or above this size are streamed line-by-line
+    /// instead of being read and parsed as a whole, so multi-gigabyte
+    /// dumps never sit fully in memory. Smaller files take the simpler
+    /// whole-file path. Set to `0` to always stream `.jsonl` files, or to
+    /// `u64::MAX` to disable streaming entirely.
+    pub jsonl_streaming_threshold_bytes: u64,
+}
+
+impl std::fmt::Debug for IngestConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IngestConfig")
+            .field("root_dir", &self.root_dir)
+            .field("max_chars", &self.max_chars)
+            .field("max_tokens", &self.max_tokens)
+            .field("max_concurrent_files", &self.max_concurrent_files)
+            .field("provider_dimensions", &self.provider.dimensions())
+            .field("incremental", &self.incremental)
+            .field("vector_store_addr", &self.vector_store_addr)
+            .field("jsonl_streaming_threshold_bytes", &self.jsonl_streaming_threshold_bytes)
+            .finish()
+    }
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self {
+            root_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            max_chars: MAX_FILE_CHARS,
+            max_tokens: MAX_EMBEDDING_TOKENS,
+            max_concurrent_files: None,
+            provider: Arc::new(OllamaProvider::new(reqwest::Client::new(), embed::EmbedConfig::default())),
+            incremental: false,
+            vector_store_addr: "hnsw:".to_string(),
+            jsonl_streaming_threshold_bytes: JSONL_STREAMING_THRESHOLD_BYTES,
+        }
+    }
+}
+
+/// A single source file's entry in the incremental cache: the digest used
+/// to detect changes, and the chunks (with their embeddings) it produced
+/// the last time it was embedded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    digest: String,
+    chunks: Vec<(ChunkMeta, Vec<f32>)>,
+}
+
+/// path -> cached digest/chunks, persisted as [`INCREMENTAL_CACHE_FILE`].
+type IncrementalCache = HashMap<PathBuf, CachedFile>;
+
+/// Computes a hex-encoded SHA-256 digest of a file's raw bytes, used to
+/// detect whether its content changed since the last incremental run.
+fn compute_file_digest(path: &std::path::Path) -> Result<String> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read file for digest: {}", path.display()))?;
+    let digest = Sha256::digest(&bytes);
+    Ok(format!("{digest:x}"))
+}
+
+/// Loads the incremental cache from disk, returning an empty cache if it
+/// doesn't exist yet (e.g. the first incremental run).
+fn load_incremental_cache(output_dir: &std::path::Path) -> IncrementalCache {
+    let path = output_dir.join(INCREMENTAL_CACHE_FILE);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return IncrementalCache::new();
+    };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+/// Persists the incremental cache next to the index.
+fn save_incremental_cache(output_dir: &std::path::Path, cache: &IncrementalCache) -> Result<()> {
+    let path = output_dir.join(INCREMENTAL_CACHE_FILE);
+    let file = File::create(&path)
+        .with_context(|| format!("Failed to create incremental cache file: {}", path.display()))?;
+    serde_json::to_writer(file, cache)
+        .with_context(|| format!("Failed to write incremental cache to: {}", path.display()))
+}
+
+/// Metadata describing a single embedded sub-chunk, keyed by its position
+/// in the HNSW index (the `n`th entry inserted corresponds to `metadata[n]`).
+///
+/// Storing the source span alongside each vector lets search return the
+/// exact snippet that matched instead of just the file it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkMeta {
+    /// File the chunk was extracted from.
+    pub path: PathBuf,
+    /// JSON field within the file the chunk's text came from.
+    pub field: String,
+    /// Start offset, in chars, of the chunk within the field's text.
+    pub char_start: usize,
+    /// End offset, in chars, of the chunk within the field's text.
+    pub char_end: usize,
+    /// Human-readable label for the chunk, when the ingest path that
+    /// produced it has something more descriptive than `field` (e.g. the
+    /// chunked-ingest pipeline's per-entity chunk labels). `#[serde(default)]`
+    /// so `meta.json` files written before this field existed still load.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Index, among the JSON objects the source file was chunked from, of
+    /// the specific object `field` came from - e.g. the second entry of a
+    /// top-level array, or the second record of a `.jsonl` file. Lets
+    /// re-reading the chunk pick the exact object it was sliced from
+    /// instead of guessing by field name, which silently grabs the wrong
+    /// text when two objects share a field name (two characters both
+    /// having a `description`, say). `None` for non-JSON files, which are
+    /// chunked as a single synthetic field covering the whole file, and
+    /// for `meta.json` files written before this field existed
+    /// (`#[serde(default)]`).
+    #[serde(default)]
+    pub record_index: Option<usize>,
+}
+
+/// Statistics from the ingestion process.
+#[derive(Debug, Clone)]
+pub struct IngestStats {
+    /// Total number of files processed.
+    pub files_processed: usize,
+    /// Total number of files skipped.
+    pub files_skipped: usize,
+    /// Output directory path.
+    pub output_dir: PathBuf,
+    /// Per-file records (filename, chars read, tokens, embedding
+    /// dimensions, elapsed time) for the files that were processed.
+    pub file_records: Vec<FileRecord>,
+    /// Errors encountered while processing individual files.
+    pub errors: Vec<IngestError>,
+    /// Files reused from the incremental cache (unchanged digest), rather
+    /// than re-embedded. Zero unless `IngestConfig::incremental` is set.
+    pub files_reused: usize,
+    /// Files re-embedded because they were new or their digest changed.
+    /// Equal to `files_processed` when `IngestConfig::incremental` is off.
+    pub files_reembedded: usize,
+    /// Files present in the incremental cache but no longer found on
+    /// disk, whose chunks were dropped from the index.
+    pub files_removed: usize,
+    /// Total JSONL records streamed across all files that took the
+    /// streaming ingest path. Zero for runs with no large `.jsonl` inputs.
+    pub records_processed: usize,
+}
+
+/// Main ingestion function that processes files and builds a searchable vector index.
+///
+/// # Errors
+/// Returns an error if file system operations, embedding requests, or index
+/// persistence fail.
+pub async fn run() -> Result<IngestStats> {
+    run_with_config(IngestConfig::default()).await
+}
+
+/// Runs ingestion with custom configuration.
+///
+/// # Errors
+/// Returns an error if any step of the ingestion process fails.
+pub async fn run_with_config(config: IngestConfig) -> Result<IngestStats> {
+    println!(
+        "Embedding provider reports {}-dimensional vectors",
+        config.provider.dimensions()
+    );
+
+    let mut store = vector_store::from_addr(&config.vector_store_addr)?;
+    let mut chunk_metadata = Vec::new();
+    let mut stats = IngestStats {
+        files_processed: 0,
+        files_skipped: 0,
+        output_dir: config.root_dir.join("data"),
+        file_records: Vec::new(),
+        errors: Vec::new(),
+        files_reused: 0,
+        files_reembedded: 0,
+        files_removed: 0,
+        records_processed: 0,
+    };
+
+    process_directory_tree(&config, store.as_mut(), &mut chunk_metadata, &mut stats).await?;
+    store
+        .persist(&stats.output_dir)
+        .context("Failed to persist vector store")?;
+    persist_chunk_metadata(&chunk_metadata, &stats.output_dir)?;
+
+    println!(
+        "Ingestion complete: {} files processed ({} reused, {} re-embedded, {} removed, {} streamed records), {} files skipped → {}",
+        stats.files_processed,
+        stats.files_reused,
+        stats.files_reembedded,
+        stats.files_removed,
+        stats.records_processed,
+        stats.files_skipped,
+        stats.output_dir.join("index.hnsw.*").display()
+    );
+
+    Ok(stats)
+}
+
+/// Processes all files in the directory tree with parallel execution.
+async fn process_directory_tree(
+    config: &IngestConfig,
+    store: &mut dyn VectorStore,
+    chunk_metadata: &mut Vec<ChunkMeta>,
+    stats: &mut IngestStats,
+) -> Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinSet;
+
+    let max_concurrent = config.max_concurrent_files.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+    });
+
+    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    let mut tasks = JoinSet::new();
+
+    let processed_count = Arc::new(AtomicUsize::new(0));
+    let skipped_count = Arc::new(AtomicUsize::new(0));
+    let file_paths = Arc::new(Mutex::new(Vec::new()));
+    let file_records = Arc::new(Mutex::new(Vec::new()));
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let new_cache: Arc<Mutex<IncrementalCache>> = Arc::new(Mutex::new(IncrementalCache::new()));
+
+    let previous_cache = if config.incremental {
+        load_incremental_cache(&stats.output_dir)
+    } else {
+        IncrementalCache::new()
+    };
+
+    let mut candidate_files = Vec::new();
+    for entry in WalkDir::new(&config.root_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+
+        if should_skip_path(path) {
+            stats.files_skipped += 1;
+            continue;
+        }
+
+        if !is_supported_file(path) {
+            stats.files_skipped += 1;
+            continue;
+        }
+
+        candidate_files.push(path.to_path_buf());
+    }
+
+    println!("Found {} files to process", candidate_files.len());
+
+    let mut reused_files = 0usize;
+    let mut reembedded_files = 0usize;
+
+    for (file_id, path) in candidate_files.iter().enumerate() {
+        if config.incremental {
+            if let Some(cached) = previous_cache.get(path) {
+                if compute_file_digest(path).ok().as_deref() == Some(cached.digest.as_str()) {
+                    reused_files += 1;
+                    processed_count.fetch_add(1, Ordering::SeqCst);
+                    file_paths
+                        .lock()
+                        .unwrap()
+                        .push((file_id, path.clone(), cached.chunks.clone()));
+                    new_cache.lock().unwrap().insert(path.clone(), cached.clone());
+                    continue;
+                }
+            }
+        }
+        reembedded_files += 1;
+
+        let semaphore_clone = semaphore.clone();
+        let config_clone = config.clone();
+        let processed_count_clone = processed_count.clone();
+        let skipped_count_clone = skipped_count.clone();
+        let file_paths_clone = file_paths.clone();
+        let file_records_clone = file_records.clone();
+        let errors_clone = errors.clone();
+        let new_cache_clone = new_cache.clone();
+        let path_clone = path.clone();
+        let max_tokens = config.max_tokens;
+
+        tasks.spawn(async move {
+            let _permit = semaphore_clone.acquire().await.unwrap();
+            let started = Instant::now();
+
+            match process_single_file_for_embedding(&path_clone, &config_clone).await {
+                Ok((chunks, chars_read, records_read)) => {
+                    let count = processed_count_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                    let embedding_dimensions =
+                        chunks.first().map(|(_, embedding)| embedding.len()).unwrap_or(0);
+
+                    file_records_clone.lock().unwrap().push(FileRecord {
+                        filename: path_clone.clone(),
+                        chars_read,
+                        tokens: max_tokens,
+                        embedding_dimensions,
+                        elapsed_secs: started.elapsed().as_secs_f64(),
+                        records_read,
+                    });
+
+                    if let Ok(digest) = compute_file_digest(&path_clone) {
+                        new_cache_clone.lock().unwrap().insert(
+                            path_clone.clone(),
+                            CachedFile { digest, chunks: chunks.clone() },
+                        );
+                    }
+
+                    let mut metadata = file_paths_clone.lock().unwrap();
+                    metadata.push((file_id, path_clone, chunks));
+
+                    if count % PROGRESS_INTERVAL == 0 {
+                        println!("Processed {count} files…");
+                    }
+
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to process file {}: {}",
+                        path_clone.display(),
+                        e
+                    );
+                    errors_clone.lock().unwrap().push(IngestError {
+                        message: e.to_string(),
+                        file: path_clone.clone(),
+                    });
+                    skipped_count_clone.fetch_add(1, Ordering::SeqCst);
+                    Err(e)
+                }
+            }
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        // Individual file failures are recorded in `errors` rather than
+        // aborting the whole run; only propagate join errors here.
+        let _ = result?;
+    }
+
+    stats.files_processed += processed_count.load(Ordering::SeqCst);
+    stats.files_skipped += skipped_count.load(Ordering::SeqCst);
+    stats.file_records.extend(file_records.lock().unwrap().drain(..));
+    stats.records_processed += stats
+        .file_records
+        .iter()
+        .filter_map(|record| record.records_read)
+        .sum::<usize>();
+    stats.errors.extend(errors.lock().unwrap().drain(..));
+    stats.files_reused += reused_files;
+    stats.files_reembedded += reembedded_files;
+
+    if config.incremental {
+        let candidate_set: std::collections::HashSet<&PathBuf> = candidate_files.iter().collect();
+        stats.files_removed += previous_cache
+            .keys()
+            .filter(|path| !candidate_set.contains(path))
+            .count();
+    }
+
+    let mut results = file_paths.lock().unwrap();
+    results.sort_by_key(|(id, _, _)| *id);
+
+    for (_, _path, chunks) in results.iter() {
+        for (meta, embedding) in chunks {
+            let chunk_id = chunk_metadata.len();
+            store.insert(chunk_id, embedding.as_slice())?;
+            chunk_metadata.push(meta.clone());
+        }
+    }
+
+    println!(
+        "Successfully indexed {} sub-chunks across {} files",
+        chunk_metadata.len(),
+        results.len()
+    );
+
+    if config.incremental {
+        save_incremental_cache(&stats.output_dir, &new_cache.lock().unwrap())
+            .context("Failed to persist incremental cache")?;
+    }
+
+    Ok(())
+}
+
+/// Determines if a path should be skipped during traversal.
+pub(crate) fn should_skip_path(path: &std::path::Path) -> bool {
+    SKIP_DIRS.iter().any(|&dir| path.ends_with(dir))
+}
+
+/// Checks if a file has a supported extension for indexing, looking past
+/// a `.gz`/`.zst` compression suffix if present.
+pub(crate) fn is_supported_file(path: &std::path::Path) -> bool {
+    let (_, decompressed_name) = super::compression::detect_compression(path);
+    decompressed_name
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext))
+}
+
+/// Whether `ext` should be chunked with the code-aware splitter instead
+/// of the prose splitter.
+fn is_code_extension(ext: &str) -> bool {
+    CODE_EXTENSIONS.contains(&ext)
+}
+
+/// Processes a single file for embedding without modifying the index.
+///
+/// Large `.jsonl` files (at or above
+/// [`IngestConfig::jsonl_streaming_threshold_bytes`]) take a streaming
+/// path that never holds the whole file or all its parsed records in
+/// memory at once; every other file is read and chunked as a whole, as
+/// before.
+///
+/// # Errors
+/// Returns an error if file reading or embedding generation fails.
+async fn process_single_file_for_embedding(
+    path: &std::path::Path,
+    config: &IngestConfig,
+) -> Result<(Vec<(ChunkMeta, Vec<f32>)>, usize, Option<usize>)> {
+    let (_, decompressed_name) = super::compression::detect_compression(path);
+    let ext = decompressed_name.extension().and_then(|e| e.to_str()).unwrap_or("").to_string();
+
+    if ext == "jsonl" {
+        let size = tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("Failed to stat file: {}", path.display()))?
+            .len();
+        if size >= config.jsonl_streaming_threshold_bytes {
+            return process_jsonl_file_streaming(path, config).await;
+        }
+    }
+
+    let content = super::compression::read_possibly_compressed(path)
+        .await
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let chars_read = content.chars().count();
+
+    // JSON/JSONL files are chunked per object field; everything else
+    // (prose or source code) is chunked as a single synthetic field
+    // covering the whole file.
+    // Object index (position among the objects chunked from this file) is
+    // threaded alongside each field so `ChunkMeta.record_index` can later
+    // point back at the exact object a chunk came from, not just its field
+    // name - see `ChunkMeta::record_index`.
+    let fields: Vec<(Option<usize>, String, String)> = if ext == "json" || ext == "jsonl" {
+        let objects: Vec<serde_json::Value> = if ext == "jsonl" {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .filter(serde_json::Value::is_object)
+                .collect()
+        } else {
+            match serde_json::from_str(&content) {
+                Ok(serde_json::Value::Array(arr)) => {
+                    arr.into_iter().filter(serde_json::Value::is_object).collect()
+                }
+                Ok(obj) => vec![obj],
+                Err(e) => return Err(anyhow::anyhow!("Failed to parse JSON: {}", e)),
+            }
+        };
+
+        objects
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, obj)| obj.as_object().map(|map| (idx, map)))
+            .flat_map(|(idx, map)| {
+                map.iter().map(move |(field, value)| {
+                    let field_str = match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        _ => value.to_string(),
+                    };
+                    (Some(idx), field.clone(), field_str)
+                })
+            })
+            .collect()
+    } else {
+        let field_name = if is_code_extension(&ext) { "source" } else { "content" };
+        vec![(None, field_name.to_string(), content.clone())]
+    };
+
+    let is_code = is_code_extension(&ext);
+
+    let mut pending_metas = Vec::new();
+    let mut pending_texts = Vec::new();
+    for (record_index, field, field_str) in &fields {
+        for span in chunking::chunk_text(field_str, is_code, config.max_tokens) {
+            pending_metas.push(ChunkMeta {
+                path: path.to_path_buf(),
+                field: field.clone(),
+                char_start: span.char_start,
+                char_end: span.char_end,
+                label: None,
+                record_index: *record_index,
+            });
+            pending_texts.push(span.text);
+        }
+    }
+
+    let indexed_embeddings = embed_chunks_in_batches(
+        config.provider.as_ref(),
+        &pending_metas,
+        &pending_texts,
+        config.max_tokens,
+        path,
+    )
+    .await;
+    println!(
+        "[DEBUG] Total sub-chunks embedded for file {}: {}",
+        path.display(),
+        indexed_embeddings.len()
+    );
+    if indexed_embeddings.is_empty() {
+        return Err(anyhow::anyhow!("No embeddings generated for file: {}", path.display()));
+    }
+    Ok((indexed_embeddings, chars_read, None))
+}
+
+/// Streaming path for large `.jsonl` files: reads one record per line via
+/// a buffered reader, chunks and embeds it immediately, and never
+/// accumulates the whole file's text or records in memory. Chunks are
+/// still embedded in [`EMBED_BATCH_SIZE`]-sized batches, flushed as soon
+/// as enough records have accumulated rather than only once the whole
+/// file has been read.
+async fn process_jsonl_file_streaming(
+    path: &std::path::Path,
+    config: &IngestConfig,
+) -> Result<(Vec<(ChunkMeta, Vec<f32>)>, usize, Option<usize>)> {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = super::compression::open_lines_possibly_compressed(path)
+        .await
+        .with_context(|| format!("Failed to open file: {}", path.display()))?;
+
+    let mut indexed_embeddings = Vec::new();
+    let mut pending_metas = Vec::new();
+    let mut pending_texts = Vec::new();
+    let mut chars_read = 0usize;
+    let mut records_read = 0usize;
+
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .with_context(|| format!("Failed to read line from: {}", path.display()))?
+    {
+        let Ok(serde_json::Value::Object(map)) = serde_json::from_str::<serde_json::Value>(&line)
+        else {
+            continue;
+        };
+        records_read += 1;
+        chars_read += line.chars().count();
+
+        for (field, value) in &map {
+            let field_str = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            for span in chunking::chunk_text(&field_str, false, config.max_tokens) {
+                pending_metas.push(ChunkMeta {
+                    path: path.to_path_buf(),
+                    field: field.clone(),
+                    char_start: span.char_start,
+                    char_end: span.char_end,
+                    label: None,
+                    record_index: Some(records_read - 1),
+                });
+                pending_texts.push(span.text);
+            }
+        }
+
+        if pending_metas.len() >= EMBED_BATCH_SIZE {
+            indexed_embeddings.extend(
+                embed_chunks_in_batches(
+                    config.provider.as_ref(),
+                    &pending_metas,
+                    &pending_texts,
+                    config.max_tokens,
+                    path,
+                )
+                .await,
+            );
+            pending_metas.clear();
+            pending_texts.clear();
+        }
+    }
+
+    if !pending_metas.is_empty() {
+        indexed_embeddings.extend(
+            embed_chunks_in_batches(
+                config.provider.as_ref(),
+                &pending_metas,
+                &pending_texts,
+                config.max_tokens,
+                path,
+            )
+            .await,
+        );
+    }
+
+    println!(
+        "[DEBUG] Streamed {} records / {} sub-chunks for file {}",
+        records_read,
+        indexed_embeddings.len(),
+        path.display()
+    );
+    if indexed_embeddings.is_empty() {
+        return Err(anyhow::anyhow!("No embeddings generated for file: {}", path.display()));
+    }
+    Ok((indexed_embeddings, chars_read, Some(records_read)))
+}
+
+/// Maximum number of distinct sub-chunk texts embedded in a single
+/// provider request. Pending chunks are flushed in batches of this size,
+/// so a file's final partial batch is still sent rather than held back
+/// waiting for more chunks to accumulate.
+const EMBED_BATCH_SIZE: usize = 16;
+
+/// Embeds `texts` (1:1 with `metas`) in fixed-size batches, deduplicating
+/// identical texts within each batch before sending it to the provider
+/// and fanning the single embedding back out to every occurrence. Each
+/// embedding is tracked by its originating `ChunkMeta`, not by arrival
+/// order, so a failed batch only drops the chunks it covered — it never
+/// shifts or mis-assigns the embeddings of other batches.
+async fn embed_chunks_in_batches(
+    provider: &dyn EmbeddingProvider,
+    metas: &[ChunkMeta],
+    texts: &[String],
+    max_tokens: usize,
+    path: &std::path::Path,
+) -> Vec<(ChunkMeta, Vec<f32>)> {
+    let mut indexed_embeddings = Vec::new();
+
+    for batch_start in (0..metas.len()).step_by(EMBED_BATCH_SIZE) {
+        let batch_end = (batch_start + EMBED_BATCH_SIZE).min(metas.len());
+        let batch_metas = &metas[batch_start..batch_end];
+        let batch_texts = &texts[batch_start..batch_end];
+
+        let mut unique_texts: Vec<String> = Vec::new();
+        let mut text_to_unique_idx: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+        let mut occurrence_unique_idx: Vec<usize> = Vec::with_capacity(batch_texts.len());
+        for text in batch_texts {
+            let idx = *text_to_unique_idx.entry(text.as_str()).or_insert_with(|| {
+                unique_texts.push(text.clone());
+                unique_texts.len() - 1
+            });
+            occurrence_unique_idx.push(idx);
+        }
+
+        match provider.embed_batch(&unique_texts, max_tokens).await {
+            Ok(unique_embeddings) => {
+                for (offset, meta) in batch_metas.iter().enumerate() {
+                    if let Some(embedding) = unique_embeddings.get(occurrence_unique_idx[offset]) {
+                        indexed_embeddings.push((meta.clone(), embedding.clone()));
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to embed batch {}-{} ({} unique texts) in file {}: {}",
+                    batch_start,
+                    batch_end,
+                    unique_texts.len(),
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    indexed_embeddings
+}
+
+/// Truncates content to the specified maximum length.
+#[allow(dead_code)]
+fn truncate_content(content: &str, max_chars: usize) -> &str {
+    if content.len() <= max_chars {
+        content
+    } else {
+        &content[..max_chars]
+    }
+}
+
+/// Persists per-chunk metadata to `meta.json`, alongside whatever the
+/// vector store itself persisted.
+fn persist_chunk_metadata(chunk_metadata: &[ChunkMeta], output_dir: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(output_dir).with_context(|| {
+        format!("Failed to create output directory: {}", output_dir.display())
+    })?;
+
+    let metadata_path = output_dir.join("meta.json");
+    let metadata_file = File::create(&metadata_path)
+        .with_context(|| format!("Failed to create metadata file: {}", metadata_path.display()))?;
+
+    serde_json::to_writer(metadata_file, &json!(chunk_metadata))
+        .with_context(|| format!("Failed to write metadata to: {}", metadata_path.display()))?;
+
+    Ok(())
+}
+
+/// A provider stub for exercising `embed_chunks_in_batches` without a
+/// real embedding backend: returns a deterministic one-hot-ish vector
+/// per unique text and records every batch it was called with, so tests
+/// can assert on deduplication and per-chunk-id failure isolation.
+#[cfg(test)]
+struct MockProvider {
+    /// Batches (as seen by the provider, after dedup) in call order.
+    calls: std::sync::Mutex<Vec<Vec<String>>>,
+    /// Texts that should make that batch fail outright.
+    fail_on: Vec<String>,
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl EmbeddingProvider for MockProvider {
+    async fn embed_batch(&self, texts: &[String], _max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+        self.calls.lock().unwrap().push(texts.to_vec());
+        if texts.iter().any(|t| self.fail_on.contains(t)) {
+            return Err(anyhow::anyhow!("mock provider failure"));
+        }
+        Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn meta(n: usize) -> ChunkMeta {
+        ChunkMeta {
+            path: PathBuf::from("doc.md"),
+            field: "content".to_string(),
+            char_start: n,
+            char_end: n + 1,
+            label: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_chunks_in_batches_dedupes_within_a_batch() {
+        let provider = MockProvider { calls: std::sync::Mutex::new(Vec::new()), fail_on: Vec::new() };
+        let metas = vec![meta(0), meta(1), meta(2)];
+        let texts = vec!["same".to_string(), "same".to_string(), "different".to_string()];
+
+        let result = embed_chunks_in_batches(&provider, &metas, &texts, 100, Path::new("doc.md")).await;
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].1, result[1].1, "identical texts must fan out the same embedding");
+        assert_ne!(result[0].1, result[2].1);
+
+        let calls = provider.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].len(), 2, "duplicate text must be sent to the provider only once");
+    }
+
+    #[tokio::test]
+    async fn test_embed_chunks_in_batches_partial_failure_skips_only_that_batch() {
+        let provider = MockProvider {
+            calls: std::sync::Mutex::new(Vec::new()),
+            fail_on: vec!["bad".to_string()],
+        };
+        let metas: Vec<ChunkMeta> = (0..EMBED_BATCH_SIZE + 1).map(meta).collect();
+        let mut texts: Vec<String> = (0..EMBED_BATCH_SIZE).map(|i| format!("ok-{i}")).collect();
+        texts.push("bad".to_string());
+
+        let result = embed_chunks_in_batches(&provider, &metas, &texts, 100, Path::new("doc.md")).await;
+
+        // The first batch (all "ok-*" texts) embeds successfully; the
+        // second batch (just "bad") fails and is dropped, without
+        // disturbing the first batch's chunk ids.
+        assert_eq!(result.len(), EMBED_BATCH_SIZE);
+        for (meta, _) in &result {
+            assert!(meta.char_start < EMBED_BATCH_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_should_skip_path() {
+        assert!(should_skip_path(Path::new(".git")));
+        assert!(should_skip_path(Path::new("project/.git")));
+        assert!(should_skip_path(Path::new("target")));
+        assert!(!should_skip_path(Path::new("src")));
+        assert!(!should_skip_path(Path::new("README.md")));
+    }
+
+    #[test]
+    fn test_is_supported_file() {
+        assert!(is_supported_file(Path::new("README.md")));
+        assert!(is_supported_file(Path::new("config.json")));
+        assert!(is_supported_file(Path::new("script.py")));
+        assert!(!is_supported_file(Path::new("binary.exe")));
+        assert!(!is_supported_file(Path::new("image.png")));
+    }
+
+    #[test]
+    fn test_is_code_extension() {
+        assert!(is_code_extension("rs"));
+        assert!(is_code_extension("py"));
+        assert!(!is_code_extension("md"));
+        assert!(!is_code_extension("json"));
+    }
+
+    #[test]
+    fn test_truncate_content() {
+        let long_content = "a".repeat(1000);
+        assert_eq!(truncate_content(&long_content, 500).len(), 500);
+
+        let short_content = "short";
+        assert_eq!(truncate_content(short_content, 500), "short");
+    }
+
+    #[test]
+    fn test_ingest_config_default() {
+        let config = IngestConfig::default();
+        assert_eq!(config.max_chars, MAX_FILE_CHARS);
+        assert_eq!(config.max_tokens, MAX_EMBEDDING_TOKENS);
+        assert!(!config.incremental);
+        assert_eq!(config.vector_store_addr, "hnsw:");
+        assert_eq!(config.jsonl_streaming_threshold_bytes, JSONL_STREAMING_THRESHOLD_BYTES);
+    }
+
+    #[test]
+    fn test_compute_file_digest_changes_with_content() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("doc.json");
+
+        std::fs::write(&path, r#"{"body": "one"}"#).unwrap();
+        let digest_a = compute_file_digest(&path).unwrap();
+
+        std::fs::write(&path, r#"{"body": "two"}"#).unwrap();
+        let digest_b = compute_file_digest(&path).unwrap();
+
+        assert_ne!(digest_a, digest_b);
+
+        std::fs::write(&path, r#"{"body": "one"}"#).unwrap();
+        let digest_c = compute_file_digest(&path).unwrap();
+        assert_eq!(digest_a, digest_c);
+    }
+
+    #[test]
+    fn test_load_incremental_cache_missing_file_returns_empty() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let cache = load_incremental_cache(tmp_dir.path());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_incremental_cache_round_trip() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut cache = IncrementalCache::new();
+        cache.insert(
+            PathBuf::from("doc.json"),
+            CachedFile {
+                digest: "abc123".to_string(),
+                chunks: vec![(
+                    ChunkMeta {
+                        path: PathBuf::from("doc.json"),
+                        field: "body".to_string(),
+                        char_start: 0,
+                        char_end: 3,
+                        label: None,
+                    },
+                    vec![0.1, 0.2, 0.3],
+                )],
+            },
+        );
+
+        save_incremental_cache(tmp_dir.path(), &cache).unwrap();
+        let loaded = load_incremental_cache(tmp_dir.path());
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[&PathBuf::from("doc.json")].digest, "abc123");
+    }
+
+    #[tokio::test]
+    async fn test_process_single_file_for_embedding_streams_large_jsonl() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("dump.jsonl");
+        let mut content = String::new();
+        for i in 0..20 {
+            content.push_str(&format!("{{\"body\": \"record {i}\"}}\n"));
+        }
+        std::fs::write(&path, &content).unwrap();
+
+        let config = IngestConfig {
+            provider: Arc::new(MockProvider { calls: std::sync::Mutex::new(Vec::new()), fail_on: Vec::new() }),
+            // Force the streaming path regardless of the file's actual size.
+            jsonl_streaming_threshold_bytes: 0,
+            ..IngestConfig::default()
+        };
+
+        let (chunks, chars_read, records_read) =
+            process_single_file_for_embedding(&path, &config).await.unwrap();
+
+        assert_eq!(records_read, Some(20));
+        assert_eq!(chunks.len(), 20);
+        assert!(chars_read > 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_single_file_for_embedding_small_jsonl_skips_streaming() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("dump.jsonl");
+        std::fs::write(&path, "{\"body\": \"only record\"}\n").unwrap();
+
+        let config = IngestConfig {
+            provider: Arc::new(MockProvider { calls: std::sync::Mutex::new(Vec::new()), fail_on: Vec::new() }),
+            ..IngestConfig::default()
+        };
+
+        let (chunks, _chars_read, records_read) =
+            process_single_file_for_embedding(&path, &config).await.unwrap();
+
+        assert_eq!(records_read, None, "small files should take the whole-file path");
+        assert_eq!(chunks.len(), 1);
+    }
+}
@@ -79,6 +79,23 @@ enum Commands {
         /// Language model name
         #[arg(long, default_value = "harald-phi4")]
         model_name: String,
+
+        /// Weight between vector and lexical retrieval when fusing
+        /// hybrid search results: 0.0 = keyword only, 1.0 = vector only
+        #[arg(long, default_value = "1.0")]
+        semantic_ratio: f32,
+    },
+
+    /// List every path in the index with its chunk count, and any
+    /// supported file on disk with zero chunks indexed.
+    ///
+    /// Useful for answering "why isn't this file showing up in search
+    /// results" without guessing from the ingest skip counter.
+    DebugPaths {
+        /// Root directory containing the data folder with index files
+        /// (defaults to current directory).
+        #[arg(short, long)]
+        root: Option<std::path::PathBuf>,
     },
 }
 
@@ -126,6 +143,7 @@ async fn main() -> Result<()> {
             max_context_chars,
             llm_endpoint,
             model_name,
+            semantic_ratio,
         } => {
             // Join prompt words into a single query string
             let query_text = prompt.join(" ");
@@ -136,6 +154,7 @@ async fn main() -> Result<()> {
                 max_context_chars,
                 llm_endpoint,
                 model_name,
+                semantic_ratio,
                 ..Default::default()
             };
 
@@ -145,12 +164,42 @@ async fn main() -> Result<()> {
             // Display results
             println!("🔍 Query: {query_text}");
             println!("📚 Context from {} documents:", result.num_context_docs);
-            for (i, file) in result.context_files.iter().enumerate() {
-                println!("  {}. {}", i + 1, file.display());
+            for (i, chunk) in result.context_chunks.iter().enumerate() {
+                println!(
+                    "  {}. {} [{} {}-{}]",
+                    i + 1,
+                    chunk.path.display(),
+                    chunk.field,
+                    chunk.char_start,
+                    chunk.char_end
+                );
             }
             println!("\n🤖 Response:");
             println!("{}", result.response);
         }
+
+        Commands::DebugPaths { root } => {
+            let mut config = QueryConfig::default();
+            if let Some(root_dir) = root {
+                config.root_dir = root_dir;
+            }
+
+            let report = query::debug_paths(&config)?;
+
+            println!("📚 Indexed paths ({}):", report.indexed.len());
+            for entry in &report.indexed {
+                println!("  {} ({} chunks)", entry.path.display(), entry.chunk_count);
+            }
+
+            if report.missing.is_empty() {
+                println!("\n✅ No supported files are missing from the index.");
+            } else {
+                println!("\n⚠️  Supported files with zero chunks indexed ({}):", report.missing.len());
+                for path in &report.missing {
+                    println!("  {}", path.display());
+                }
+            }
+        }
     }
 
     Ok(())
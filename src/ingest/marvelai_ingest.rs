@@ -1,101 +1,115 @@
-use anyhow::{Context, Result};
-use clap::Parser;
-use harald::ingest::embed;
-use harald::ingest::{run_with_config, IngestConfig};
+//! MarvelAI ingest: runs the standard ingest pipeline over
+//! `MarvelAIs.json`, converting it to JSONL first if needed.
+//!
+//! Exposed as [`run`] with [`MarvelIngestOptions`] so the unified `harald`
+//! CLI can invoke it as a subcommand instead of a standalone binary.
+
+use super::compression::Compression;
+use super::embed;
+use super::report::IngestReport;
+use super::{run_with_config, IngestConfig};
+use anyhow::Context;
+use anyhow::Result;
 use reqwest::Client;
 use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about = "MarvelAI Marvel Ingest Tool", long_about = None)]
-struct Args {
-    /// Path to the MarvelAIs.json file
-    #[arg(
-        short,
-        long,
-        default_value = "personality-archetypes/pop-culture/marvel/MarvelAIs.json"
-    )]
-    input: PathBuf,
-
-    /// Maximum characters to read per file
-    #[arg(long, default_value_t = 800)]
-    max_chars: usize,
-
-    /// Maximum tokens for embedding requests
-    #[arg(long, default_value_t = 600)]
-    max_tokens: usize,
-
-    /// Maximum number of files to process concurrently
-    #[arg(long)]
-    max_concurrent_files: Option<usize>,
+use tracing::info;
+
+/// Options for a MarvelAI ingest run.
+#[derive(Debug, Clone)]
+pub struct MarvelIngestOptions {
+    /// Path to the MarvelAIs.json (or .jsonl) file.
+    pub input: PathBuf,
+    /// Maximum characters to read per file.
+    pub max_chars: usize,
+    /// Maximum tokens for embedding requests.
+    pub max_tokens: usize,
+    /// Maximum number of files to process concurrently.
+    pub max_concurrent_files: Option<usize>,
+    /// If set, writes a versioned, structured [`IngestReport`] to this
+    /// path so downstream tooling can diff ingest runs and track
+    /// failures over time, instead of scraping stdout.
+    pub report: Option<PathBuf>,
+    /// Compression applied to the converted JSONL intermediate. Inputs
+    /// whose extension indicates compression are decompressed
+    /// transparently regardless of this setting.
+    pub compress: Compression,
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+impl Default for MarvelIngestOptions {
+    fn default() -> Self {
+        Self {
+            input: PathBuf::from("personality-archetypes/pop-culture/marvel/MarvelAIs.json"),
+            max_chars: 800,
+            max_tokens: 600,
+            max_concurrent_files: None,
+            report: None,
+            compress: Compression::None,
+        }
+    }
+}
 
-    println!("==================================================");
-    println!("🚀 HARALD MARVELAI INGEST (Rust)");
-    println!("🔍 Processing MarvelAIs.json using JSONL format");
-    println!("==================================================");
+/// Runs the MarvelAI ingest pipeline with the given options.
+///
+/// # Errors
+/// Returns an error if the input file is missing, the embedding API is
+/// unreachable, or the underlying ingest pipeline fails.
+pub async fn run(opts: MarvelIngestOptions) -> Result<()> {
+    info!(input = %opts.input.display(), "starting MarvelAI ingest");
 
-    // Verify input file exists
-    if !args.input.exists() {
-        eprintln!("❌ Input file not found: {}", args.input.display());
-        std::process::exit(1);
+    if !opts.input.exists() {
+        anyhow::bail!("Input file not found: {}", opts.input.display());
     }
 
     // Test embedding API first - exit early if it fails
     let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10)) // Reduced from 30 to 10 seconds
+        .timeout(std::time::Duration::from_secs(10))
         .build()
         .context("Failed to create HTTP client")?;
 
-    println!("Testing embedding API with model harald-phi4");
-
-    // Test with a simple string first
-    match test_embedding_api(&client, args.max_tokens).await {
-        Ok(_) => println!("  ✅ Embedding API test successful"),
-        Err(e) => {
-            eprintln!("  ❌ Embedding API test failed: {}", e);
-            eprintln!(
-                "❌ Cannot proceed without working embedding API. Please check Ollama is running."
-            );
-            std::process::exit(1);
-        }
-    }
+    info!("testing embedding API with model harald-phi4");
+    test_embedding_api(&client, opts.max_tokens)
+        .await
+        .context("Cannot proceed without working embedding API. Please check Ollama is running")?;
 
     // Convert JSON to JSONL if needed
-    let jsonl_path = prepare_jsonl_input(&args.input)?;
+    let jsonl_path = prepare_jsonl_input(&opts.input, opts.compress).await?;
 
     // Create a temporary directory for processing
     let temp_dir = tempfile::TempDir::new().context("Failed to create temporary directory")?;
 
-    // Copy JSONL to temp directory
-    let temp_jsonl = temp_dir.path().join("MarvelAIs.jsonl");
+    // Copy JSONL to temp directory, preserving whatever compression
+    // suffix prepare_jsonl_input produced.
+    let temp_jsonl = temp_dir.path().join(
+        jsonl_path
+            .file_name()
+            .context("JSONL path has no file name")?,
+    );
     fs::copy(&jsonl_path, &temp_jsonl).context("Failed to copy JSONL to temp directory")?;
 
-    // Configure ingestion to use the temp directory
     let config = IngestConfig {
         root_dir: temp_dir.path().to_path_buf(),
-        max_chars: args.max_chars,
-        max_tokens: args.max_tokens,
-        max_concurrent_files: args.max_concurrent_files,
+        max_chars: opts.max_chars,
+        max_tokens: opts.max_tokens,
+        max_concurrent_files: opts.max_concurrent_files,
+        ..IngestConfig::default()
     };
 
-    // Run the standard harald_ingest logic
-    match run_with_config(config).await {
-        Ok(stats) => {
-            println!("✅ MarvelAI ingest completed successfully!");
-            println!("📁 Processed: {} files", stats.files_processed);
-            println!("⏭️  Skipped: {} files", stats.files_skipped);
-            println!("💾 Output: {}", stats.output_dir.display());
-        }
-        Err(e) => {
-            eprintln!("❌ MarvelAI ingest failed: {}", e);
-            std::process::exit(1);
-        }
+    let stats = run_with_config(config).await.context("MarvelAI ingest failed")?;
+    info!(
+        processed = stats.files_processed,
+        skipped = stats.files_skipped,
+        output = %stats.output_dir.display(),
+        "MarvelAI ingest completed successfully"
+    );
+
+    if let Some(report_path) = &opts.report {
+        let report = IngestReport::new(stats.file_records, stats.errors);
+        let report_json = serde_json::to_string_pretty(&report)?;
+        fs::write(report_path, report_json)
+            .with_context(|| format!("Failed to write report file: {}", report_path.display()))?;
+        info!(path = %report_path.display(), "ingest report written");
     }
 
     Ok(())
@@ -111,28 +125,26 @@ async fn test_embedding_api(client: &Client, max_tokens: usize) -> Result<()> {
         endpoint: "http://localhost:11434/api/embeddings".to_string(),
         timeout_secs: 15, // Longer timeout to account for model loading
         max_retries: 2,   // Allow 2 attempts for initial API warmup
+        backend: embed::Backend::Ollama,
+        ..embed::EmbedConfig::default()
     };
 
-    println!("  Testing: embedding '{}' (using {})", test_text, test_config.endpoint);
-    println!("  Model warmup may take a moment on first request...");
+    info!(endpoint = %test_config.endpoint, "testing embedding API, model warmup may take a moment");
 
     match embed::embed_with_config(test_text, max_tokens, client, test_config).await {
         Ok(embedding) => {
             if embedding.is_empty() {
                 return Err(anyhow::anyhow!("Received empty embedding vector"));
             }
-            println!("  ✅ Embedding vectors received successfully ({} dimensions)", embedding.len());
+            info!(dimensions = embedding.len(), "embedding vectors received successfully");
             Ok(())
         }
         Err(e) => {
-            println!("  ❌ Request failed: {}", e);
-            
-            // Provide helpful debugging information
-            eprintln!("  💡 Troubleshooting tips:");
-            eprintln!("     • Ensure 'ollama serve' is running in a terminal");
-            eprintln!("     • Verify harald-phi4 model is available: ollama list");
-            eprintln!("     • Check API endpoint: curl http://localhost:11434/api/version");
-            
+            tracing::error!(
+                error = %e,
+                "embedding API test failed; ensure 'ollama serve' is running, harald-phi4 is available (ollama list), \
+                 and http://localhost:11434/api/version responds"
+            );
             Err(anyhow::anyhow!(
                 "Failed to generate embeddings with harald-phi4 model (fast test failed)"
             ))
@@ -140,23 +152,31 @@ async fn test_embedding_api(client: &Client, max_tokens: usize) -> Result<()> {
     }
 }
 
-/// Prepare JSONL input file from the MarvelAIs.json file
-fn prepare_jsonl_input(input_path: &PathBuf) -> Result<PathBuf> {
-    // If it's already JSONL, return as-is
-    if input_path.extension().and_then(|s| s.to_str()) == Some("jsonl") {
+/// Prepare JSONL input file from the MarvelAIs.json file, compressing the
+/// output according to `compress` if it isn't already JSONL.
+///
+/// The source file is read transparently regardless of whether its own
+/// extension indicates compression.
+async fn prepare_jsonl_input(input_path: &PathBuf, compress: Compression) -> Result<PathBuf> {
+    let (_, decompressed_name) = super::compression::detect_compression(input_path);
+
+    // If it's already JSONL, return as-is.
+    if decompressed_name.extension().and_then(|s| s.to_str()) == Some("jsonl") {
         return Ok(input_path.clone());
     }
 
-    // Read and parse JSON file
-    let json_content = fs::read_to_string(input_path)
+    // Read and parse JSON file, decompressing first if needed.
+    let json_content = super::compression::read_possibly_compressed(input_path)
+        .await
         .with_context(|| format!("Failed to read JSON file: {}", input_path.display()))?;
 
     let json_value: Value = serde_json::from_str(&json_content)
         .with_context(|| format!("Failed to parse JSON file: {}", input_path.display()))?;
 
-    // Create JSONL output path
+    // Create JSONL output path, with compress's extension suffix if set.
     let mut jsonl_path = input_path.clone();
     jsonl_path.set_extension("jsonl");
+    jsonl_path = compress.apply_extension(&jsonl_path);
 
     // Convert to JSONL
     let jsonl_content = match json_value {
@@ -175,17 +195,17 @@ fn prepare_jsonl_input(input_path: &PathBuf) -> Result<PathBuf> {
         }
     };
 
-    // Write JSONL file
-    fs::write(&jsonl_path, &jsonl_content)
+    super::compression::write_compressed(&jsonl_path, jsonl_content.as_bytes(), compress)
+        .await
         .with_context(|| format!("Failed to write JSONL file: {}", jsonl_path.display()))?;
 
     let line_count = jsonl_content.lines().count();
-    println!(
-        "Converting \"{}\" to JSONL at \"{}\"",
-        input_path.display(),
-        jsonl_path.display()
+    info!(
+        from = %input_path.display(),
+        to = %jsonl_path.display(),
+        lines = line_count,
+        "converted JSON to JSONL"
     );
-    println!("✅ JSONL conversion complete: {} lines", line_count);
 
     Ok(jsonl_path)
 }
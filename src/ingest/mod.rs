@@ -2,15 +2,28 @@
 //!
 //! This module provides functionality for ingesting data into the system.
 
+pub mod bench;
+pub mod bin_resolver;
 pub mod chunked_ingest;
+pub mod chunked_ingest_new;
+pub mod chunking;
 pub mod chunking_utils;
+pub mod compression;
 pub mod embed;
 pub mod ingest;
 pub mod ingest_utils;
+pub mod marvelai_ingest;
 pub mod query;
+pub mod report;
+pub mod retry_failed;
 pub mod single_character_ingest;
+pub mod vector_store;
 
 // Re-export commonly used items
-pub use ingest::{run_with_config, IngestConfig};
-pub use embed::{embed, embed_with_config, EmbedConfig};
-pub use query::QueryConfig;
+pub use ingest::{run_with_config, ChunkMeta, IngestConfig, IngestStats};
+pub use embed::{
+    embed, embed_with_config, DummyProvider, EmbedConfig, EmbeddingProvider, OllamaProvider,
+    OpenAiProvider, RestEmbedConfig, RestProvider,
+};
+pub use query::{run_streaming, CalibrationCurve, QueryConfig, ScoreCalibration};
+pub use vector_store::VectorStore;
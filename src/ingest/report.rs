@@ -0,0 +1,81 @@
+//! Versioned, structured ingest report output.
+//!
+//! Replaces the decorative stdout summary (`files_processed`,
+//! `files_skipped`, `output_dir`) with a serde-serializable [`IngestReport`]
+//! that downstream tooling can diff across runs and use to track failures
+//! over time.
+
+use serde::{Serialize, Serializer};
+use std::path::PathBuf;
+
+/// Schema version for [`IngestReport`]. Bump whenever the report's shape
+/// changes in a way downstream tooling should be aware of.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Per-file record in an [`IngestReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FileRecord {
+    /// Path of the file that was ingested, relative to the ingest root.
+    pub filename: PathBuf,
+    /// Number of characters read from the file.
+    pub chars_read: usize,
+    /// Number of tokens requested for the embedding call.
+    pub tokens: usize,
+    /// Dimensionality of the resulting embedding vector.
+    pub embedding_dimensions: usize,
+    /// Wall-clock time spent processing this file, in seconds.
+    pub elapsed_secs: f64,
+    /// Number of JSONL records streamed from this file, for files large
+    /// enough to take the streaming ingest path. `None` for files ingested
+    /// as a single whole-file read.
+    pub records_read: Option<usize>,
+}
+
+/// An error encountered while ingesting a single file.
+#[derive(Debug, Clone)]
+pub struct IngestError {
+    /// Human-readable error message.
+    pub message: String,
+    /// File that failed to ingest.
+    pub file: PathBuf,
+}
+
+/// A versioned, structured report of an ingest run.
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestReport {
+    /// Schema version of this report, for downstream compatibility checks.
+    pub schema_version: u32,
+    /// Per-file ingest records, in processing order.
+    pub files: Vec<FileRecord>,
+    /// Errors encountered during the run, flattened to one message per
+    /// line. Omitted entirely when no errors occurred.
+    #[serde(serialize_with = "serialize_errors", skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<IngestError>>,
+}
+
+impl IngestReport {
+    /// Builds a report from per-file records and any errors encountered.
+    /// `errors` is stored as `None` when empty so it's omitted from the
+    /// serialized report.
+    pub fn new(files: Vec<FileRecord>, errors: Vec<IngestError>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            files,
+            errors: if errors.is_empty() { None } else { Some(errors) },
+        }
+    }
+}
+
+/// Serializes `errors` as a flat list of `"<file>: <message>"` strings
+/// instead of nested `{message, file}` objects.
+fn serialize_errors<S>(errors: &Option<Vec<IngestError>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let errors = errors.as_ref().expect("skip_serializing_if filters out None");
+    let flat: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.file.display(), e.message))
+        .collect();
+    flat.serialize(serializer)
+}
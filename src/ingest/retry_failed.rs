@@ -0,0 +1,370 @@
+//! Resumable retry queue for chunks recorded in a `failed_chunks.log`.
+//!
+//! [`crate::ingest::single_character_ingest`] (and any other ingest path
+//! that exhausts its embedding retries) writes one [`FailedChunkRecord`]
+//! per unrecoverable chunk to `failed_chunks.log`, but nothing consumed
+//! that log until now. [`retry_log`] re-embeds every recorded chunk with
+//! the same `EMBED_MAX_RETRIES`/`EMBED_RETRY_DELAY_SECS` backoff the
+//! original ingest used, then rewrites the log in place: chunks that now
+//! succeed are dropped, chunks that still fail have their `attempts`
+//! incremented, and chunks that cross `RetryConfig::max_total_cycles`
+//! total attempts are moved to a sibling `dead_letter.log` instead of
+//! being retried again. The log format is JSONL so it stays
+//! self-contained (original prompt text included) and resumable long
+//! after the ingest process that wrote it has exited.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::ingest::embed::{EmbedConfig, EmbeddingProvider, OllamaProvider};
+
+/// A single chunk recorded in a `failed_chunks.log`, self-contained
+/// enough to retry without access to the original ingest run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedChunkRecord {
+    /// The chunk's field/sub-chunk label, as produced by
+    /// `chunk_entity_fields`.
+    pub label: String,
+    /// The exact prompt text that was sent for embedding.
+    pub prompt: String,
+    /// Number of embedding cycles this chunk has exhausted so far,
+    /// across every retry pass (not just the most recent one).
+    pub attempts: u32,
+    /// The error from the most recent failed attempt.
+    pub last_error: String,
+}
+
+/// Configuration for a [`retry_log`] pass.
+pub struct RetryConfig {
+    /// Embedding backend to retry chunks against. Defaults to a local
+    /// `OllamaProvider`, matching the provider the original ingest used.
+    pub provider: Arc<dyn EmbeddingProvider>,
+    /// Maximum embedding attempts per chunk within this pass, mirroring
+    /// the `EMBED_MAX_RETRIES` knob `single_character_ingest` reads.
+    pub max_retries: u64,
+    /// Base delay between attempts; actual backoff is
+    /// `retry_delay_secs * attempt`, mirroring `EMBED_RETRY_DELAY_SECS`.
+    pub retry_delay_secs: u64,
+    /// Total attempts (summed across every retry pass) a chunk may
+    /// accumulate before it's moved to `dead_letter.log` instead of
+    /// being retried again.
+    pub max_total_cycles: u32,
+}
+
+impl Default for RetryConfig {
+    /// Reads `EMBED_MAX_RETRIES`/`EMBED_RETRY_DELAY_SECS` the same way
+    /// `single_character_ingest::run` does, so a retry pass backs off the
+    /// same way the original ingest would have.
+    fn default() -> Self {
+        let max_retries = std::env::var("EMBED_MAX_RETRIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3);
+        let retry_delay_secs = std::env::var("EMBED_RETRY_DELAY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        Self {
+            provider: Arc::new(OllamaProvider::new(
+                reqwest::Client::new(),
+                EmbedConfig::default(),
+            )),
+            max_retries,
+            retry_delay_secs,
+            max_total_cycles: 5,
+        }
+    }
+}
+
+/// Outcome of retrying every chunk in one `failed_chunks.log`.
+#[derive(Debug, Default, Serialize)]
+pub struct RetryReport {
+    pub recovered: usize,
+    pub still_failing: usize,
+    pub dead_lettered: usize,
+}
+
+/// Retries every chunk recorded in `log_path`, rewriting it in place with
+/// only the chunks that still failed (their `attempts` incremented), and
+/// appending chunks that crossed `config.max_total_cycles` to a sibling
+/// `dead_letter.log` next to it.
+///
+/// # Errors
+/// Returns an error if `log_path` can't be read, contains a line that
+/// isn't a valid [`FailedChunkRecord`], or the rewritten logs can't be
+/// written back.
+pub async fn retry_log(log_path: &Path, config: &RetryConfig) -> Result<RetryReport> {
+    let records = read_records(log_path)?;
+
+    let mut report = RetryReport::default();
+    let mut still_failing = Vec::new();
+    let mut dead_letters = Vec::new();
+
+    for mut record in records {
+        match retry_chunk(&record, config).await {
+            Ok(()) => {
+                report.recovered += 1;
+                info!(label = %record.label, "chunk recovered on retry");
+            }
+            Err(last_error) => {
+                record.attempts += 1;
+                record.last_error = last_error;
+                if record.attempts >= config.max_total_cycles {
+                    report.dead_lettered += 1;
+                    warn!(
+                        label = %record.label,
+                        attempts = record.attempts,
+                        "chunk exceeded max_total_cycles, moving to dead letter log"
+                    );
+                    dead_letters.push(record);
+                } else {
+                    report.still_failing += 1;
+                    still_failing.push(record);
+                }
+            }
+        }
+    }
+
+    write_records(log_path, &still_failing)?;
+    if !dead_letters.is_empty() {
+        let dead_letter_path = log_path.with_file_name("dead_letter.log");
+        let mut existing = if dead_letter_path.exists() {
+            read_records(&dead_letter_path)?
+        } else {
+            Vec::new()
+        };
+        existing.extend(dead_letters);
+        write_records(&dead_letter_path, &existing)?;
+    }
+
+    info!(
+        path = %log_path.display(),
+        recovered = report.recovered,
+        still_failing = report.still_failing,
+        dead_lettered = report.dead_lettered,
+        "retry pass complete"
+    );
+    Ok(report)
+}
+
+/// Retries a single chunk up to `config.max_retries` times, backing off
+/// `config.retry_delay_secs * attempt` between tries. Returns the last
+/// error seen if every attempt failed.
+async fn retry_chunk(record: &FailedChunkRecord, config: &RetryConfig) -> Result<(), String> {
+    let mut last_error = record.last_error.clone();
+    for attempt in 1..=config.max_retries.max(1) {
+        info!(
+            label = %record.label,
+            attempt,
+            max_retries = config.max_retries,
+            "retrying chunk embedding"
+        );
+        match config
+            .provider
+            .embed_batch(std::slice::from_ref(&record.prompt), 600)
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                last_error = e.to_string();
+                warn!(label = %record.label, error = %last_error, "retry attempt failed");
+            }
+        }
+        if attempt < config.max_retries {
+            let backoff = config.retry_delay_secs * attempt;
+            tokio::time::sleep(Duration::from_secs(backoff)).await;
+        }
+    }
+    Err(last_error)
+}
+
+/// Reads `path` as JSONL, parsing each non-blank line as a
+/// [`FailedChunkRecord`].
+fn read_records(path: &Path) -> Result<Vec<FailedChunkRecord>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid record in {}", path.display()))
+        })
+        .collect()
+}
+
+/// Writes `records` to `path` as JSONL, one compact record per line.
+/// Writes an empty file when `records` is empty, so a fully-recovered
+/// log doesn't linger with stale entries.
+fn write_records(path: &Path, records: &[FailedChunkRecord]) -> Result<()> {
+    if records.is_empty() {
+        fs::write(path, "").with_context(|| format!("Failed to clear {}", path.display()))?;
+        return Ok(());
+    }
+    let content = records
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n");
+    fs::write(path, content + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Builds the [`FailedChunkRecord`] JSONL content `single_character_ingest`
+/// (or any other ingest path) should write for a set of exhausted
+/// `(label, prompt, last_error)` chunks - kept here so the write side and
+/// the read side of the log format can't drift apart.
+pub fn render_failed_chunks(chunks: &[(String, String, String)]) -> Result<String> {
+    let lines = chunks
+        .iter()
+        .map(|(label, prompt, last_error)| {
+            serde_json::to_string(&FailedChunkRecord {
+                label: label.clone(),
+                prompt: prompt.clone(),
+                attempts: 1,
+                last_error: last_error.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A provider that fails its first `fail_times` calls, then succeeds.
+    struct FlakyProvider {
+        fail_times: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FlakyProvider {
+        async fn embed_batch(&self, texts: &[String], _max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.fail_times {
+                anyhow::bail!("simulated failure {call}");
+            }
+            Ok(texts.iter().map(|_| vec![0.0; 4]).collect())
+        }
+
+        fn dimensions(&self) -> usize {
+            4
+        }
+    }
+
+    /// A provider that always fails, for exercising dead-letter promotion.
+    struct AlwaysFailProvider;
+
+    #[async_trait]
+    impl EmbeddingProvider for AlwaysFailProvider {
+        async fn embed_batch(&self, _texts: &[String], _max_tokens: usize) -> Result<Vec<Vec<f32>>> {
+            anyhow::bail!("always fails")
+        }
+
+        fn dimensions(&self) -> usize {
+            4
+        }
+    }
+
+    fn write_log(dir: &Path, records: &[FailedChunkRecord]) -> PathBuf {
+        let path = dir.join("failed_chunks.log");
+        write_records(&path, records).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn recovers_a_chunk_that_now_succeeds() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = write_log(
+            dir.path(),
+            &[FailedChunkRecord {
+                label: "bio".to_string(),
+                prompt: "bio: some text".to_string(),
+                attempts: 1,
+                last_error: "timed out".to_string(),
+            }],
+        );
+
+        let config = RetryConfig {
+            provider: Arc::new(FlakyProvider {
+                fail_times: 0,
+                calls: AtomicUsize::new(0),
+            }),
+            max_retries: 2,
+            retry_delay_secs: 0,
+            max_total_cycles: 5,
+        };
+
+        let report = retry_log(&log_path, &config).await.unwrap();
+        assert_eq!(report.recovered, 1);
+        assert_eq!(report.still_failing, 0);
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn bumps_attempts_on_continued_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = write_log(
+            dir.path(),
+            &[FailedChunkRecord {
+                label: "bio".to_string(),
+                prompt: "bio: some text".to_string(),
+                attempts: 1,
+                last_error: "timed out".to_string(),
+            }],
+        );
+
+        let config = RetryConfig {
+            provider: Arc::new(AlwaysFailProvider),
+            max_retries: 1,
+            retry_delay_secs: 0,
+            max_total_cycles: 5,
+        };
+
+        let report = retry_log(&log_path, &config).await.unwrap();
+        assert_eq!(report.still_failing, 1);
+        let remaining = read_records(&log_path).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn promotes_to_dead_letter_after_max_total_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = write_log(
+            dir.path(),
+            &[FailedChunkRecord {
+                label: "bio".to_string(),
+                prompt: "bio: some text".to_string(),
+                attempts: 2,
+                last_error: "timed out".to_string(),
+            }],
+        );
+
+        let config = RetryConfig {
+            provider: Arc::new(AlwaysFailProvider),
+            max_retries: 1,
+            retry_delay_secs: 0,
+            max_total_cycles: 3,
+        };
+
+        let report = retry_log(&log_path, &config).await.unwrap();
+        assert_eq!(report.dead_lettered, 1);
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "");
+
+        let dead_letter_path = dir.path().join("dead_letter.log");
+        let dead_letters = read_records(&dead_letter_path).unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].attempts, 3);
+    }
+}
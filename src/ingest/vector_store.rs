@@ -0,0 +1,216 @@
+//! Pluggable vector-store backend for the ingestion pipeline.
+//!
+//! `VectorStore` abstracts over how embedded chunk vectors are indexed and
+//! persisted, so the pipeline isn't wired directly to `hnsw_rs`. The
+//! concrete backend is selected from a URI-style address (e.g.
+//! `hnsw:./data`, `memory:`) via [`from_addr`]: `hnsw` builds the
+//! production on-disk HNSW index (the same `index.hnsw.*` files `query`
+//! loads), while `memory` keeps everything in a `Vec` with brute-force
+//! search and a no-op `persist` — useful for fast, filesystem-free tests.
+
+use std::path::Path;
+
+use anyhow::Result;
+use hnsw_rs::hnswio::HnswIo;
+use hnsw_rs::prelude::*;
+
+/// HNSW index construction parameters, matching the values the pipeline
+/// has always used for semantic search.
+const HNSW_MAX_CONNECTIONS: usize = 16;
+const HNSW_MAX_ELEMENTS: usize = 100_000;
+const HNSW_EF_CONSTRUCTION: usize = 200;
+const HNSW_MAX_LAYER: usize = 16;
+const HNSW_SEARCH_EF: usize = 200;
+
+/// A nearest-neighbor match returned by [`VectorStore::search`]: the id
+/// passed to [`VectorStore::insert`], and its distance from the query
+/// vector (smaller is more similar).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StoreMatch {
+    pub id: usize,
+    pub distance: f32,
+}
+
+/// A backend for storing and searching embedding vectors.
+///
+/// Implementors decide how vectors are indexed in memory and how (or
+/// whether) that index is persisted to disk; the ingestion pipeline only
+/// ever talks to this trait, never to a concrete index type directly.
+pub trait VectorStore: Send + Sync {
+    /// Inserts `vector` under `id`. `id` is the caller's own identifier
+    /// (the pipeline uses each chunk's position in the metadata list), not
+    /// assigned by the store.
+    fn insert(&mut self, id: usize, vector: &[f32]) -> Result<()>;
+
+    /// Returns the `k` nearest neighbors to `query`, nearest first.
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<StoreMatch>>;
+
+    /// Persists the store's contents to `dir`.
+    fn persist(&self, dir: &Path) -> Result<()>;
+
+    /// Loads a store's contents from `dir`.
+    fn load(dir: &Path) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+/// The production backend: an on-disk HNSW index via `hnsw_rs`.
+pub struct HnswVectorStore {
+    index: Hnsw<'static, f32, DistCosine>,
+}
+
+impl HnswVectorStore {
+    pub fn new() -> Self {
+        Self {
+            index: Hnsw::<'static, f32, DistCosine>::new(
+                HNSW_MAX_CONNECTIONS,
+                HNSW_MAX_ELEMENTS,
+                HNSW_MAX_LAYER,
+                HNSW_EF_CONSTRUCTION,
+                DistCosine {},
+            ),
+        }
+    }
+}
+
+impl Default for HnswVectorStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VectorStore for HnswVectorStore {
+    fn insert(&mut self, id: usize, vector: &[f32]) -> Result<()> {
+        self.index.insert((vector, id));
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<StoreMatch>> {
+        Ok(self
+            .index
+            .search(query, k, HNSW_SEARCH_EF)
+            .into_iter()
+            .map(|neighbour| StoreMatch { id: neighbour.d_id, distance: neighbour.distance })
+            .collect())
+    }
+
+    fn persist(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| anyhow::anyhow!("Failed to create output directory {}: {}", dir.display(), e))?;
+        self.index
+            .file_dump(dir, "index")
+            .map_err(|e| anyhow::anyhow!("Failed to save HNSW index to {}: {}", dir.display(), e))?;
+        Ok(())
+    }
+
+    fn load(dir: &Path) -> Result<Self> {
+        let mut loader = HnswIo::new(dir, "index");
+        let loaded: Hnsw<'_, f32, DistCosine> = loader
+            .load_hnsw()
+            .map_err(|e| anyhow::anyhow!("Failed to load HNSW index from {}: {}", dir.display(), e))?;
+        // SAFETY: we take ownership of `loaded` here, so extending its
+        // lifetime to 'static is sound — nothing borrows from the loader
+        // past this point.
+        let index: Hnsw<'static, f32, DistCosine> = unsafe { std::mem::transmute(loaded) };
+        Ok(Self { index })
+    }
+}
+
+/// An in-memory backend with brute-force cosine search and a no-op
+/// `persist`/`load`, for unit tests that want a real `VectorStore` without
+/// touching the filesystem.
+#[derive(Default)]
+pub struct MemoryVectorStore {
+    vectors: Vec<(usize, Vec<f32>)>,
+}
+
+impl MemoryVectorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / (norm_a * norm_b)
+}
+
+impl VectorStore for MemoryVectorStore {
+    fn insert(&mut self, id: usize, vector: &[f32]) -> Result<()> {
+        self.vectors.push((id, vector.to_vec()));
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<StoreMatch>> {
+        let mut matches: Vec<StoreMatch> = self
+            .vectors
+            .iter()
+            .map(|(id, vector)| StoreMatch { id: *id, distance: cosine_distance(query, vector) })
+            .collect();
+        matches.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(std::cmp::Ordering::Equal));
+        matches.truncate(k);
+        Ok(matches)
+    }
+
+    fn persist(&self, _dir: &Path) -> Result<()> {
+        Ok(())
+    }
+
+    fn load(_dir: &Path) -> Result<Self> {
+        Ok(Self::default())
+    }
+}
+
+/// Selects and constructs a fresh `VectorStore` backend from a URI-style
+/// address, e.g. `hnsw:./data` or `memory:`. Only the scheme (the part
+/// before `:`) determines the backend; the rest is ignored for now, room
+/// for future disk-backed KV stores that need their own connection string.
+pub fn from_addr(addr: &str) -> Result<Box<dyn VectorStore>> {
+    let scheme = addr.split(':').next().unwrap_or(addr);
+    match scheme {
+        "hnsw" => Ok(Box::new(HnswVectorStore::new())),
+        "memory" => Ok(Box::new(MemoryVectorStore::new())),
+        other => Err(anyhow::anyhow!("Unknown vector store backend: '{other}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_addr_selects_backend() {
+        assert!(from_addr("memory:").is_ok());
+        assert!(from_addr("hnsw:./data").is_ok());
+        assert!(from_addr("redis://localhost").is_err());
+    }
+
+    #[test]
+    fn test_memory_store_insert_and_search() {
+        let mut store = MemoryVectorStore::new();
+        store.insert(0, &[1.0, 0.0]).unwrap();
+        store.insert(1, &[0.0, 1.0]).unwrap();
+
+        let results = store.search(&[1.0, 0.0], 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, 0);
+    }
+
+    #[test]
+    fn test_memory_store_persist_and_load_round_trip() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut store = MemoryVectorStore::new();
+        store.insert(0, &[1.0, 2.0]).unwrap();
+        store.persist(tmp_dir.path()).unwrap();
+
+        // Memory backend doesn't actually persist; loading always starts
+        // from an empty store.
+        let loaded = MemoryVectorStore::load(tmp_dir.path()).unwrap();
+        assert!(loaded.vectors.is_empty());
+    }
+}
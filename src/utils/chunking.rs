@@ -4,10 +4,17 @@
 //! 1. Size-based: Simply splits text at character count boundaries
 //! 2. Character-based: Splits at word boundaries to preserve semantic units
 //! 3. Semantic: Splits at natural breaks like sentences and paragraphs
+//! 4. Content-defined: Splits at FastCDC rolling-hash boundaries so small
+//!    edits don't reshuffle every downstream chunk
+//! 5. Syntactic: Splits source code along tree-sitter outline boundaries so
+//!    chunks don't cut through the middle of a function or struct
 //!
 //! This is a Rust implementation of the functionality from text_chunker.sh
 
 use std::cmp;
+use std::ops::Range;
+
+use tree_sitter::Node;
 
 /// Defines different strategies for text chunking.
 #[derive(Debug, Clone)]
@@ -22,6 +29,46 @@ pub enum ChunkingStrategy {
 
     /// Splits text at natural semantic boundaries like sentences and paragraphs
     Semantic,
+
+    /// Splits text at content-defined (FastCDC) boundaries: chunk size
+    /// varies between `min` and `max` bytes, targeting `avg`, but the cut
+    /// points themselves are a function of the bytes around them rather
+    /// than a fixed offset - so inserting a byte early in the text shifts
+    /// only the chunks that actually changed, not every chunk after it.
+    /// See [`content_defined_chunking`] for the algorithm.
+    ContentDefined { min: usize, avg: usize, max: usize },
+
+    /// Splits source code along tree-sitter outline boundaries (functions,
+    /// structs, impl blocks, classes) instead of prose boundaries, so a
+    /// chunk never ends up with half a function. `max_size` is the target
+    /// chunk size in bytes; the grammar to parse with comes from
+    /// [`ChunkerOptions::grammar`]. See [`syntactic_chunking`] for the
+    /// algorithm.
+    Syntactic { max_size: usize },
+}
+
+impl ChunkingStrategy {
+    /// Maps a registry-style strategy name (`"size-based"`,
+    /// `"character-based"`, `"semantic"`, `"content-defined"`) to a
+    /// [`ChunkingStrategy`], sizing the `Size`/`Character` variants from
+    /// `max_chunk_size` directly and deriving `ContentDefined`'s
+    /// min/avg/max around it (a quarter, a half, and the full
+    /// `max_chunk_size`, mirroring FastCDC's own min/avg/max defaults of
+    /// roughly 1:2:4 around the average). Returns `None` for an
+    /// unrecognized name.
+    pub fn from_name(name: &str, max_chunk_size: usize) -> Option<Self> {
+        match name {
+            "size-based" => Some(Self::Size(max_chunk_size)),
+            "character-based" => Some(Self::Character(max_chunk_size)),
+            "semantic" => Some(Self::Semantic),
+            "content-defined" => Some(Self::ContentDefined {
+                min: max_chunk_size / 4,
+                avg: max_chunk_size / 2,
+                max: max_chunk_size,
+            }),
+            _ => None,
+        }
+    }
 }
 
 /// Configuration options for the text chunker.
@@ -38,6 +85,21 @@ pub struct ChunkerOptions {
 
     /// Whether to output debug information
     pub debug: bool,
+
+    /// The tree-sitter grammar to parse with when `strategy` is
+    /// [`ChunkingStrategy::Syntactic`] (e.g. `"rust"`, `"python"`,
+    /// `"javascript"`). Ignored by every other strategy.
+    pub grammar: Option<String>,
+
+    /// How many trailing units of each chunk to prepend onto the next one,
+    /// so adjacent chunks share context instead of splitting cleanly at
+    /// the boundary - useful for retrieval, where a query can straddle
+    /// where one chunk ends and the next begins. `0` (the default)
+    /// disables overlap. The unit is whatever the active `strategy`
+    /// naturally splits on (characters for `Size`, words for `Character`,
+    /// sentences for `Semantic`, lines for `Syntactic`), so an overlap
+    /// never splits a word or sentence in half.
+    pub overlap: usize,
 }
 
 impl Default for ChunkerOptions {
@@ -47,10 +109,25 @@ impl Default for ChunkerOptions {
             preserve_whitespace: false,
             delimiter: None,
             debug: false,
+            grammar: None,
+            overlap: 0,
         }
     }
 }
 
+/// One chunk of text together with where it came from in the original
+/// input. `byte_range` indexes into the original `&str` and always falls
+/// on a UTF-8 character boundary, while `char_range` counts Unicode scalar
+/// values independently - the two diverge as soon as the text contains
+/// anything outside ASCII, so neither can be derived from the other by a
+/// fixed ratio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    pub text: String,
+    pub byte_range: Range<usize>,
+    pub char_range: Range<usize>,
+}
+
 /// Chunks text based on the provided chunking strategy.
 ///
 /// # Arguments
@@ -77,17 +154,246 @@ impl Default for ChunkerOptions {
 /// assert!(chunks.len() > 1);
 /// ```
 pub fn chunk_text(text: &str, options: ChunkerOptions) -> Vec<String> {
-    match options.strategy {
+    chunk_text_spans(text, options)
+        .into_iter()
+        .map(|chunk| chunk.text)
+        .collect()
+}
+
+/// Chunks `text` the same way [`chunk_text`] does, but keeps each chunk's
+/// byte and char span in the original `text` alongside its content - so
+/// the ingest pipeline can record provenance for an embedding, or a caller
+/// can highlight the source region a chunk came from.
+///
+/// Chunking strategies themselves trim or reconstruct text (whitespace
+/// trimming, semantic's sentence splitting), so rather than duplicate
+/// every strategy's internals here, each chunk's span is located by
+/// searching `text` for its content starting right after the end of the
+/// previous chunk's span. This holds for every strategy in this module:
+/// chunks are always emitted in the same left-to-right order as their
+/// source, and no strategy invents text that isn't a substring of the
+/// input - they only ever trim.
+///
+/// Spans are computed from the strategy's raw chunks *before*
+/// `options.overlap` is applied, since an overlapping chunk's text
+/// deliberately repeats a slice of the input that already belongs to the
+/// previous chunk's span and so can't be located by a left-to-right scan.
+/// A chunk's `byte_range`/`char_range` therefore always cover its own core
+/// content; any overlap text borrowed from the previous chunk sits outside
+/// the span despite being part of `text`.
+pub fn chunk_text_spans(text: &str, options: ChunkerOptions) -> Vec<Chunk> {
+    let core_chunks = chunk_text_raw(text, &options);
+    let windowed_chunks = apply_overlap(core_chunks.clone(), options.overlap, &options.strategy);
+
+    let mut byte_cursor = 0usize;
+    let mut char_cursor = 0usize;
+
+    core_chunks
+        .into_iter()
+        .zip(windowed_chunks)
+        .map(|(core, windowed)| {
+            let start = byte_cursor
+                + text[byte_cursor..]
+                    .find(core.as_str())
+                    .unwrap_or(0);
+            let end = start + core.len();
+
+            let char_start = char_cursor + text[byte_cursor..start].chars().count();
+            let char_end = char_start + core.chars().count();
+
+            byte_cursor = end;
+            char_cursor = char_end;
+
+            Chunk {
+                text: windowed,
+                byte_range: start..end,
+                char_range: char_start..char_end,
+            }
+        })
+        .collect()
+}
+
+/// Dispatches to the chunking function for `options.strategy`. Split out
+/// from [`chunk_text_spans`] so the strategy match stays readable without
+/// the span-tracking bookkeeping interleaved into it.
+fn chunk_text_raw(text: &str, options: &ChunkerOptions) -> Vec<String> {
+    match &options.strategy {
         ChunkingStrategy::Size(max_size) => {
-            size_based_chunking(text, max_size, options.preserve_whitespace)
+            size_based_chunking(text, *max_size, options.preserve_whitespace)
         }
         ChunkingStrategy::Character(target_size) => {
-            character_based_chunking(text, target_size, options.preserve_whitespace)
+            character_based_chunking(text, *target_size, options.preserve_whitespace)
         }
         ChunkingStrategy::Semantic => semantic_chunking(text, options.preserve_whitespace),
+        ChunkingStrategy::ContentDefined { min, avg, max } => {
+            content_defined_chunking(text, *min, *avg, *max)
+        }
+        ChunkingStrategy::Syntactic { max_size } => {
+            syntactic_chunking(text, *max_size, options.grammar.as_deref())
+        }
+    }
+}
+
+/// Lazily yields `text`'s chunks as borrowed slices instead of
+/// materializing a `Vec<String>`, so chunking a large file read from stdin
+/// keeps memory bounded. For [`ChunkingStrategy::Size`] this walks `text`
+/// once by character boundary, slicing directly off the remaining `&str`
+/// instead of collecting an intermediate `Vec<char>` the way
+/// `size_based_chunking` does. The other strategies reconstruct or trim
+/// their boundaries in ways that can't be re-expressed as a walk over
+/// borrowed slices (semantic's sentence joins, content-defined's hash
+/// cuts, syntactic's tree-sitter walk), so they fall back to computing the
+/// full chunk list once via [`chunk_text_raw`] and locating each chunk's
+/// span in `text` the same way [`chunk_text_spans`] does - still lazy in
+/// that chunks are produced one at a time, but not allocation-free.
+///
+/// `options.overlap` is not applied here: an overlapping chunk
+/// deliberately repeats bytes that belong to the previous chunk's span, so
+/// it can't be represented as a single borrowed slice of `text`. Use
+/// [`chunk_text`]/[`chunk_text_spans`] when `options.overlap` is non-zero.
+pub fn chunk_iter<'a>(text: &'a str, options: &ChunkerOptions) -> ChunkIter<'a> {
+    match &options.strategy {
+        ChunkingStrategy::Size(max_size) => ChunkIter::Size {
+            remaining: if options.preserve_whitespace {
+                text
+            } else {
+                text.trim()
+            },
+            max_chars: (*max_size).max(1),
+        },
+        _ => ChunkIter::Fallback {
+            text,
+            chunks: chunk_text_raw(text, options).into_iter(),
+            byte_cursor: 0,
+        },
     }
 }
 
+/// Iterator returned by [`chunk_iter`].
+pub enum ChunkIter<'a> {
+    Size {
+        remaining: &'a str,
+        max_chars: usize,
+    },
+    Fallback {
+        text: &'a str,
+        chunks: std::vec::IntoIter<String>,
+        byte_cursor: usize,
+    },
+}
+
+impl<'a> Iterator for ChunkIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        match self {
+            ChunkIter::Size { remaining, max_chars } => {
+                if remaining.is_empty() {
+                    return None;
+                }
+                let mut boundary = remaining.len();
+                let mut count = 0usize;
+                for (idx, _) in remaining.char_indices() {
+                    if count == *max_chars {
+                        boundary = idx;
+                        break;
+                    }
+                    count += 1;
+                }
+                let (chunk, rest) = remaining.split_at(boundary);
+                *remaining = rest;
+                Some(chunk)
+            }
+            ChunkIter::Fallback {
+                text,
+                chunks,
+                byte_cursor,
+            } => {
+                let chunk = chunks.next()?;
+                let start =
+                    *byte_cursor + text[*byte_cursor..].find(chunk.as_str()).unwrap_or(0);
+                let end = start + chunk.len();
+                *byte_cursor = end;
+                Some(&text[start..end])
+            }
+        }
+    }
+}
+
+/// Prepends the trailing `overlap` units of each chunk onto the front of
+/// the next one, so adjacent chunks share context instead of splitting
+/// cleanly at the boundary. The unit searched for - characters, words,
+/// sentences, or lines - is chosen from `strategy` so an overlap never
+/// splits a word or sentence in half; strategies with no natural sub-unit
+/// ([`ChunkingStrategy::ContentDefined`]) fall back to characters. A no-op
+/// when `overlap` is `0` or there's only one chunk to begin with.
+fn apply_overlap(chunks: Vec<String>, overlap: usize, strategy: &ChunkingStrategy) -> Vec<String> {
+    if overlap == 0 || chunks.len() < 2 {
+        return chunks;
+    }
+
+    let (trailing, separator): (fn(&str, usize) -> String, &str) = match strategy {
+        ChunkingStrategy::Character(_) => (trailing_words, " "),
+        ChunkingStrategy::Semantic => (trailing_sentences, " "),
+        ChunkingStrategy::Syntactic { .. } => (trailing_lines, "\n"),
+        _ => (trailing_chars, ""),
+    };
+
+    let mut result = Vec::with_capacity(chunks.len());
+    let mut previous: Option<String> = None;
+
+    for chunk in chunks {
+        let windowed = match &previous {
+            Some(prev) => {
+                let prefix = trailing(prev, overlap);
+                if prefix.is_empty() {
+                    chunk.clone()
+                } else {
+                    format!("{prefix}{separator}{chunk}")
+                }
+            }
+            None => chunk.clone(),
+        };
+        previous = Some(chunk);
+        result.push(windowed);
+    }
+
+    result
+}
+
+/// Returns the trailing `n` characters of `s`.
+fn trailing_chars(s: &str, n: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let start = chars.len().saturating_sub(n);
+    chars[start..].iter().collect()
+}
+
+/// Returns the trailing `n` whitespace-separated words of `s`.
+fn trailing_words(s: &str, n: usize) -> String {
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let start = words.len().saturating_sub(n);
+    words[start..].join(" ")
+}
+
+/// Returns the trailing `n` sentences of `s`, split the same way
+/// [`semantic_chunking`] splits on `.`/`!`/`?`.
+fn trailing_sentences(s: &str, n: usize) -> String {
+    let sentences: Vec<&str> = s
+        .split_inclusive(['.', '!', '?'])
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .collect();
+    let start = sentences.len().saturating_sub(n);
+    sentences[start..].join(" ")
+}
+
+/// Returns the trailing `n` lines of `s`.
+fn trailing_lines(s: &str, n: usize) -> String {
+    let lines: Vec<&str> = s.lines().collect();
+    let start = lines.len().saturating_sub(n);
+    lines[start..].join("\n")
+}
+
 /// Splits text at exact character positions, regardless of word boundaries.
 fn size_based_chunking(text: &str, max_size: usize, preserve_whitespace: bool) -> Vec<String> {
     let text = if !preserve_whitespace {
@@ -114,6 +420,14 @@ fn size_based_chunking(text: &str, max_size: usize, preserve_whitespace: bool) -
 }
 
 /// Splits text at word boundaries to preserve semantic units.
+///
+/// Chunks are sliced verbatim out of `text` (by word span, not rebuilt by
+/// joining words with a single space) so that runs of whitespace wider
+/// than one space - double spaces, tabs, newlines - between words survive
+/// unchanged. This matters beyond cosmetics: [`chunk_text_spans`] locates
+/// each chunk in the source by substring search, which silently misses
+/// (and falls back to a wrong offset) if a chunk's text doesn't appear
+/// verbatim in `text`.
 fn character_based_chunking(
     text: &str,
     target_size: usize,
@@ -130,22 +444,37 @@ fn character_based_chunking(
         return vec![text];
     }
 
+    // Byte spans of whitespace-delimited words, so a chunk can later be
+    // sliced as `text[first_word.0..last_word.1]` and keep whatever
+    // whitespace originally separated its words.
+    let mut word_spans: Vec<(usize, usize)> = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                word_spans.push((start, i));
+            }
+        } else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+    if let Some(start) = word_start {
+        word_spans.push((start, text.len()));
+    }
+
     let mut result = Vec::new();
-    let mut current_chunk = String::new();
+    let mut chunk_span: Option<(usize, usize)> = None;
     let mut current_size = 0;
 
-    // Split text into words
-    let words: Vec<&str> = text.split_whitespace().collect();
-
-    for word in words {
+    for (word_start, word_end) in word_spans {
+        let word = &text[word_start..word_end];
         let word_len = word.chars().count();
 
         // If this word by itself is longer than target size, use size-based chunking for it
         if word_len > target_size {
             // Push the current chunk if it's not empty
-            if !current_chunk.is_empty() {
-                result.push(current_chunk.trim().to_string());
-                current_chunk = String::new();
+            if let Some((start, end)) = chunk_span.take() {
+                result.push(text[start..end].to_string());
                 current_size = 0;
             }
 
@@ -156,24 +485,28 @@ fn character_based_chunking(
         }
 
         // If adding this word would exceed the target size, start a new chunk
-        if current_size + word_len + 1 > target_size && !current_chunk.is_empty() {
-            result.push(current_chunk.trim().to_string());
-            current_chunk = String::new();
+        if current_size + word_len + 1 > target_size && chunk_span.is_some() {
+            let (start, end) = chunk_span.take().unwrap();
+            result.push(text[start..end].to_string());
             current_size = 0;
         }
 
         // Add the word to the current chunk
-        if !current_chunk.is_empty() {
-            current_chunk.push(' ');
-            current_size += 1;
+        match &mut chunk_span {
+            Some((_, end)) => {
+                current_size += 1 + word_len;
+                *end = word_end;
+            }
+            None => {
+                chunk_span = Some((word_start, word_end));
+                current_size = word_len;
+            }
         }
-        current_chunk.push_str(word);
-        current_size += word_len;
     }
 
     // Add the last chunk if it's not empty
-    if !current_chunk.is_empty() {
-        result.push(current_chunk.trim().to_string());
+    if let Some((start, end)) = chunk_span {
+        result.push(text[start..end].to_string());
     }
 
     result
@@ -229,6 +562,291 @@ fn semantic_chunking(text: &str, preserve_whitespace: bool) -> Vec<String> {
     result
 }
 
+/// Fixed table of 256 pseudo-random `u64` values used by the FastCDC
+/// rolling hash in [`content_defined_chunking`] - one entry per possible
+/// byte value, indexed directly by `byte as usize`. The values themselves
+/// don't need any particular distribution property beyond "well mixed";
+/// they're generated once and frozen so chunk boundaries are reproducible
+/// across runs.
+const GEAR: [u64; 256] = [
+    0x949B58FF7CDC9EF5, 0xD2053DDDCF601788, 0xD2528F5BA6395359, 0x98AE089045BD2951,
+    0xBCF1C2E726280E0C, 0x6E189AE749800A25, 0xF26FDAE70E2B88F0, 0x6B6DE4CC8F6CAF72,
+    0xDDA9A25AA23C74D2, 0x1707A5F827F4A24B, 0x770E610EFA6DC78D, 0x7E7427FCC72CC977,
+    0xD3AF3C1726943606, 0x1EF9A39ADCC1FFE3, 0xE231CC001AA02D2F, 0xBCE2CA1871D4D73C,
+    0x28EE6369DA912E17, 0x8548769A5A408ED0, 0x20163BEE1AF58837, 0x068968070BE02D79,
+    0x9A8ABB3A14F4720D, 0x2761BAFEC610B901, 0xA64878542DF3F73E, 0xD27BA518BB6C7ABA,
+    0xC6F321589F02AA39, 0x199276CB5EFE1414, 0x2533960CD2AE43E4, 0x20D9B1EDA82D7269,
+    0x30E03337F6BBED6E, 0xD913B457A72F683E, 0xD4DEAC534EFF5ECA, 0x0D09E3BD1B49D7BC,
+    0x5F65CB7DE5C4D9EC, 0x452CA6CD771B59C9, 0xDE552EAB71D777A9, 0x6CB0FF7818693B67,
+    0xD5512DDB71E8816D, 0x7C525AAE0D041737, 0xE1851AD25155C9D8, 0xF8FF01AD89C2B8CE,
+    0x47D94CA19A62DC89, 0x25A8CFADC75A8359, 0xAFD3125528B355BD, 0x4183A18C4617A755,
+    0x1BB9EED138436AD3, 0x40E2652270FAAEC2, 0xDFED4F9A4DA55A46, 0x82FB7A6CCD65F0E1,
+    0x0687519F0454FE90, 0x1719695E74DEF134, 0x8879F93F7507EBCC, 0x44879F2D79F788D2,
+    0xC29F0EE81B2343F5, 0xFBDF347670F9FA52, 0x30AF304C28A621F5, 0x021C99435295155A,
+    0xA3D735BF072F1495, 0xA935CAC820507085, 0xAF10A13EDB78A9D5, 0x41D14801F07968A9,
+    0x96308FDDFD1E0941, 0x70D91234BD5436B0, 0xECE9280AD5E9BC3E, 0x04B2DF65C317ED99,
+    0xE2EFD5DC16FBDF9F, 0x46B8B967029A844C, 0x7EA8E4FBBF3BDBD1, 0xA5486289A0D224E1,
+    0xF54132C4E4A5000D, 0xF8830F533970AEEB, 0x1E006D415FEDA208, 0xB2A291305896DCAA,
+    0x9DE982BA1DF40617, 0x2905ED8FEF502337, 0x4E5457B0A9B0FD4B, 0x3766312A573111B8,
+    0x060E3B3CF1F02223, 0x21FD87E722A3656E, 0x0601BCFCEB59E3D4, 0xEB9FAD32213B416D,
+    0x4DFC29031B8CCA4C, 0xA9DAF201D6E35034, 0x7086B9852E06FEF2, 0xA70AA052B6E4D15C,
+    0xD987002F47E0B1D6, 0x70723D9406414AB1, 0xB25A015AF58BBB24, 0x635229D7549F4438,
+    0x438D82612E740427, 0xB2B887D0DDAC5DEB, 0xE8E5E969706DF7E8, 0x211316CDC3DF8DD4,
+    0xCE0A7283618816CE, 0x9CE79246E1D43044, 0x7DAF630284D9CDFF, 0xF0621F5525791914,
+    0x09C0DF0EF74CA61B, 0xF063F0E865842882, 0xBACB5C869CB1904B, 0x620CB9D869C15755,
+    0xC93947A6A8DD574A, 0x67B9F7EC2D1A4C58, 0xFF28A2BC000A4481, 0xF2BEFDFD91F027C6,
+    0x4BB35C57A312F506, 0xCEF187F03C8E806F, 0xDD9B986D18B609E2, 0xFE6DF6007149A45C,
+    0xA386AB7F93A727D8, 0x16E6C4A8A4C64702, 0x3D4AB1B26B5DD4CB, 0xACD2AD92CC20C8E3,
+    0x5200FE8B95961052, 0x97EFDF2FE75B314A, 0x18134B78F8163B23, 0x71F4062FAA398ED7,
+    0x26317C0A2A5AE54E, 0x5A0AEE6820F5C5B8, 0x05F30673B7E0C55E, 0xE1BE7FC08F20B027,
+    0x9DDF8FCFA56D1777, 0xD0E6FAD633581965, 0xF83D555DBE751040, 0xE583F71CED9B0A99,
+    0x5DE09A27E596150A, 0x00E4C54CD3E93956, 0x96EEA785A29594C7, 0x5BC035EAEBE30049,
+    0x6F73AD8660AC7CB1, 0x8753C49C6030349F, 0x77283C6BA4D1DBBC, 0xF2B1672DA87E2EA1,
+    0xBF604CF865857F11, 0x12B2E34F47BB9E30, 0x076E08C4F741E098, 0xDEF5D75C19A8D369,
+    0x6C16B339350D8E47, 0x1E449A2F63D0B37C, 0xD34EB99E49D1C333, 0x6EAB0EBC54BE6B9E,
+    0xE19E709685D5D762, 0x7DE8774403469E53, 0x22B22A4DE8808A07, 0xDB9310553C0DFAAF,
+    0x4C9BD992BDEB11F7, 0x92FEF6C826797094, 0x7672D1BCF27345C1, 0x49346899CBE2B559,
+    0xBBF3605329E99A4C, 0xB87515391919B7FE, 0x7514280012C906DF, 0x0C14920379122DC7,
+    0xECB51F199AC7EE9E, 0xBA2F36D0C5B078CC, 0x5463805B32FE99E5, 0xBB3CF5F2314AD406,
+    0x9261B5ADB2FCAC01, 0x75DA8721994662BF, 0x307CFD401DE49F7B, 0xC162A5F308346D0B,
+    0xE042EBB78D97483A, 0xD9096D3F1EB06D2D, 0x803ACA74F1387726, 0x9724FF8037931AA0,
+    0x4607AA52271C3399, 0xC2DC458A962E6F8C, 0x7B2ED3100FC5B290, 0xA6D776088FA3F1B7,
+    0x916F638DCF97C82F, 0x5BC7E354680436C6, 0x9FA43B37ADF58CC2, 0x319CFA5B31341592,
+    0x8ECA1F39765C9D30, 0xC5C8C0262037C568, 0x8E04F0E61A056D67, 0x3F9C2497487BC0CF,
+    0xB1EEF0A6A6990E5C, 0x7E2A6FEEBA90C8B1, 0xC00D5D80B3BFFF3E, 0x733D3286508E84AB,
+    0x3A8C4BEA848FE937, 0xE89603E08A77AAD6, 0xBBB519A575B07C69, 0xED38D89A4A31D938,
+    0x2799064A39BF8F0D, 0x398C1CFC2278C9A3, 0x03DE8B065D200BAD, 0x2B773E01A3FC8CD8,
+    0xE7C66415B10F7921, 0x649D407297912448, 0x1165BB621CFF09B5, 0x732B40976E94239C,
+    0x8C184C456376BF6B, 0x148CA80F7A3DB837, 0x7B0B87CFF257090E, 0xE294D2F28D02EABA,
+    0xD2E5896557B21D2F, 0xD3C2A27A207784DF, 0xB72DE2DC4589F05F, 0x611E87D7F8987609,
+    0x16B4BC841FAAA951, 0x5E5FF0D7C02ACD49, 0x359D12BE8830CFF3, 0x2F30631E424269E0,
+    0x1AC289933E589093, 0x53807E4118024995, 0x0D17F6A7C24AE902, 0x4D8A2E5592A8344C,
+    0xB5189CBB0CF9B514, 0x01B3FBB16B88F055, 0x9A280E8F0BF2AA7D, 0xD475E19F53088F57,
+    0x9AA1ECF772D7880C, 0x7C7E4A709480E8A4, 0x3F5868174BAA0FDB, 0xA7A1344959479DD6,
+    0x2661D8FB655F5570, 0x85BA2CE193514B78, 0xB2116FD0427DD052, 0xCB0BFC49FCCD6C26,
+    0x256BAAC6933341AF, 0xDF48A08E72711A3C, 0x8B7038F56589A6C3, 0x665D2A38BA12EC3F,
+    0x2EE03FCC4EB9B2D8, 0x6126D4758D537A74, 0x3AF9262F5564F84F, 0x44A635F14EFC76F4,
+    0x685ED12B72ACFDA4, 0xA2E8AC4A1DB704DD, 0x290B478C9974C135, 0x08FFC1ACF4B25B0F,
+    0xEA962C141F2CCA45, 0xFABE4EAFCCF60533, 0x052AC9C8D087E37E, 0x5F77A9D3D80D5A5F,
+    0x8765E199ADBEC773, 0xB178AC71B7A84C3E, 0x804463D239EC1B15, 0xB7842FB2033BDD49,
+    0xC30151D3315CCA87, 0x27FE5BA7E3290E13, 0x3E2639EC91F766DC, 0xD23F514138201CE3,
+    0xEEA3FE772E69782A, 0x61102E0290B7AA24, 0x54957C166A07AAA8, 0x89545E90FCCD1825,
+    0xE77DAFDC811C0EB0, 0x5989EB266EF244D7, 0xE0CF47CEF7A6933B, 0x1F51C9668D4ACC48,
+    0x2367F5957F898F06, 0x04B82EB5DA577A89, 0xE08D2851D0489DDC, 0x5B176FAD149B7D4E,
+];
+
+/// Returns a `u64` with the low `bits` bits set and the rest zero (`0`
+/// bits yields `0`), used to build FastCDC's `mask_s`/`mask_l`.
+fn low_bits_mask(bits: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        (1u64 << bits.min(63)) - 1
+    }
+}
+
+/// Splits `text`'s raw bytes into content-defined chunks using the
+/// FastCDC rolling-hash algorithm, so a small edit only reshuffles the
+/// chunk(s) around it instead of every chunk that follows.
+///
+/// A rolling fingerprint `fp` is updated one byte at a time as
+/// `fp = (fp << 1).wrapping_add(GEAR[byte])`; a cut point is declared the
+/// first time `fp & mask == 0`. Normalized chunking tightens the size
+/// distribution around `avg` by switching masks partway through: `mask_s`
+/// (more set bits, so harder for the AND to come out zero) is used while
+/// the chunk is still under `avg` bytes, and `mask_l` (fewer set bits,
+/// easier to satisfy) once it's past `avg`, biasing cuts to cluster near
+/// `avg` rather than drifting to `max`. The first `min` bytes of each
+/// chunk are never tested, and a cut is forced at `max` regardless of the
+/// hash. Cut points are nudged back to the nearest UTF-8 character
+/// boundary before slicing, since `text` is valid UTF-8 but a byte-level
+/// cut can otherwise land inside a multi-byte sequence.
+fn content_defined_chunking(text: &str, min: usize, avg: usize, max: usize) -> Vec<String> {
+    let bytes = text.as_bytes();
+    if bytes.is_empty() {
+        return vec![String::new()];
+    }
+
+    let avg = avg.max(1);
+    let min = min.min(max.max(1));
+    let max = max.max(min + 1);
+    let bits = (usize::BITS - 1 - avg.leading_zeros().min(usize::BITS - 1)) as u32;
+    let mask_s = low_bits_mask(bits + 2);
+    let mask_l = low_bits_mask(bits.saturating_sub(2));
+
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+
+    while start < bytes.len() {
+        let remaining = bytes.len() - start;
+        if remaining <= min {
+            chunks.push(text[start..].to_string());
+            break;
+        }
+
+        let hard_max = (start + max).min(bytes.len());
+        let mut fp: u64 = 0;
+        let mut cut = hard_max;
+        let mut i = start + min;
+        while i < hard_max {
+            fp = (fp << 1).wrapping_add(GEAR[bytes[i] as usize]);
+            let mask = if i - start < avg { mask_s } else { mask_l };
+            if fp & mask == 0 {
+                cut = i + 1;
+                break;
+            }
+            i += 1;
+        }
+
+        let mut boundary = cut.min(bytes.len());
+        while boundary > start && !text.is_char_boundary(boundary) {
+            boundary -= 1;
+        }
+        if boundary <= start {
+            boundary = bytes.len();
+        }
+
+        chunks.push(text[start..boundary].to_string());
+        start = boundary;
+    }
+
+    chunks
+}
+
+/// Tree-sitter node kinds that count as an "outline item" - a unit whose
+/// body a chunk boundary should avoid splitting - across the handful of
+/// grammars [`language_for_grammar`] knows about.
+const OUTLINE_KINDS: &[&str] = &[
+    "function_item",
+    "struct_item",
+    "impl_item",
+    "trait_item",
+    "mod_item",
+    "function_definition",
+    "class_definition",
+    "function_declaration",
+    "class_declaration",
+    "method_definition",
+];
+
+/// Resolves a grammar name (`"rust"`, `"python"`, `"javascript"`) to its
+/// tree-sitter [`tree_sitter::Language`], so [`ChunkerOptions::grammar`] can
+/// stay a plain string instead of every caller depending on the grammar
+/// crates directly. Returns `None` for an unrecognized name.
+fn language_for_grammar(name: &str) -> Option<tree_sitter::Language> {
+    match name {
+        "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "python" => Some(tree_sitter_python::LANGUAGE.into()),
+        "javascript" => Some(tree_sitter_javascript::LANGUAGE.into()),
+        _ => None,
+    }
+}
+
+/// For each line of source, the nesting depth of the deepest
+/// [`OUTLINE_KINDS`] node that encloses it - `0` for a line at the top
+/// level, higher for a line nested inside functions/impls/classes within
+/// one another. Used by [`syntactic_chunking`] to prefer cut points that
+/// aren't buried inside a deeply nested block.
+fn outline_depths(root: Node, line_count: usize) -> Vec<usize> {
+    let mut depths = vec![0usize; line_count.max(1)];
+    visit_outline_depths(root, 0, &mut depths);
+    depths
+}
+
+fn visit_outline_depths(node: Node, depth: usize, depths: &mut [usize]) {
+    let inner_depth = if OUTLINE_KINDS.contains(&node.kind()) {
+        depth + 1
+    } else {
+        depth
+    };
+
+    if node.child_count() == 0 {
+        let start_row = node.start_position().row;
+        let end_row = node.end_position().row.min(depths.len().saturating_sub(1));
+        for row in start_row..=end_row {
+            depths[row] = depth;
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_outline_depths(child, inner_depth, depths);
+    }
+}
+
+/// Among the line boundaries strictly between `start` and `end` (line
+/// indices into `depths`), returns the latest one whose preceding line sits
+/// at the shallowest nesting depth in that range - i.e. the cut point
+/// nested inside as few enclosing outline items as possible, breaking ties
+/// in favor of a bigger chunk. Falls back to `end` (a hard line boundary)
+/// when the range is too small to have an interior candidate.
+fn best_cut_point(depths: &[usize], start: usize, end: usize) -> usize {
+    if end <= start + 1 {
+        return end;
+    }
+    let min_depth = depths[start..end].iter().copied().min().unwrap_or(0);
+    for candidate in (start + 1..end).rev() {
+        if depths[candidate - 1] == min_depth {
+            return candidate;
+        }
+    }
+    end
+}
+
+/// Splits source code into chunks of at most `max_size` bytes, placing cut
+/// points at line boundaries chosen to stay out of the middle of a
+/// function, struct, impl block or class: `text` is parsed with the
+/// tree-sitter grammar named by `grammar`, each line is assigned the
+/// nesting depth of its deepest enclosing outline item via
+/// [`outline_depths`], and lines are greedily accumulated into a chunk
+/// until the next line would push it over `max_size` - at which point
+/// [`best_cut_point`] picks the latest boundary seen so far at the
+/// shallowest nesting depth, falling back to the hard line boundary if
+/// nothing shallower is available. Falls back to
+/// [`character_based_chunking`] if `grammar` is missing, unrecognized, or
+/// fails to parse.
+fn syntactic_chunking(text: &str, max_size: usize, grammar: Option<&str>) -> Vec<String> {
+    let fallback = || character_based_chunking(text, max_size, true);
+
+    let Some(language) = grammar.and_then(language_for_grammar) else {
+        return fallback();
+    };
+
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return fallback();
+    }
+    let Some(tree) = parser.parse(text, None) else {
+        return fallback();
+    };
+
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.is_empty() {
+        return vec![text.to_string()];
+    }
+    let depths = outline_depths(tree.root_node(), lines.len());
+
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0usize;
+    let mut i = 0usize;
+    while i < lines.len() {
+        let size: usize = lines[chunk_start..=i].iter().map(|line| line.len() + 1).sum();
+        if size > max_size && i > chunk_start {
+            let cut = best_cut_point(&depths, chunk_start, i);
+            chunks.push(lines[chunk_start..cut].join("\n"));
+            chunk_start = cut;
+            continue;
+        }
+        i += 1;
+    }
+    if chunk_start < lines.len() {
+        chunks.push(lines[chunk_start..].join("\n"));
+    }
+
+    chunks
+}
+
 /// Creates a CLI for the chunking utility, similar to text_chunker.sh
 #[cfg(feature = "cli")]
 pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
@@ -290,6 +908,12 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
                 .help("Show debug information")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("overlap")
+                .long("overlap")
+                .value_name("UNITS")
+                .help("Overlap each chunk with the trailing N units of the previous one"),
+        )
         .arg(Arg::new("INPUT").help("Text to chunk").index(1))
         .get_matches();
 
@@ -311,11 +935,17 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // Set up options
+    let overlap = match matches.get_one::<String>("overlap") {
+        Some(value) => value.parse::<usize>()?,
+        None => 0,
+    };
     let options = ChunkerOptions {
         strategy,
         preserve_whitespace: matches.contains_id("preserve-whitespace"),
         delimiter: None,
         debug: matches.contains_id("debug"),
+        grammar: None,
+        overlap,
     };
 
     // Get input text
@@ -330,20 +960,31 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
         buffer
     };
 
-    // Process the text
-    let chunks = chunk_text(&input_text, options);
+    fn print_chunks<'a>(chunks: impl Iterator<Item = &'a str>, numbered: bool) {
+        if numbered {
+            for (i, chunk) in chunks.enumerate() {
+                println!("{}: {}", i + 1, chunk);
+            }
+        } else {
+            for chunk in chunks {
+                println!("{}", chunk);
+            }
+        }
+    }
 
-    // Output the chunks
+    // JSON output needs every chunk materialized anyway to serialize it,
+    // and overlap can't be represented as plain slices of the input (see
+    // `chunk_iter`'s doc comment), so both go through the owned
+    // `chunk_text`. Otherwise, stream chunks lazily via `chunk_iter` so a
+    // large input read from stdin doesn't sit fully materialized twice.
     if matches.contains_id("json") {
+        let chunks = chunk_text(&input_text, options);
         println!("{}", serde_json::to_string(&chunks)?);
-    } else if matches.contains_id("numbered") {
-        for (i, chunk) in chunks.iter().enumerate() {
-            println!("{}: {}", i + 1, chunk);
-        }
+    } else if options.overlap > 0 {
+        let chunks = chunk_text(&input_text, options);
+        print_chunks(chunks.iter().map(String::as_str), matches.contains_id("numbered"));
     } else {
-        for chunk in &chunks {
-            println!("{}", chunk);
-        }
+        print_chunks(chunk_iter(&input_text, &options), matches.contains_id("numbered"));
     }
 
     Ok(())
@@ -383,4 +1024,148 @@ mod tests {
         assert_eq!(chunks[2], "Is this sentence three");
         assert_eq!(chunks[3], "Yes it is.");
     }
+
+    #[test]
+    fn test_content_defined_chunking_reassembles_and_respects_max() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let chunks = content_defined_chunking(&text, 16, 64, 128);
+        assert_eq!(chunks.concat(), text);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 128);
+        }
+    }
+
+    #[test]
+    fn test_content_defined_chunking_is_shift_resistant() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let edited = format!("An extra sentence up front. {text}");
+
+        let chunks = content_defined_chunking(&text, 16, 64, 128);
+        let edited_chunks = content_defined_chunking(&edited, 16, 64, 128);
+
+        let shared = chunks
+            .iter()
+            .filter(|chunk| edited_chunks.contains(chunk))
+            .count();
+        assert!(shared >= chunks.len() / 2);
+    }
+
+    #[test]
+    fn test_syntactic_chunking_keeps_functions_whole() {
+        let text = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        let chunks = syntactic_chunking(text, 20, Some("rust"));
+        assert_eq!(chunks.join("\n"), text.trim_end());
+        for chunk in &chunks {
+            assert!(chunk.contains("fn a()") || chunk.contains("fn b()"));
+        }
+    }
+
+    #[test]
+    fn test_syntactic_chunking_falls_back_without_grammar() {
+        let text = "word ".repeat(20);
+        let chunks = syntactic_chunking(&text, 15, None);
+        assert_eq!(chunks, character_based_chunking(&text, 15, true));
+    }
+
+    #[test]
+    fn test_chunk_text_spans_tracks_byte_and_char_offsets() {
+        let text = "caf\u{e9} is a word. so is r\u{e9}sum\u{e9}.";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Semantic,
+            ..Default::default()
+        };
+
+        let chunks = chunk_text_spans(text, options);
+        assert!(chunks.len() >= 2);
+        for chunk in &chunks {
+            assert_eq!(&text[chunk.byte_range.clone()], chunk.text);
+            assert_eq!(
+                chunk.char_range.end - chunk.char_range.start,
+                chunk.text.chars().count()
+            );
+        }
+        // "café" contains a 2-byte character, so its byte range is longer
+        // than its char range.
+        let first = &chunks[0];
+        assert!(first.byte_range.end - first.byte_range.start > first.char_range.end - first.char_range.start);
+    }
+
+    #[test]
+    fn test_chunk_text_spans_handles_irregular_whitespace() {
+        // Double space between "alpha" and "beta": character_based_chunking
+        // used to rejoin words with a single space, so the chunk text
+        // wouldn't appear verbatim in `text` and span lookup fell back to 0.
+        let text = "alpha  beta gamma delta";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Character(11),
+            ..Default::default()
+        };
+
+        let chunks = chunk_text_spans(text, options);
+        assert!(chunks.len() >= 2);
+        for chunk in &chunks {
+            assert_eq!(&text[chunk.byte_range.clone()], chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_overlap_prepends_trailing_words_for_character_strategy() {
+        let text = "This is a test string that should be split at word boundaries.";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Character(15),
+            overlap: 2,
+            ..Default::default()
+        };
+
+        let chunks = chunk_text(text, options);
+        let plain = character_based_chunking(text, 15, false);
+        assert_eq!(chunks.len(), plain.len());
+        assert_eq!(chunks[0], plain[0]);
+        for i in 1..chunks.len() {
+            let expected_prefix = trailing_words(&plain[i - 1], 2);
+            assert!(chunks[i].starts_with(&expected_prefix));
+            assert!(chunks[i].ends_with(plain[i].as_str()));
+        }
+    }
+
+    #[test]
+    fn test_chunk_iter_size_matches_size_based_chunking() {
+        let text = "This is a test string that should be split into chunks of maximum size.";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Size(10),
+            ..Default::default()
+        };
+
+        let streamed: Vec<&str> = chunk_iter(text, &options).collect();
+        let materialized = size_based_chunking(text, 10, false);
+        assert_eq!(streamed, materialized);
+    }
+
+    #[test]
+    fn test_chunk_iter_semantic_falls_back_but_still_matches() {
+        let text = "This is sentence one. This is sentence two! Is this sentence three? Yes it is.";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Semantic,
+            ..Default::default()
+        };
+
+        let streamed: Vec<&str> = chunk_iter(text, &options).collect();
+        let materialized = semantic_chunking(text, false);
+        assert_eq!(streamed, materialized);
+    }
+
+    #[test]
+    fn test_overlap_zero_is_a_no_op() {
+        let text = "This is a test string that should be split at word boundaries.";
+        let options = ChunkerOptions {
+            strategy: ChunkingStrategy::Character(15),
+            overlap: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            chunk_text(text, options),
+            character_based_chunking(text, 15, false)
+        );
+    }
 }
@@ -2,10 +2,167 @@ use anyhow::{Context, Result};
 use chrono::Local;
 use clap::{Arg, ArgAction, Command};
 use colored::*;
+use harald::utils::validation::chunk_invariants::{validate_chunk_invariants, StoreChunkSpec};
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command as SystemCommand;
+use std::time::Duration;
+
+/// Indent width used by the native formatter, matching prettier's default.
+const NATIVE_INDENT_WIDTH: usize = 2;
+
+/// Above this many lines per side, skip the Myers diff and fall back to a
+/// trivial equal/unequal report so `--diff` can't blow up memory on huge
+/// files (the algorithm is O((N+M)^2) in the worst case).
+const MAX_DIFF_LINES: usize = 5_000;
+
+/// One line of a computed diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffLine {
+    Equal(String),
+    Delete(String),
+    Insert(String),
+}
+
+/// Computes the shortest edit script between `old` and `new` using the
+/// Myers O(ND) algorithm, returning a line-oriented diff.
+///
+/// Falls back to a single `Delete`-then-`Insert` pair (of the whole
+/// content) when either side exceeds [`MAX_DIFF_LINES`].
+fn myers_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    if a.len() > MAX_DIFF_LINES || b.len() > MAX_DIFF_LINES {
+        let mut fallback = Vec::new();
+        fallback.extend(a.iter().map(|l| DiffLine::Delete((*l).to_string())));
+        fallback.extend(b.iter().map(|l| DiffLine::Insert((*l).to_string())));
+        return fallback;
+    }
+
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = n + m;
+    let offset = max as usize;
+
+    // trace[d] holds the V array (furthest-reaching x per diagonal) at
+    // edit distance d, so we can backtrack from the end once found.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let mut v = vec![0isize; 2 * offset + 1];
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset as isize) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[idx] = x;
+
+            if x >= n && y >= m {
+                trace.push(v.clone());
+                break 'search;
+            }
+
+            k += 2;
+        }
+    }
+
+    backtrack_diff(&a, &b, &trace, offset)
+}
+
+/// Walks the recorded `V` snapshots backward from `(N, M)` to `(0, 0)`,
+/// emitting insert/delete/equal runs in forward order.
+fn backtrack_diff(a: &[&str], b: &[&str], trace: &[Vec<isize>], offset: usize) -> Vec<DiffLine> {
+    let mut x = a.len() as isize;
+    let mut y = b.len() as isize;
+    let mut lines = Vec::new();
+
+    for d in (0..trace.len().saturating_sub(1)).rev() {
+        let v = &trace[d];
+        let k = x - y;
+        let idx = (k + offset as isize) as usize;
+
+        let prev_k = if k == -(d as isize) || (k != d as isize && v[idx - 1] < v[idx + 1]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_idx = (prev_k + offset as isize) as usize;
+        let prev_x = v[prev_idx];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            lines.push(DiffLine::Equal(a[x as usize - 1].to_string()));
+            x -= 1;
+            y -= 1;
+        }
+
+        if x == prev_x {
+            lines.push(DiffLine::Insert(b[y as usize - 1].to_string()));
+            y -= 1;
+        } else {
+            lines.push(DiffLine::Delete(a[x as usize - 1].to_string()));
+            x -= 1;
+        }
+    }
+
+    while x > 0 && y > 0 {
+        lines.push(DiffLine::Equal(a[x as usize - 1].to_string()));
+        x -= 1;
+        y -= 1;
+    }
+
+    lines.reverse();
+    lines
+}
+
+/// Renders a computed diff with colored `+`/`-` gutters, prettier-CLI
+/// style.
+fn render_diff(lines: &[DiffLine]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        match line {
+            DiffLine::Equal(text) => out.push_str(&format!("  {text}\n")),
+            DiffLine::Delete(text) => out.push_str(&format!("{}\n", format!("- {text}").red())),
+            DiffLine::Insert(text) => out.push_str(&format!("{}\n", format!("+ {text}").green())),
+        }
+    }
+    out
+}
+
+/// Which implementation formats a file: our own `serde_json`-based
+/// printer, or the external `prettier` binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatterKind {
+    Native,
+    Prettier,
+}
+
+impl FormatterKind {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "native" => Ok(Self::Native),
+            "prettier" => Ok(Self::Prettier),
+            other => anyhow::bail!("Unknown formatter '{other}', expected native|prettier"),
+        }
+    }
+}
 
 /// Registry configuration
 #[derive(Debug, Clone)]
@@ -41,6 +198,15 @@ struct VectorRegistry {
     embedding_models: Vec<Value>,
     #[serde(rename = "lastUpdated")]
     last_updated: String,
+    /// Paths (relative to this file) of registry fragments to merge in.
+    /// Vector stores with matching `id` have their `sourceFiles` unioned;
+    /// scalar fields from the most-specific file win. Not written back by
+    /// [`save_registry`], which only persists the merged top-level view.
+    #[serde(rename = "includes", default, skip_serializing_if = "Vec::is_empty")]
+    includes: Vec<String>,
+    /// Store IDs to drop after merging includes, Mercurial-layering style.
+    #[serde(rename = "%unset", default, skip_serializing_if = "Vec::is_empty")]
+    unset: Vec<String>,
 }
 
 /// Processing mode
@@ -97,17 +263,13 @@ fn log_error(message: &str) {
     eprintln!("{} {}", "[ERROR]".red().bold(), message);
 }
 
-/// Check for required dependencies
-fn check_dependencies() -> Result<()> {
-    let mut missing = false;
-
-    // Check for jq
-    if SystemCommand::new("jq").arg("--version").output().is_err() {
-        log_error("jq is required but not installed. Please install with: brew install jq");
-        missing = true;
+/// Check for required dependencies. The native formatter has none; the
+/// `prettier` binary is only required when it's explicitly selected.
+fn check_dependencies(formatter: FormatterKind) -> Result<()> {
+    if formatter != FormatterKind::Prettier {
+        return Ok(());
     }
 
-    // Check for prettier
     if SystemCommand::new("prettier")
         .arg("--version")
         .output()
@@ -116,10 +278,6 @@ fn check_dependencies() -> Result<()> {
         log_error(
             "prettier is required but not installed. Please install with: npm install -g prettier",
         );
-        missing = true;
-    }
-
-    if missing {
         return Err(anyhow::anyhow!("Missing required dependencies"));
     }
 
@@ -128,20 +286,100 @@ fn check_dependencies() -> Result<()> {
 
 /// Load and validate the vector store registry
 fn load_registry(config: &RegistryConfig) -> Result<VectorRegistry> {
-    if !config.file_path.exists() {
-        return Err(anyhow::anyhow!(
-            "Registry file not found: {:?}",
-            config.file_path
-        ));
+    let mut visited = HashSet::new();
+    load_registry_file(&config.file_path, &mut visited)
+}
+
+/// Loads `path` and recursively resolves its `includes` fragments,
+/// merging vector stores (union `sourceFiles`, most-specific scalar
+/// fields win) and embedding models (merged by `id`), then applies this
+/// file's own `%unset` list to drop inherited stores.
+///
+/// Tracks canonicalized paths currently on the include stack in
+/// `visited` to detect cycles.
+fn load_registry_file(path: &Path, visited: &mut HashSet<PathBuf>) -> Result<VectorRegistry> {
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Registry file not found: {:?}", path));
     }
 
-    let content = fs::read_to_string(&config.file_path)
-        .with_context(|| format!("Failed to read registry file: {:?}", config.file_path))?;
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if !visited.insert(canonical.clone()) {
+        anyhow::bail!("Registry include cycle detected at {:?}", path);
+    }
 
-    let registry: VectorRegistry =
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read registry file: {:?}", path))?;
+    let raw: VectorRegistry =
         serde_json::from_str(&content).with_context(|| "Registry file is not valid JSON")?;
 
-    Ok(registry)
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut vector_stores = Vec::new();
+    let mut embedding_models = Vec::new();
+
+    for include in &raw.includes {
+        let fragment = load_registry_file(&base_dir.join(include), visited)
+            .with_context(|| format!("Failed to resolve include '{include}' from {path:?}"))?;
+        vector_stores = merge_vector_stores(vector_stores, fragment.vector_stores);
+        embedding_models = merge_embedding_models(embedding_models, fragment.embedding_models);
+    }
+
+    vector_stores = merge_vector_stores(vector_stores, raw.vector_stores);
+    embedding_models = merge_embedding_models(embedding_models, raw.embedding_models);
+    vector_stores.retain(|store| !raw.unset.contains(&store.id));
+
+    visited.remove(&canonical);
+
+    Ok(VectorRegistry {
+        vector_stores,
+        embedding_models,
+        last_updated: raw.last_updated,
+        includes: Vec::new(),
+        unset: Vec::new(),
+    })
+}
+
+/// Merges `overlay` stores onto `base`: matching `id`s have their
+/// `sourceFiles` unioned while the rest of the overlay's (more specific)
+/// fields replace the base's.
+fn merge_vector_stores(base: Vec<VectorStore>, overlay: Vec<VectorStore>) -> Vec<VectorStore> {
+    let mut merged = base;
+    for store in overlay {
+        if let Some(existing) = merged.iter_mut().find(|s| s.id == store.id) {
+            let mut source_files = existing.source_files.clone();
+            for file in &store.source_files {
+                if !source_files.contains(file) {
+                    source_files.push(file.clone());
+                }
+            }
+            *existing = VectorStore {
+                source_files,
+                ..store
+            };
+        } else {
+            merged.push(store);
+        }
+    }
+    merged
+}
+
+/// Merges `overlay` embedding model entries onto `base` by their `"id"`
+/// field, with the overlay's entry replacing the base's on a match.
+fn merge_embedding_models(base: Vec<Value>, overlay: Vec<Value>) -> Vec<Value> {
+    let mut merged = base;
+    for model in overlay {
+        let id = model.get("id").and_then(Value::as_str).map(str::to_string);
+        let existing = id.as_ref().and_then(|id| {
+            merged
+                .iter()
+                .position(|m| m.get("id").and_then(Value::as_str) == Some(id.as_str()))
+        });
+
+        match existing {
+            Some(index) => merged[index] = model,
+            None => merged.push(model),
+        }
+    }
+    merged
 }
 
 /// Save the registry back to file
@@ -191,6 +429,68 @@ fn validate_registry(config: &RegistryConfig, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Validates every store's `chunkingStrategy`/`maxChunkSize` against its
+/// actual registered source files (via
+/// [`harald::utils::validation::chunk_invariants`]), printing a
+/// per-store pass/fail summary. Returns an error if any violation is
+/// found so the registry can't silently drift out of sync.
+fn run_validate_chunks(registry: &VectorRegistry) -> Result<()> {
+    let known_embedding_models: Vec<String> = registry
+        .embedding_models
+        .iter()
+        .filter_map(|m| m.get("id"))
+        .filter_map(|id| id.as_str())
+        .map(|s| s.to_string())
+        .collect();
+
+    let specs: Vec<StoreChunkSpec> = registry
+        .vector_stores
+        .iter()
+        .map(|store| StoreChunkSpec {
+            id: store.id.clone(),
+            chunking_strategy: store.chunking_strategy.clone(),
+            max_chunk_size: store.max_chunk_size as usize,
+            embedding_model: store.embedding_model.clone(),
+            source_files: store.source_files.clone(),
+        })
+        .collect();
+
+    log_info(&format!(
+        "Validating chunk invariants for {} store(s)...",
+        specs.len()
+    ));
+    let violations = validate_chunk_invariants(&specs, &known_embedding_models);
+
+    let mut stats = ProcessingStats::default();
+    for store in &specs {
+        let store_violations: Vec<_> = violations
+            .iter()
+            .filter(|v| v.store_id() == store.id)
+            .collect();
+
+        if store_violations.is_empty() {
+            stats.add_success();
+        } else {
+            stats.add_failure();
+            for violation in store_violations {
+                log_error(&violation.to_string());
+            }
+        }
+    }
+
+    println!();
+    log_info("Chunk invariant validation complete:");
+    println!("- Total stores: {}", stats.total);
+    println!("- Passed: {}", stats.successful);
+    if stats.failed > 0 {
+        log_warning(&format!("- Failed: {}", stats.failed));
+        anyhow::bail!("{} chunk invariant violation(s) found", violations.len());
+    }
+    println!("- Failed: {}", stats.failed);
+
+    Ok(())
+}
+
 /// Check if a file is registered in the vector store registry
 fn is_file_registered(registry: &VectorRegistry, file_path: &str) -> bool {
     let current_dir = std::env::current_dir().unwrap_or_default();
@@ -229,8 +529,51 @@ fn get_store_for_file(registry: &VectorRegistry, file_path: &str) -> Option<Stri
         .map(|store| store.id.clone())
 }
 
-/// Format a JSON file using prettier
-fn format_json_file(file_path: &str, check_mode: bool, show_diff: bool) -> Result<bool> {
+/// Renders `value` in prettier's canonical JSON layout: 2-space indent,
+/// object keys sorted when `sort_keys` is set (otherwise left as parsed),
+/// a trailing newline, and `serde_json`'s already-stable number rendering.
+fn format_native(value: &Value, sort_keys: bool) -> Result<String> {
+    let value = if sort_keys { sort_object_keys(value) } else { value.clone() };
+
+    let indent = " ".repeat(NATIVE_INDENT_WIDTH);
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    value
+        .serialize(&mut serializer)
+        .context("Failed to serialize formatted JSON")?;
+
+    let mut formatted = String::from_utf8(buf).context("Formatted JSON was not valid UTF-8")?;
+    formatted.push('\n');
+    Ok(formatted)
+}
+
+/// Recursively sorts object keys alphabetically.
+fn sort_object_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: serde_json::Map<String, Value> = map
+                .iter()
+                .map(|(k, v)| (k.clone(), sort_object_keys(v)))
+                .collect::<std::collections::BTreeMap<_, _>>()
+                .into_iter()
+                .collect();
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sort_object_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Format a JSON file with either the native `serde_json`-based formatter
+/// or the external `prettier` binary.
+fn format_json_file(
+    file_path: &str,
+    check_mode: bool,
+    show_diff: bool,
+    formatter: FormatterKind,
+    sort_keys: bool,
+) -> Result<bool> {
     if !Path::new(file_path).exists() {
         return Err(anyhow::anyhow!("File not found: {}", file_path));
     }
@@ -239,10 +582,33 @@ fn format_json_file(file_path: &str, check_mode: bool, show_diff: bool) -> Resul
     let content = fs::read_to_string(file_path)
         .with_context(|| format!("Failed to read file: {}", file_path))?;
 
-    let _: Value = serde_json::from_str(&content)
+    let value: Value = serde_json::from_str(&content)
         .with_context(|| format!("File is not valid JSON: {}", file_path))?;
 
-    if check_mode {
+    if formatter == FormatterKind::Native {
+        let formatted = format_native(&value, sort_keys)?;
+
+        if check_mode {
+            if content == formatted {
+                log_success(&format!("File already properly formatted: {}", file_path));
+                Ok(true)
+            } else {
+                log_warning(&format!("File needs formatting: {}", file_path));
+                if show_diff {
+                    println!();
+                    println!("Diff for {}:", file_path);
+                    print!("{}", render_diff(&myers_diff(&content, &formatted)));
+                    println!();
+                }
+                Ok(false)
+            }
+        } else {
+            fs::write(file_path, &formatted)
+                .with_context(|| format!("Failed to write formatted file: {}", file_path))?;
+            log_success(&format!("Formatted file: {}", file_path));
+            Ok(true)
+        }
+    } else if check_mode {
         // Create formatted version in temp file
         let temp_file = tempfile::NamedTempFile::new()?;
         let temp_path = temp_file.path().to_string_lossy().to_string();
@@ -273,8 +639,7 @@ fn format_json_file(file_path: &str, check_mode: bool, show_diff: bool) -> Resul
             if show_diff {
                 println!();
                 println!("Diff for {}:", file_path);
-                // Simple diff - in a real implementation you might use a proper diff library
-                println!("(Diff output would show here)");
+                print!("{}", render_diff(&myers_diff(&original_content, &formatted_content)));
                 println!();
             }
             Ok(false)
@@ -434,14 +799,96 @@ fn register_new_file(config: &RegistryConfig, file_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// A small seeded linear-congruential generator, used only to make
+/// `--shuffle` reproducible across runs given the same seed - not
+/// suitable for anything security-sensitive.
+struct Lcg(u64);
+
+impl Lcg {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // Constants from Knuth's MMIX generator.
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0
+    }
+
+    /// Fisher-Yates shuffle in place.
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Formats `files` using up to `jobs` worker threads pulling from a
+/// shared queue, optionally shuffling the order first with a seeded RNG
+/// for reproducible, ordering-independent test runs. Each file's begin
+/// log and result are printed under a single lock so concurrent workers
+/// can't interleave mid-message.
+fn process_files(
+    mut files: Vec<String>,
+    check_mode: bool,
+    show_diff: bool,
+    formatter: FormatterKind,
+    sort_keys: bool,
+    jobs: usize,
+    shuffle_seed: Option<u64>,
+) -> ProcessingStats {
+    if let Some(seed) = shuffle_seed {
+        log_info(&format!("Shuffling file order with seed {seed}"));
+        Lcg::new(seed).shuffle(&mut files);
+    }
+
+    let jobs = jobs.max(1);
+    let queue = std::sync::Mutex::new(std::collections::VecDeque::from(files));
+    let stats = std::sync::Mutex::new(ProcessingStats::default());
+    let print_lock = std::sync::Mutex::new(());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let file = match queue.lock().unwrap().pop_front() {
+                    Some(file) => file,
+                    None => break,
+                };
+
+                let _guard = print_lock.lock().unwrap();
+                println!();
+                log_info(&format!("Processing: {}", file));
+
+                match format_json_file(&file, check_mode, show_diff, formatter, sort_keys) {
+                    Ok(true) => stats.lock().unwrap().add_success(),
+                    Ok(false) => stats.lock().unwrap().add_failure(),
+                    Err(e) => {
+                        log_error(&format!("Failed to process {}: {}", file, e));
+                        stats.lock().unwrap().add_failure();
+                    }
+                }
+            });
+        }
+    });
+
+    stats.into_inner().unwrap()
+}
+
 /// Process all registered files
+#[allow(clippy::too_many_arguments)]
 fn process_all_files(
     registry: &VectorRegistry,
     check_mode: bool,
     show_diff: bool,
+    formatter: FormatterKind,
+    sort_keys: bool,
+    jobs: usize,
+    shuffle_seed: Option<u64>,
 ) -> Result<ProcessingStats> {
-    let mut stats = ProcessingStats::default();
-
     // Get all registered files
     let mut all_files = Vec::new();
     for store in &registry.vector_stores {
@@ -455,19 +902,15 @@ fn process_all_files(
         all_files.len()
     ));
 
-    for file in &all_files {
-        println!();
-        log_info(&format!("Processing: {}", file));
-
-        match format_json_file(file, check_mode, show_diff) {
-            Ok(true) => stats.add_success(),
-            Ok(false) => stats.add_failure(),
-            Err(e) => {
-                log_error(&format!("Failed to process {}: {}", file, e));
-                stats.add_failure();
-            }
-        }
-    }
+    let stats = process_files(
+        all_files,
+        check_mode,
+        show_diff,
+        formatter,
+        sort_keys,
+        jobs,
+        shuffle_seed,
+    );
 
     println!();
     log_info("Processing complete:");
@@ -483,14 +926,17 @@ fn process_all_files(
 }
 
 /// Process files for a specific store
+#[allow(clippy::too_many_arguments)]
 fn process_store_files(
     registry: &VectorRegistry,
     store_id: &str,
     check_mode: bool,
     show_diff: bool,
+    formatter: FormatterKind,
+    sort_keys: bool,
+    jobs: usize,
+    shuffle_seed: Option<u64>,
 ) -> Result<ProcessingStats> {
-    let mut stats = ProcessingStats::default();
-
     // Find the store
     let store = registry
         .vector_stores
@@ -510,19 +956,15 @@ fn process_store_files(
         store_id
     ));
 
-    for file in &store.source_files {
-        println!();
-        log_info(&format!("Processing: {}", file));
-
-        match format_json_file(file, check_mode, show_diff) {
-            Ok(true) => stats.add_success(),
-            Ok(false) => stats.add_failure(),
-            Err(e) => {
-                log_error(&format!("Failed to process {}: {}", file, e));
-                stats.add_failure();
-            }
-        }
-    }
+    let stats = process_files(
+        store.source_files.clone(),
+        check_mode,
+        show_diff,
+        formatter,
+        sort_keys,
+        jobs,
+        shuffle_seed,
+    );
 
     println!();
     log_info(&format!("Processing complete for store {}:", store_id));
@@ -537,6 +979,113 @@ fn process_store_files(
     Ok(stats)
 }
 
+/// Debounce window for coalescing bursts of filesystem events (e.g. an
+/// editor's save-via-rename producing several events for one logical
+/// write) into a single reformat pass.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Runs a long-lived watch loop: reformats registered files as they
+/// change on disk, and reloads the registry (picking up newly registered
+/// files) when the registry file itself changes.
+///
+/// Never returns under normal operation; only exits on a watcher error.
+fn run_watch(config: &RegistryConfig, formatter: FormatterKind, sort_keys: bool) -> Result<()> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    let registry_path =
+        fs::canonicalize(&config.file_path).unwrap_or_else(|_| config.file_path.clone());
+    let mut registry = load_registry(config)?;
+    let mut watched = watch_registry_files(&mut watcher, config, &registry)?;
+
+    log_info(&format!(
+        "Watching {} registered file(s) and {:?} for changes. Press Ctrl+C to stop.",
+        watched.len(),
+        config.file_path
+    ));
+
+    loop {
+        // Block for the first event, then drain anything else that
+        // arrives within the debounce window so a burst of saves only
+        // triggers one reformat per file.
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        let mut changed: HashSet<PathBuf> = first.paths.into_iter().collect();
+        while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+            changed.extend(event.paths);
+        }
+
+        if changed.contains(&registry_path) {
+            log_info("Registry file changed, reloading");
+            registry = load_registry(config)?;
+            watched = watch_registry_files(&mut watcher, config, &registry)?;
+            continue;
+        }
+
+        for path in &changed {
+            let Some(path_str) = path.to_str() else {
+                continue;
+            };
+            if !watched.contains(path) {
+                continue;
+            }
+
+            println!();
+            log_info(&format!("Detected change: {}", path_str));
+            let mut stats = ProcessingStats::default();
+            match format_json_file(path_str, false, false, formatter, sort_keys) {
+                Ok(_) => stats.add_success(),
+                Err(e) => {
+                    log_error(&format!("Failed to reformat {}: {}", path_str, e));
+                    stats.add_failure();
+                }
+            }
+            println!(
+                "- Total: {}, successful: {}, failed: {}",
+                stats.total, stats.successful, stats.failed
+            );
+        }
+    }
+}
+
+/// (Re-)subscribes the watcher to every registered source file plus the
+/// registry file itself, returning the resolved absolute set so watch
+/// events (which arrive as absolute paths) can be matched back to them.
+fn watch_registry_files(
+    watcher: &mut notify::RecommendedWatcher,
+    config: &RegistryConfig,
+    registry: &VectorRegistry,
+) -> Result<HashSet<PathBuf>> {
+    let mut watched = HashSet::new();
+
+    for store in &registry.vector_stores {
+        for file in &store.source_files {
+            let path = fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file));
+            if watcher.watch(&path, RecursiveMode::NonRecursive).is_ok() {
+                watched.insert(path);
+            }
+        }
+    }
+
+    let registry_path =
+        fs::canonicalize(&config.file_path).unwrap_or_else(|_| config.file_path.clone());
+    if watcher
+        .watch(&registry_path, RecursiveMode::NonRecursive)
+        .is_ok()
+    {
+        watched.insert(registry_path);
+    }
+
+    Ok(watched)
+}
+
 /// Main entry point for the JSON formatting tool
 fn main() -> Result<()> {
     let matches = Command::new("format_json")
@@ -577,6 +1126,12 @@ fn main() -> Result<()> {
                 .help("Validate the registry file")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("validate-chunks")
+                .long("validate-chunks")
+                .help("Validate that stores' chunkingStrategy/maxChunkSize hold for their source files")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("diff")
                 .long("diff")
@@ -596,6 +1151,40 @@ fn main() -> Result<()> {
                 .help("Path to registry file")
                 .default_value("./config/vector-stores-registry.json"),
         )
+        .arg(
+            Arg::new("formatter")
+                .long("formatter")
+                .value_name("native|prettier")
+                .help("Which formatter implementation to use")
+                .default_value("native"),
+        )
+        .arg(
+            Arg::new("sort-keys")
+                .long("sort-keys")
+                .help("Sort object keys alphabetically (native formatter only)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .help("Watch registered files and the registry, reformatting on change")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .value_name("N")
+                .help("Number of files to format concurrently")
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("shuffle")
+                .long("shuffle")
+                .value_name("SEED")
+                .help("Randomize file processing order (seeded, reproducible)")
+                .num_args(0..=1)
+                .default_missing_value("auto"),
+        )
         .get_matches();
 
     // Configuration
@@ -607,6 +1196,28 @@ fn main() -> Result<()> {
     let verbose = matches.get_flag("verbose");
     let show_diff = matches.get_flag("diff");
     let check_mode = matches.get_flag("check");
+    let formatter = FormatterKind::parse(matches.get_one::<String>("formatter").unwrap())?;
+    let sort_keys = matches.get_flag("sort-keys");
+    let jobs: usize = matches
+        .get_one::<String>("jobs")
+        .unwrap()
+        .parse()
+        .context("Invalid --jobs value, expected a positive integer")?;
+    let shuffle_seed: Option<u64> = match matches.get_one::<String>("shuffle") {
+        None => None,
+        Some(seed) if seed == "auto" => {
+            let seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0);
+            log_info(&format!("Using shuffle seed {seed}"));
+            Some(seed)
+        }
+        Some(seed) => Some(
+            seed.parse()
+                .context("Invalid --shuffle seed, expected a number")?,
+        ),
+    };
 
     // Determine mode and target
     let mode = if matches.get_flag("validate-registry") {
@@ -630,7 +1241,16 @@ fn main() -> Result<()> {
     };
 
     // Check dependencies
-    check_dependencies()?;
+    check_dependencies(formatter)?;
+
+    if matches.get_flag("watch") {
+        return run_watch(&config, formatter, sort_keys);
+    }
+
+    if matches.get_flag("validate-chunks") {
+        let registry = load_registry(&config)?;
+        return run_validate_chunks(&registry);
+    }
 
     // Process based on mode
     match mode {
@@ -646,10 +1266,27 @@ fn main() -> Result<()> {
 
             match target {
                 ProcessingTarget::All => {
-                    process_all_files(&registry, check, show_diff)?;
+                    process_all_files(
+                        &registry,
+                        check,
+                        show_diff,
+                        formatter,
+                        sort_keys,
+                        jobs,
+                        shuffle_seed,
+                    )?;
                 }
                 ProcessingTarget::Store(store_id) => {
-                    process_store_files(&registry, &store_id, check, show_diff)?;
+                    process_store_files(
+                        &registry,
+                        &store_id,
+                        check,
+                        show_diff,
+                        formatter,
+                        sort_keys,
+                        jobs,
+                        shuffle_seed,
+                    )?;
                 }
                 ProcessingTarget::File(file_path) => {
                     // Check if file is registered
@@ -659,7 +1296,7 @@ fn main() -> Result<()> {
                         return Err(anyhow::anyhow!("File not registered: {}", file_path));
                     }
 
-                    match format_json_file(&file_path, check, show_diff) {
+                    match format_json_file(&file_path, check, show_diff, formatter, sort_keys) {
                         Ok(_) => {}
                         Err(e) => {
                             log_error(&format!("Failed to process file: {}", e));
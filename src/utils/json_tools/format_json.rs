@@ -1,57 +1,166 @@
-//! JSON formatting and validation CLI tool.
+//! JSON formatting and validation.
 //!
-//! This binary provides command-line JSON formatting and validation functionality.
+//! Exposed as [`format_file`] (single-document) and [`format_jsonl_file`]
+//! (line-delimited) so the unified `harald` CLI can invoke them under its
+//! `format` subcommand instead of living in their own binaries.
 
-use clap::Parser;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
-#[derive(Parser)]
-#[command(author, version, about = "Format and validate JSON files", long_about = None)]
-struct Args {
-    /// Input JSON file path
-    #[arg(value_name = "FILE")]
-    input: PathBuf,
+use crate::ingest::ingest_utils::validate_jsonl_lines;
 
-    /// Output file path (default: overwrite input)
-    #[arg(short, long)]
+/// Reads `input` as JSON, validates it, and - unless `validate_only` is
+/// set - writes it back out pretty-printed with `indent` spaces (or
+/// compact if `indent` is zero) to `output` (defaulting to overwriting
+/// `input`).
+///
+/// # Errors
+/// Returns an error if `input` cannot be read or is not valid JSON.
+pub fn format_file(
+    input: &Path,
     output: Option<PathBuf>,
-
-    /// Pretty print with indentation
-    #[arg(short, long, default_value_t = 2)]
     indent: usize,
-
-    /// Validate only, don't format
-    #[arg(long)]
     validate_only: bool,
-}
-
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-
-    // Read input file
-    let content = fs::read_to_string(&args.input)?;
-
-    // Parse JSON to validate
+) -> anyhow::Result<()> {
+    let content = fs::read_to_string(input)?;
     let value: Value = serde_json::from_str(&content)?;
 
-    if args.validate_only {
-        println!("✅ JSON is valid");
+    if validate_only {
+        info!(file = %input.display(), "JSON is valid");
         return Ok(());
     }
 
-    // Format JSON
-    let formatted = if args.indent > 0 {
+    let formatted = if indent > 0 {
         serde_json::to_string_pretty(&value)?
     } else {
         serde_json::to_string(&value)?
     };
 
-    // Write output
-    let output_path = args.output.unwrap_or(args.input);
+    let output_path = output.unwrap_or_else(|| input.to_path_buf());
     fs::write(&output_path, formatted)?;
 
-    println!("✅ Formatted JSON written to: {}", output_path.display());
+    info!(path = %output_path.display(), "formatted JSON written");
+    Ok(())
+}
+
+/// Reads `input` as JSONL (one JSON value per line) and validates each
+/// line independently, so a single malformed record doesn't fail the
+/// whole file the way [`format_file`]'s single-document parse would.
+///
+/// Unless `validate_only` is set, valid records are written back out to
+/// `output` (defaulting to overwriting `input`), one compact JSON value
+/// per line - unlike [`format_file`], pretty-printing isn't offered here,
+/// since spreading a record across multiple lines would break the
+/// one-record-per-line layout this format (and its readers, like
+/// [`validate_jsonl_lines`]) depend on. If `canonicalize` is set, each
+/// record's object keys are sorted recursively first, so two regenerated
+/// ingest files that differ only in field order diff as identical. If
+/// `skip_invalid` is set, unparseable lines are dropped from the output
+/// and appended instead to a `<input>.rejected.jsonl` sidecar; otherwise
+/// the first invalid line is a hard error, in either mode.
+///
+/// # Errors
+/// Returns an error if `input` cannot be read, or (without
+/// `skip_invalid`) if any line fails to parse as JSON.
+pub fn format_jsonl_file(
+    input: &Path,
+    output: Option<PathBuf>,
+    skip_invalid: bool,
+    canonicalize: bool,
+    validate_only: bool,
+) -> anyhow::Result<()> {
+    let content = fs::read_to_string(input)?;
+
+    if validate_only {
+        let (valid, invalid) = validate_jsonl_lines(&content);
+        info!(file = %input.display(), valid, invalid, "JSONL summary");
+        if invalid > 0 && !skip_invalid {
+            anyhow::bail!("{invalid} of {} lines are invalid JSON", valid + invalid);
+        }
+        return Ok(());
+    }
+
+    let mut kept = Vec::new();
+    let mut rejected = Vec::new();
+    for (offset, line) in content.lines().enumerate() {
+        let line_number = offset + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Value>(line) {
+            Ok(mut record) => {
+                if canonicalize {
+                    canonicalize_value(&mut record);
+                }
+                kept.push(record);
+            }
+            Err(e) => {
+                warn!(line = line_number, column = e.column(), error = %e, "invalid JSONL record");
+                if skip_invalid {
+                    rejected.push(line.to_string());
+                } else {
+                    anyhow::bail!(
+                        "Invalid JSON at line {line_number}, column {}: {e}",
+                        e.column()
+                    );
+                }
+            }
+        }
+    }
+
+    if !rejected.is_empty() {
+        let rejected_path = sidecar_path(input, "rejected.jsonl");
+        fs::write(&rejected_path, rejected.join("\n") + "\n")?;
+        info!(path = %rejected_path.display(), count = rejected.len(), "rejected JSONL lines written");
+    }
+
+    let formatted = kept
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n");
+
+    let output_path = output.unwrap_or_else(|| input.to_path_buf());
+    fs::write(&output_path, formatted + "\n")?;
+
+    info!(
+        path = %output_path.display(),
+        valid = kept.len(),
+        invalid = rejected.len(),
+        "formatted JSONL written"
+    );
     Ok(())
 }
+
+/// Builds `<input>.<suffix>` next to `input`, keeping its original
+/// extension intact (unlike [`Path::with_extension`], which would replace
+/// it).
+fn sidecar_path(input: &Path, suffix: &str) -> PathBuf {
+    let mut name = input.as_os_str().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Recursively sorts every object's keys so two structurally-identical
+/// values serialize identically regardless of original field order.
+fn canonicalize_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (_, v) in &mut entries {
+                canonicalize_value(v);
+            }
+            *map = entries.into_iter().collect::<Map<String, Value>>();
+        }
+        Value::Array(items) => {
+            for item in items {
+                canonicalize_value(item);
+            }
+        }
+        _ => {}
+    }
+}
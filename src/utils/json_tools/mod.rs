@@ -0,0 +1,8 @@
+//! JSON formatting and validation tools.
+//!
+//! This module groups the standalone JSON utilities - formatting,
+//! schema validation, and schema generation - that the `harald` CLI
+//! exposes under its `schema` and `format` subcommands.
+
+pub mod format_json;
+pub mod validate_json_schema;
@@ -1,68 +1,97 @@
-//! JSON schema validation CLI tool.
+//! JSON schema validation, backed by the `jsonschema` crate for full
+//! Draft 7 / 2020-12 validation.
 //!
-//! This binary provides command-line JSON schema validation functionality.
+//! Exposed as [`validate_against_schema`] and [`generate_schema_file`] so
+//! the unified `harald` CLI can invoke them under its `schema` subcommand
+//! instead of each living in its own binary.
 
-use clap::Parser;
+use anyhow::Context;
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
-
-#[derive(Parser)]
-#[command(author, version, about = "Validate JSON against schema", long_about = None)]
-struct Args {
-    /// JSON file to validate
-    #[arg(value_name = "JSON_FILE")]
-    json_file: PathBuf,
-
-    /// JSON schema file
-    #[arg(short, long, value_name = "SCHEMA_FILE")]
-    schema: PathBuf,
-
-    /// Generate schema from JSON file
-    #[arg(long)]
-    generate_schema: bool,
-
-    /// Output file for generated schema
-    #[arg(short, long)]
-    output: Option<PathBuf>,
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Generates a basic JSON Schema from `json_file` and writes it to
+/// `output` (defaulting to `<json_file>.schema.json`), returning the path
+/// written to.
+///
+/// # Errors
+/// Returns an error if `json_file` cannot be read/parsed or the schema
+/// cannot be written.
+pub fn generate_schema_file(json_file: &Path, output: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    let content = fs::read_to_string(json_file)
+        .with_context(|| format!("Failed to read JSON file: {}", json_file.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("File is not valid JSON: {}", json_file.display()))?;
+
+    let schema = generate_basic_schema(&value);
+    let schema_json = serde_json::to_string_pretty(&schema)?;
+
+    let output_path = output.unwrap_or_else(|| {
+        let mut path = json_file.to_path_buf();
+        path.set_extension("schema.json");
+        path
+    });
+
+    fs::write(&output_path, schema_json)
+        .with_context(|| format!("Failed to write schema file: {}", output_path.display()))?;
+    info!(path = %output_path.display(), "schema generated");
+
+    Ok(output_path)
 }
 
-#[cfg(feature = "cli")]
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-
-    if args.generate_schema {
-        // Read JSON file
-        let content = fs::read_to_string(&args.json_file)?;
-        let value: Value = serde_json::from_str(&content)?;
-
-        // Generate basic schema (simplified)
-        let schema = generate_basic_schema(&value);
-        let schema_json = serde_json::to_string_pretty(&schema)?;
-
-        // Write schema
-        let output_path = args.output.unwrap_or_else(|| {
-            let mut path = args.json_file.clone();
-            path.set_extension("schema.json");
-            path
-        });
-
-        fs::write(&output_path, schema_json)?;
-        println!("✅ Schema generated: {}", output_path.display());
-    } else {
-        // Read and validate JSON
-        let json_content = fs::read_to_string(&args.json_file)?;
-        let _json_value: Value = serde_json::from_str(&json_content)?;
-
-        let _schema_content = fs::read_to_string(&args.schema)?;
-        let _schema_value: Value = serde_json::from_str(&_schema_content)?;
-
-        // Basic validation (would need a proper JSON schema validator library)
-        println!("✅ JSON validation completed (basic check)");
-        println!("Note: Full JSON Schema validation requires additional dependencies");
+/// Validates a JSON instance file against a Draft 7 / 2020-12 JSON Schema
+/// file, logging each validation error with its JSON Pointer path, the
+/// failing keyword, and the offending value.
+///
+/// # Returns
+/// `Ok(true)` if the instance is valid, `Ok(false)` if it failed
+/// validation (errors are logged, not returned as an `Err`).
+///
+/// # Errors
+/// Returns an error if either file cannot be read/parsed or the schema
+/// fails to compile.
+pub fn validate_against_schema(json_file: &Path, schema_file: &Path) -> anyhow::Result<bool> {
+    let json_content = fs::read_to_string(json_file)
+        .with_context(|| format!("Failed to read JSON file: {}", json_file.display()))?;
+    let instance: Value = serde_json::from_str(&json_content)
+        .with_context(|| format!("File is not valid JSON: {}", json_file.display()))?;
+
+    let schema_content = fs::read_to_string(schema_file)
+        .with_context(|| format!("Failed to read schema file: {}", schema_file.display()))?;
+    let schema_value: Value = serde_json::from_str(&schema_content)
+        .with_context(|| format!("Schema is not valid JSON: {}", schema_file.display()))?;
+
+    let compiled = jsonschema::validator_for(&schema_value)
+        .with_context(|| format!("Failed to compile schema: {}", schema_file.display()))?;
+
+    let errors: Vec<_> = compiled.iter_errors(&instance).collect();
+
+    if errors.is_empty() {
+        info!(
+            json_file = %json_file.display(),
+            schema_file = %schema_file.display(),
+            "instance is valid against schema"
+        );
+        return Ok(true);
     }
 
-    Ok(())
+    warn!(
+        json_file = %json_file.display(),
+        schema_file = %schema_file.display(),
+        error_count = errors.len(),
+        "instance failed schema validation"
+    );
+    for error in &errors {
+        warn!(
+            path = %error.instance_path,
+            keyword = ?error.kind,
+            value = %error.instance,
+            "{error}"
+        );
+    }
+
+    Ok(false)
 }
 
 fn generate_basic_schema(value: &Value) -> Value {
@@ -72,23 +101,14 @@ fn generate_basic_schema(value: &Value) -> Value {
             for (key, val) in map {
                 properties.insert(key.clone(), generate_basic_schema(val));
             }
+            let required: Vec<&String> = map.keys().collect();
             serde_json::json!({
                 "type": "object",
-                "properties": properties
+                "properties": properties,
+                "required": required
             })
         }
-        Value::Array(arr) => {
-            if let Some(first) = arr.first() {
-                serde_json::json!({
-                    "type": "array",
-                    "items": generate_basic_schema(first)
-                })
-            } else {
-                serde_json::json!({
-                    "type": "array"
-                })
-            }
-        }
+        Value::Array(arr) => generate_array_schema(arr),
         Value::String(_) => serde_json::json!({"type": "string"}),
         Value::Number(n) => {
             if n.is_f64() {
@@ -101,3 +121,70 @@ fn generate_basic_schema(value: &Value) -> Value {
         Value::Null => serde_json::json!({"type": "null"}),
     }
 }
+
+/// Infers a schema for an array by merging the shape of every element
+/// instead of looking only at the first one.
+///
+/// When all elements are objects, the merged `items` schema's `properties`
+/// cover every field seen across the array, and `required` lists only the
+/// fields present on every element - fields seen on some but not all
+/// elements are treated as optional.
+fn generate_array_schema(arr: &[Value]) -> Value {
+    match arr.first() {
+        None => serde_json::json!({"type": "array"}),
+        Some(_) if arr.iter().all(|v| v.is_object()) => {
+            serde_json::json!({
+                "type": "array",
+                "items": merge_object_schemas(arr)
+            })
+        }
+        Some(first) => serde_json::json!({
+            "type": "array",
+            "items": generate_basic_schema(first)
+        }),
+    }
+}
+
+/// Merges the field shapes of a slice of JSON objects into a single object
+/// schema, marking as `required` only the fields present on every object.
+fn merge_object_schemas(objects: &[Value]) -> Value {
+    let total = objects.len();
+    let mut field_values: std::collections::BTreeMap<&str, Vec<&Value>> = Default::default();
+
+    for object in objects {
+        if let Some(map) = object.as_object() {
+            for (key, val) in map {
+                field_values.entry(key.as_str()).or_default().push(val);
+            }
+        }
+    }
+
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+
+    for (key, values) in &field_values {
+        let merged_schema = if values.iter().all(|v| v.is_object()) {
+            let owned: Vec<Value> = values.iter().map(|v| (*v).clone()).collect();
+            merge_object_schemas(&owned)
+        } else if values.iter().all(|v| v.is_array()) {
+            let flattened: Vec<Value> = values
+                .iter()
+                .flat_map(|v| v.as_array().cloned().unwrap_or_default())
+                .collect();
+            generate_array_schema(&flattened)
+        } else {
+            generate_basic_schema(values[0])
+        };
+
+        properties.insert(key.to_string(), merged_schema);
+        if values.len() == total {
+            required.push(key.to_string());
+        }
+    }
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required
+    })
+}
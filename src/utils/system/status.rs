@@ -1,14 +1,35 @@
 use anyhow::{Context, Result};
 use clap::{Arg, ArgAction, Command};
 use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::TcpStream;
 use std::process::{Command as ProcessCommand, Output};
 use std::time::{Duration, Instant};
 
+/// Output format for the status check, selected with `--format`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+enum OutputFormat {
+    /// Emoji-decorated human text (the original, default behavior).
+    #[default]
+    Text,
+    /// A single [`StatusReport`] serialized to stdout, for monitoring
+    /// integrations that want to consume this without screen-scraping.
+    Json,
+}
+
 /// Configuration for status check
 #[derive(Debug)]
 struct StatusConfig {
     verbose: bool,
     check_all: bool,
+    format: OutputFormat,
+}
+
+impl StatusConfig {
+    fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
 }
 
 fn log_success(message: &str) {
@@ -27,9 +48,20 @@ fn log_warning(message: &str) {
     println!("{} {}", "⚠️".yellow(), message);
 }
 
+/// Ollama's measured state, as reported by [`check_ollama`].
+#[derive(Debug, Default, Serialize)]
+struct OllamaStatus {
+    running: bool,
+    version: Option<String>,
+    embedding_working: bool,
+    embedding_latency_ms: Option<f64>,
+}
+
 /// Check if Ollama is running
-fn check_ollama(config: &StatusConfig) -> Result<bool> {
-    log_info("Checking Ollama service...");
+fn check_ollama(config: &StatusConfig) -> Result<OllamaStatus> {
+    if config.is_text() {
+        log_info("Checking Ollama service...");
+    }
 
     // Check if process is running
     let output = ProcessCommand::new("pgrep")
@@ -57,8 +89,15 @@ fn check_ollama(config: &StatusConfig) -> Result<bool> {
             .unwrap_or(0.0);
         let mem_gb = mem_kb / 1024.0 / 1024.0;
 
-        log_success(&format!("Ollama service: RUNNING"));
-        println!("   Memory usage: {:.2} GB", mem_gb);
+        if config.is_text() {
+            log_success(&format!("Ollama service: RUNNING"));
+            println!("   Memory usage: {:.2} GB", mem_gb);
+        }
+
+        let mut status = OllamaStatus {
+            running: true,
+            ..OllamaStatus::default()
+        };
 
         // Check API connectivity
         match ProcessCommand::new("curl")
@@ -69,33 +108,44 @@ fn check_ollama(config: &StatusConfig) -> Result<bool> {
             Ok(version_output) if version_output.status.success() => {
                 let version_str = String::from_utf8_lossy(&version_output.stdout);
                 if let Some(version) = extract_version(&version_str) {
-                    println!("   Version: {}", version);
+                    if config.is_text() {
+                        println!("   Version: {}", version);
+                    }
+                    status.version = Some(version);
                 }
 
                 // Try embedding test
                 let start = Instant::now();
                 let embed_test = check_embedding_api(config)?;
                 let duration = start.elapsed();
-
-                if embed_test {
-                    log_success(&format!(
-                        "Embedding API: WORKING (response time: {:.2}s)",
-                        duration.as_secs_f64()
-                    ));
-                } else {
-                    log_error("Embedding API: NOT WORKING");
+                status.embedding_working = embed_test;
+                status.embedding_latency_ms = Some(duration.as_secs_f64() * 1000.0);
+
+                if config.is_text() {
+                    if embed_test {
+                        log_success(&format!(
+                            "Embedding API: WORKING (response time: {:.2}s)",
+                            duration.as_secs_f64()
+                        ));
+                    } else {
+                        log_error("Embedding API: NOT WORKING");
+                    }
                 }
             }
             _ => {
-                log_error("Ollama API: NOT RESPONDING");
+                if config.is_text() {
+                    log_error("Ollama API: NOT RESPONDING");
+                }
             }
         }
 
-        Ok(true)
+        Ok(status)
     } else {
-        log_error("Ollama service: NOT RUNNING");
-        println!("   Run 'ollama serve' to start the service");
-        Ok(false)
+        if config.is_text() {
+            log_error("Ollama service: NOT RUNNING");
+            println!("   Run 'ollama serve' to start the service");
+        }
+        Ok(OllamaStatus::default())
     }
 }
 
@@ -129,16 +179,25 @@ fn check_embedding_api(config: &StatusConfig) -> Result<bool> {
 
     let response = String::from_utf8_lossy(&output.stdout);
 
-    if config.verbose {
+    if config.verbose && config.is_text() {
         println!("Embedding API Response: {}", response);
     }
 
     Ok(response.contains("embedding"))
 }
 
+/// A single required model's availability, as reported by [`check_models`].
+#[derive(Debug, Serialize)]
+struct ModelStatus {
+    name: String,
+    available: bool,
+}
+
 /// Check models available in Ollama
-fn check_models(config: &StatusConfig) -> Result<()> {
-    log_info("Checking available models...");
+fn check_models(config: &StatusConfig) -> Result<Vec<ModelStatus>> {
+    if config.is_text() {
+        log_info("Checking available models...");
+    }
 
     let output = ProcessCommand::new("ollama")
         .arg("list")
@@ -147,28 +206,45 @@ fn check_models(config: &StatusConfig) -> Result<()> {
 
     let models = String::from_utf8_lossy(&output.stdout);
 
-    if config.verbose {
+    if config.verbose && config.is_text() {
         println!("\nModels available:");
         println!("{}", models);
     }
 
     // Check for required models
     let required_models = ["harald-phi4"];
+    let mut statuses = Vec::new();
     for model in required_models {
-        if models.contains(model) {
-            log_success(&format!("Required model '{}' is available", model));
-        } else {
-            log_error(&format!("Required model '{}' is NOT available", model));
-            println!("   Run 'ollama pull {}' to install", model);
+        let available = models.contains(model);
+        if config.is_text() {
+            if available {
+                log_success(&format!("Required model '{}' is available", model));
+            } else {
+                log_error(&format!("Required model '{}' is NOT available", model));
+                println!("   Run 'ollama pull {}' to install", model);
+            }
         }
+        statuses.push(ModelStatus {
+            name: model.to_string(),
+            available,
+        });
     }
 
-    Ok(())
+    Ok(statuses)
+}
+
+/// Disk usage for the current directory, as reported by [`check_filesystem`].
+#[derive(Debug, Serialize)]
+struct FilesystemStatus {
+    disk_used_percent: Option<u32>,
+    available_space: Option<String>,
 }
 
 /// Check file system status
-fn check_filesystem(config: &StatusConfig) -> Result<()> {
-    log_info("Checking filesystem...");
+fn check_filesystem(config: &StatusConfig) -> Result<FilesystemStatus> {
+    if config.is_text() {
+        log_info("Checking filesystem...");
+    }
 
     // Check disk space
     let output = ProcessCommand::new("df")
@@ -180,6 +256,11 @@ fn check_filesystem(config: &StatusConfig) -> Result<()> {
     let df_output = String::from_utf8_lossy(&output.stdout);
     let lines: Vec<&str> = df_output.lines().collect();
 
+    let mut status = FilesystemStatus {
+        disk_used_percent: None,
+        available_space: None,
+    };
+
     if lines.len() >= 2 {
         let parts: Vec<&str> = lines[1].split_whitespace().collect();
         if parts.len() >= 5 {
@@ -188,70 +269,270 @@ fn check_filesystem(config: &StatusConfig) -> Result<()> {
                 .trim_end_matches('%')
                 .parse::<u32>()
                 .unwrap_or(0);
-
-            if used_value > 90 {
-                log_warning(&format!("Disk usage: {} (critical)", used_percent));
-            } else if used_value > 80 {
-                log_warning(&format!("Disk usage: {} (high)", used_percent));
-            } else {
-                log_success(&format!("Disk usage: {} (ok)", used_percent));
+            status.disk_used_percent = Some(used_value);
+            status.available_space = Some(parts[3].to_string());
+
+            if config.is_text() {
+                if used_value > 90 {
+                    log_warning(&format!("Disk usage: {} (critical)", used_percent));
+                } else if used_value > 80 {
+                    log_warning(&format!("Disk usage: {} (high)", used_percent));
+                } else {
+                    log_success(&format!("Disk usage: {} (ok)", used_percent));
+                }
+                println!("   Available space: {}", parts[3]);
             }
-
-            println!("   Available space: {}", parts[3]);
         }
     }
 
-    Ok(())
+    Ok(status)
+}
+
+/// A single entry in `config/vector-stores-registry.json`.
+#[derive(Debug, Deserialize)]
+struct VectorStoreRegistryEntry {
+    name: String,
+    /// `#[serde(default)]` so registries written before this field existed
+    /// still load, defaulting to the on-disk flat-file backend.
+    #[serde(default)]
+    backend: VectorStoreBackend,
+    /// Directory (for `flat`) or connection string (for `pgvector`).
+    addr: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VectorStoreBackend {
+    #[default]
+    Flat,
+    Pgvector,
+}
+
+/// Per-store health, as reported by [`check_vector_store`].
+#[derive(Debug, Serialize)]
+struct StoreHealth {
+    reachable: bool,
+    row_count: Option<usize>,
+    dimension: Option<usize>,
+    detail: Option<String>,
+}
+
+/// A named vector store's measured health, combining its registry entry
+/// with the [`StoreHealth`] [`check_flat_store`]/[`check_pgvector_store`]
+/// produced for it.
+#[derive(Debug, Serialize)]
+struct VectorStoreStatus {
+    name: String,
+    backend: &'static str,
+    #[serde(flatten)]
+    health: StoreHealth,
+}
+
+/// Checks a `flat`-backend store: a directory holding an `index.hnsw.*`
+/// file set and a `meta.json` sidecar (written by the ingest pipeline).
+/// Row count comes straight from `meta.json`'s record count; dimension
+/// isn't recorded there today, so it's always reported as unknown for
+/// this backend.
+fn check_flat_store(addr: &str) -> StoreHealth {
+    let dir = std::path::Path::new(addr);
+    let meta_path = dir.join("meta.json");
+
+    match fs::read_to_string(&meta_path) {
+        Ok(content) => match serde_json::from_str::<Vec<serde_json::Value>>(&content) {
+            Ok(records) => StoreHealth {
+                reachable: true,
+                row_count: Some(records.len()),
+                dimension: None,
+                detail: None,
+            },
+            Err(e) => StoreHealth {
+                reachable: false,
+                row_count: None,
+                dimension: None,
+                detail: Some(format!("meta.json is not valid JSON: {e}")),
+            },
+        },
+        Err(e) => StoreHealth {
+            reachable: false,
+            row_count: None,
+            dimension: None,
+            detail: Some(format!("{} not readable: {e}", meta_path.display())),
+        },
+    }
+}
+
+/// Checks a `pgvector`-backend store. Only confirms the host:port from the
+/// connection string accepts a TCP connection - row count and dimension
+/// need a real Postgres client (not a dependency of this crate yet), so
+/// they're reported as unknown rather than guessed at.
+fn check_pgvector_store(addr: &str) -> StoreHealth {
+    let host_port = addr
+        .split("://")
+        .last()
+        .unwrap_or(addr)
+        .split('@')
+        .last()
+        .unwrap_or(addr)
+        .split('/')
+        .next()
+        .unwrap_or(addr);
+
+    match TcpStream::connect(host_port) {
+        Ok(_) => StoreHealth {
+            reachable: true,
+            row_count: None,
+            dimension: None,
+            detail: Some("row count/dimension require a Postgres client".to_string()),
+        },
+        Err(e) => StoreHealth {
+            reachable: false,
+            row_count: None,
+            dimension: None,
+            detail: Some(format!("could not connect to {host_port}: {e}")),
+        },
+    }
 }
 
 /// Check vector store status
-fn check_vector_store(config: &StatusConfig) -> Result<()> {
-    log_info("Checking vector store status...");
+fn check_vector_store(config: &StatusConfig) -> Result<Vec<VectorStoreStatus>> {
+    if config.is_text() {
+        log_info("Checking vector store status...");
+    }
 
-    // For this example, we'll just list the vector stores
     let registry_path = "config/vector-stores-registry.json";
 
-    // Check if registry exists
-    let output = ProcessCommand::new("cat").arg(registry_path).output();
+    let registry = match fs::read_to_string(registry_path) {
+        Ok(content) => content,
+        Err(_) => {
+            if config.is_text() {
+                log_error("Vector store registry not found or cannot be read");
+            }
+            return Ok(Vec::new());
+        }
+    };
+
+    let entries: Vec<VectorStoreRegistryEntry> = match serde_json::from_str(&registry) {
+        Ok(entries) => entries,
+        Err(e) => {
+            if config.is_text() {
+                log_error(&format!("Vector store registry is not valid JSON: {e}"));
+            }
+            return Ok(Vec::new());
+        }
+    };
+
+    if config.is_text() {
+        log_success(&format!(
+            "Vector store registry found ({} stores)",
+            entries.len()
+        ));
+    }
 
-    match output {
-        Ok(output) if output.status.success() => {
-            let registry = String::from_utf8_lossy(&output.stdout);
-            log_success("Vector store registry found");
+    let mut statuses = Vec::new();
+    for entry in &entries {
+        let health = match entry.backend {
+            VectorStoreBackend::Flat => check_flat_store(&entry.addr),
+            VectorStoreBackend::Pgvector => check_pgvector_store(&entry.addr),
+        };
+        let backend_label = match entry.backend {
+            VectorStoreBackend::Flat => "flat",
+            VectorStoreBackend::Pgvector => "pgvector",
+        };
+
+        if config.is_text() {
+            if health.reachable {
+                log_success(&format!("'{}' ({}): reachable", entry.name, backend_label));
+            } else {
+                log_error(&format!(
+                    "'{}' ({}): unreachable",
+                    entry.name, backend_label
+                ));
+            }
 
             if config.verbose {
-                // Try to count the number of stores
-                let store_count = registry.matches(r#""name":"#).count();
-                println!("   {} vector stores registered", store_count);
+                if let Some(row_count) = health.row_count {
+                    println!("   rows: {row_count}");
+                }
+                if let Some(dimension) = health.dimension {
+                    println!("   dimension: {dimension}");
+                }
+                if let Some(detail) = &health.detail {
+                    println!("   {detail}");
+                }
             }
         }
-        _ => {
-            log_error("Vector store registry not found or cannot be read");
-        }
+
+        statuses.push(VectorStoreStatus {
+            name: entry.name.clone(),
+            backend: backend_label,
+            health,
+        });
     }
 
-    Ok(())
+    Ok(statuses)
+}
+
+/// Schema version of [`StatusReport`], for downstream compatibility
+/// checks (mirrors `harald::ingest::report::SCHEMA_VERSION`'s role for
+/// `IngestReport`). Bump whenever the report's shape changes in a way
+/// downstream tooling should be aware of.
+const STATUS_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// A full snapshot of HARALD's system status, serialized as-is for
+/// `--format json`.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    schema_version: u32,
+    /// Overall rollup: `false` if Ollama isn't running, a required model
+    /// is missing, or (when `--all` was passed) a registered vector store
+    /// is unreachable. Drives the process exit code.
+    healthy: bool,
+    ollama: OllamaStatus,
+    models: Vec<ModelStatus>,
+    filesystem: Option<FilesystemStatus>,
+    vector_stores: Vec<VectorStoreStatus>,
 }
 
 /// Run the status check
-fn run_status_check(config: &StatusConfig) -> Result<bool> {
-    println!("🔍 Checking HARALD System Status");
-    println!("--------------------------------");
+fn run_status_check(config: &StatusConfig) -> Result<StatusReport> {
+    if config.is_text() {
+        println!("🔍 Checking HARALD System Status");
+        println!("--------------------------------");
+    }
 
-    let ollama_running = check_ollama(config)?;
+    let ollama = check_ollama(config)?;
 
-    if ollama_running {
-        check_models(config)?;
-    }
+    let models = if ollama.running {
+        check_models(config)?
+    } else {
+        Vec::new()
+    };
 
-    if config.check_all {
-        check_filesystem(config)?;
-        check_vector_store(config)?;
-    }
+    let (filesystem, vector_stores) = if config.check_all {
+        (
+            Some(check_filesystem(config)?),
+            check_vector_store(config)?,
+        )
+    } else {
+        (None, Vec::new())
+    };
 
-    println!("\nStatus check completed.");
+    if config.is_text() {
+        println!("\nStatus check completed.");
+    }
 
-    Ok(ollama_running)
+    let healthy = ollama.running
+        && models.iter().all(|m| m.available)
+        && vector_stores.iter().all(|s| s.health.reachable);
+
+    Ok(StatusReport {
+        schema_version: STATUS_REPORT_SCHEMA_VERSION,
+        healthy,
+        ollama,
+        models,
+        filesystem,
+        vector_stores,
+    })
 }
 
 fn main() -> Result<()> {
@@ -271,15 +552,36 @@ fn main() -> Result<()> {
                 .help("Check all subsystems including filesystem and vector stores")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .help("Output format: 'text' (default, emoji-decorated) or 'json' (a StatusReport on stdout, for monitoring integrations)"),
+        )
         .get_matches();
 
+    let format = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("json") => OutputFormat::Json,
+        _ => OutputFormat::Text,
+    };
+
     let config = StatusConfig {
         verbose: matches.get_flag("verbose"),
         check_all: matches.get_flag("all"),
+        format,
     };
 
-    match run_status_check(&config)? {
-        true => Ok(()),
-        false => std::process::exit(1),
+    let report = run_status_check(&config)?;
+
+    if config.format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+
+    if report.healthy {
+        Ok(())
+    } else {
+        std::process::exit(1);
     }
 }
@@ -1,9 +1,18 @@
 use anyhow::{Context, Result};
 use clap::{ArgAction, Command};
-use colored::*;
-use std::path::PathBuf;
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
 use std::process::Command as SystemCommand;
 use std::process::ExitStatus;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::utils::validation::formatter_config::FormatterFileConfig;
+use crate::utils::validation::report::{FileStatus, OutputFormat, Reporter};
+
+/// How long to wait after the first file-change event before re-checking,
+/// coalescing a burst of saves into a single run.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
 
 /// Run a command and return its exit status
 fn run_command(command: &str, args: &[&str]) -> Result<ExitStatus> {
@@ -15,19 +24,6 @@ fn run_command(command: &str, args: &[&str]) -> Result<ExitStatus> {
     Ok(status)
 }
 
-/// Log utilities for consistent output
-fn log_info(message: &str) {
-    println!("{} {}", "[INFO]".blue().bold(), message);
-}
-
-fn log_success(message: &str) {
-    println!("{} {}", "[SUCCESS]".green().bold(), message);
-}
-
-fn log_error(message: &str) {
-    eprintln!("{} {}", "[ERROR]".red().bold(), message);
-}
-
 fn main() -> Result<()> {
     let matches = Command::new("check_json")
         .version("0.1.0")
@@ -47,36 +43,79 @@ fn main() -> Result<()> {
                 .help("Automatically fix formatting issues")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            clap::Arg::new("watch")
+                .long("watch")
+                .help("Stay resident and re-check when the registry or a registered JSON file changes")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("diff")
+                .long("diff")
+                .help("Show a diff of what would change instead of just pass/fail")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("format")
+                .long("format")
+                .value_name("human|json")
+                .help("Output format: colored human-readable lines, or one JSON report on stdout")
+                .default_value("human"),
+        )
         .get_matches();
 
+    let output_format = OutputFormat::parse(matches.get_one::<String>("format").unwrap())?;
+    let mut reporter = Reporter::new(output_format);
+
     // Get the path to the format_json binary
     let format_json_path = get_format_json_path()?;
 
     // Build Rust tools if requested
     if matches.get_flag("build") {
-        log_info("Building Rust JSON tools...");
+        reporter.info("Building Rust JSON tools...");
         let status = run_command("cargo", &["build", "--release", "--features", "cli"])?;
         if !status.success() {
-            log_error("Failed to build Rust tools");
+            reporter.error("Failed to build Rust tools");
             std::process::exit(1);
         }
-        log_success("Build successful");
+        reporter.success("Build successful");
+    }
+
+    let current_dir = std::env::current_dir()?;
+    let (file_config, config_dir) =
+        FormatterFileConfig::load(&current_dir).context("Failed to load .heraldfmt.toml")?;
+    let registry_path = match &file_config.registry {
+        Some(path) => config_dir.join(path),
+        None => find_registry_path(&reporter)?,
+    };
+    let fix = matches.get_flag("fix");
+    let diff = matches.get_flag("diff");
+
+    if matches.get_flag("watch") {
+        return watch_registry(&format_json_path, &registry_path, fix, diff, output_format);
     }
 
-    log_info("Validating and formatting JSON files using Rust tools...");
+    run_checks(&format_json_path, &registry_path, fix, diff, &mut reporter)?;
+    match reporter.finish() {
+        0 => Ok(()),
+        _ => std::process::exit(1),
+    }
+}
 
-    // Get the absolute path to the registry file
+/// Locates `data/vector-stores-registry.json`, walking from the current
+/// directory and (if run from `src/`) its parent, same as before `--watch`
+/// existed. Falls back to the project-root guess if neither exists, and
+/// lets the subsequent `format_json` invocation fail naturally. Skipped
+/// entirely when `.heraldfmt.toml` sets an explicit `registry` path.
+fn find_registry_path(reporter: &Reporter) -> Result<PathBuf> {
     let current_dir = std::env::current_dir()?;
-    log_info(&format!("Current directory: {}", current_dir.display()));
+    reporter.info(&format!("Current directory: {}", current_dir.display()));
 
-    // Try to find the registry file
-    log_info("Searching for registry file...");
+    reporter.info("Searching for registry file...");
 
-    // Try direct path
     let registry_path_direct = current_dir.join("data").join("vector-stores-registry.json");
-    log_info(&format!("Checking: {}", registry_path_direct.display()));
+    reporter.info(&format!("Checking: {}", registry_path_direct.display()));
 
-    // Try project root
     let registry_path_root = if current_dir.ends_with("src") {
         current_dir
             .parent()
@@ -86,35 +125,60 @@ fn main() -> Result<()> {
     } else {
         current_dir.join("data").join("vector-stores-registry.json")
     };
-    log_info(&format!("Checking: {}", registry_path_root.display()));
+    reporter.info(&format!("Checking: {}", registry_path_root.display()));
 
-    // Use whatever exists
     let registry_path = if registry_path_direct.exists() {
-        log_info(&format!(
+        reporter.info(&format!(
             "Found registry at: {}",
             registry_path_direct.display()
         ));
         registry_path_direct
     } else if registry_path_root.exists() {
-        log_info(&format!(
+        reporter.info(&format!(
             "Found registry at: {}",
             registry_path_root.display()
         ));
         registry_path_root
     } else {
-        log_error("Registry file not found!");
-        log_info("Using default registry path as fallback...");
+        reporter.error("Registry file not found!");
+        reporter.info("Using default registry path as fallback...");
         registry_path_root
     };
 
-    // First, check if all registered JSON files are valid
-    let mut check_args = vec!["--check", "--all"];
+    Ok(registry_path)
+}
+
+/// Runs the check (or check-then-fix) pass plus registry validation against
+/// `registry_path`, exactly as `main` did before `--watch` existed. Returns
+/// whether every step passed.
+///
+/// When `diff` is set, `format_json` is asked to print what would change
+/// instead of just failing, via its own `--diff` flag - `format_json`
+/// already computes this in memory while checking, so there's nothing for
+/// this wrapper to compute itself.
+///
+/// Each stage is recorded on `reporter` as one result (`"registered JSON
+/// files"` for the check/fix pass, `registry_path` for validation) rather
+/// than one per underlying JSON file: `format_json` does the real
+/// per-file checking as a subprocess and only reports back a single
+/// pass/fail for the whole batch, so that's the finest detail this
+/// wrapper actually has to report.
+fn run_checks(
+    format_json_path: &Path,
+    registry_path: &Path,
+    fix: bool,
+    diff: bool,
+    reporter: &mut Reporter,
+) -> Result<bool> {
+    let registry_path_str = registry_path.to_str().unwrap();
 
-    // Always provide the explicit registry path
-    check_args.push("--registry");
-    check_args.push(registry_path.to_str().unwrap());
+    // First, check if all registered JSON files are valid
+    let mut check_args = vec!["--check", "--all", "--registry", registry_path_str];
+    if diff {
+        check_args.push("--diff");
+    }
 
-    log_info(&format!(
+    reporter.info(&format!(
         "Running: {} {}",
         format_json_path.display(),
         check_args.join(" ")
@@ -123,51 +187,139 @@ fn main() -> Result<()> {
     let check_status = run_command(format_json_path.to_str().unwrap(), &check_args)?;
 
     if check_status.success() {
-        log_success("All registered JSON files are valid");
-    } else if matches.get_flag("fix") {
-        log_info("Some JSON files need formatting - applying fixes...");
-
-        let mut fix_args = vec!["--all"];
-
-        // Always provide the explicit registry path
-        fix_args.push("--registry");
-        fix_args.push(registry_path.to_str().unwrap());
-
+        reporter.success("All registered JSON files are valid");
+        reporter.record(
+            "registered JSON files",
+            FileStatus::Ok,
+            "all registered JSON files are valid",
+        );
+    } else if fix {
+        reporter.info("Some JSON files need formatting - applying fixes...");
+
+        let fix_args = vec!["--all", "--registry", registry_path_str];
         let fix_status = run_command(format_json_path.to_str().unwrap(), &fix_args)?;
 
         if fix_status.success() {
-            log_success("Applied formatting fixes");
+            reporter.success("Applied formatting fixes");
+            reporter.record(
+                "registered JSON files",
+                FileStatus::Reformatted,
+                "formatting fixes applied",
+            );
         } else {
-            log_error("Failed to apply formatting fixes");
-            std::process::exit(1);
+            reporter.error("Failed to apply formatting fixes");
+            reporter.record(
+                "registered JSON files",
+                FileStatus::Error,
+                "failed to apply formatting fixes",
+            );
+            return Ok(false);
         }
     } else {
-        log_error("JSON formatting issues detected. Run with --fix to automatically resolve them.");
-        std::process::exit(1);
+        reporter.error("JSON formatting issues detected. Run with --fix to automatically resolve them.");
+        reporter.record(
+            "registered JSON files",
+            FileStatus::NeedsFormat,
+            "formatting issues detected; run with --fix",
+        );
+        return Ok(false);
     }
 
     // Now validate registry
-    log_info("Validating registry...");
-
-    let mut validate_args = vec!["--validate-registry"];
-
-    // Always provide the explicit registry path
-    validate_args.push("--registry");
-    validate_args.push(registry_path.to_str().unwrap());
+    reporter.info("Validating registry...");
 
+    let validate_args = vec!["--validate-registry", "--registry", registry_path_str];
     let validate_status = run_command(format_json_path.to_str().unwrap(), &validate_args)?;
 
     if validate_status.success() {
-        log_success("Registry validation passed");
+        reporter.success("Registry validation passed");
+        reporter.record(registry_path_str, FileStatus::Ok, "registry validation passed");
     } else {
-        log_error("Registry validation failed");
-        std::process::exit(1);
+        reporter.error("Registry validation failed");
+        reporter.record(registry_path_str, FileStatus::Error, "registry validation failed");
+        return Ok(false);
+    }
+
+    reporter.success("JSON validation and formatting complete!");
+    Ok(true)
+}
+
+/// Watches the registry file and its containing directory (so changes to
+/// any registered JSON file are also caught) and re-runs [`run_checks`]
+/// whenever something changes, coalescing a burst of events the same way
+/// `format_md --watch` does. Runs until interrupted (Ctrl-C).
+fn watch_registry(
+    format_json_path: &Path,
+    registry_path: &Path,
+    fix: bool,
+    diff: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let watch_dir = registry_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    Reporter::new(format).info(&format!(
+        "Watching {} for changes (Ctrl-C to stop)...",
+        watch_dir.display()
+    ));
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(&watch_dir, RecursiveMode::Recursive)
+        .context("Failed to start watching registry directory")?;
+
+    loop {
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => break, // watcher dropped, channel closed
+        };
+        let mut relevant = touches_json_file(&first);
+
+        // Drain whatever else arrives within WATCH_DEBOUNCE so a burst of
+        // saves triggers one re-check instead of one per event.
+        let deadline = std::time::Instant::now() + WATCH_DEBOUNCE;
+        while let Ok(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(remaining) {
+                Ok(event) => relevant |= touches_json_file(&event),
+                Err(_) => break,
+            }
+        }
+
+        if !relevant {
+            continue;
+        }
+        let mut reporter = Reporter::new(format);
+        reporter.info("File change detected, re-checking...");
+        if let Err(e) = run_checks(format_json_path, registry_path, fix, diff, &mut reporter) {
+            reporter.error(&format!("Check run failed: {e}"));
+        }
+        reporter.finish();
     }
 
-    log_success("JSON validation and formatting complete!");
     Ok(())
 }
 
+/// Whether `event` touches a `.json` file, so unrelated writes under the
+/// registry's directory (editor swap files, `.DS_Store`, etc.) don't
+/// trigger a full check+fix+validate pass.
+fn touches_json_file(event: &notify::Event) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|path| path.extension().is_some_and(|ext| ext == "json"))
+}
+
 /// Get the path to the format_json binary
 fn get_format_json_path() -> Result<PathBuf> {
     // First check if we're in the src directory or project root
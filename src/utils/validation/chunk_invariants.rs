@@ -0,0 +1,123 @@
+//! Validates that a vector store's declared `chunkingStrategy` and
+//! `maxChunkSize` actually hold for its registered source files, so the
+//! registry can't silently drift out of sync with the data it describes.
+
+use crate::utils::chunking::{chunk_text, ChunkerOptions, ChunkingStrategy};
+use std::fs;
+
+/// A store's chunking-relevant configuration, decoupled from whichever
+/// registry type a caller happens to use so this module has no
+/// dependency on it.
+#[derive(Debug, Clone)]
+pub struct StoreChunkSpec {
+    pub id: String,
+    pub chunking_strategy: String,
+    pub max_chunk_size: usize,
+    pub embedding_model: String,
+    pub source_files: Vec<String>,
+}
+
+/// One invariant violation found while validating a store's chunking
+/// configuration.
+#[derive(Debug, Clone)]
+pub enum ChunkViolation {
+    /// A chunk produced from `file` exceeded the store's `maxChunkSize`.
+    OversizedChunk {
+        store_id: String,
+        file: String,
+        chunk_size: usize,
+        max_chunk_size: usize,
+    },
+    /// The store's `chunkingStrategy` doesn't match a known strategy name.
+    UnknownStrategy { store_id: String, strategy: String },
+    /// The store's `embeddingModel` isn't listed in `embeddingModels`.
+    UnknownEmbeddingModel { store_id: String, model: String },
+}
+
+impl ChunkViolation {
+    /// The ID of the store this violation was found in.
+    pub fn store_id(&self) -> &str {
+        match self {
+            Self::OversizedChunk { store_id, .. }
+            | Self::UnknownStrategy { store_id, .. }
+            | Self::UnknownEmbeddingModel { store_id, .. } => store_id,
+        }
+    }
+}
+
+impl std::fmt::Display for ChunkViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OversizedChunk {
+                store_id,
+                file,
+                chunk_size,
+                max_chunk_size,
+            } => write!(
+                f,
+                "{store_id}: {file} produced a {chunk_size}-char chunk, exceeding maxChunkSize {max_chunk_size}"
+            ),
+            Self::UnknownStrategy { store_id, strategy } => {
+                write!(f, "{store_id}: unknown chunkingStrategy '{strategy}'")
+            }
+            Self::UnknownEmbeddingModel { store_id, model } => {
+                write!(f, "{store_id}: embeddingModel '{model}' is not registered")
+            }
+        }
+    }
+}
+
+/// Validates each store's chunking invariants, returning every violation
+/// found. Source files that can't be read are skipped rather than
+/// treated as a violation - that's a separate, pre-existing concern
+/// (missing files are reported elsewhere during formatting).
+pub fn validate_chunk_invariants(
+    stores: &[StoreChunkSpec],
+    known_embedding_models: &[String],
+) -> Vec<ChunkViolation> {
+    let mut violations = Vec::new();
+
+    for store in stores {
+        if !known_embedding_models.contains(&store.embedding_model) {
+            violations.push(ChunkViolation::UnknownEmbeddingModel {
+                store_id: store.id.clone(),
+                model: store.embedding_model.clone(),
+            });
+        }
+
+        let Some(strategy) = ChunkingStrategy::from_name(&store.chunking_strategy, store.max_chunk_size) else {
+            violations.push(ChunkViolation::UnknownStrategy {
+                store_id: store.id.clone(),
+                strategy: store.chunking_strategy.clone(),
+            });
+            continue;
+        };
+
+        for file in &store.source_files {
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+
+            let chunks = chunk_text(
+                &content,
+                ChunkerOptions {
+                    strategy: strategy.clone(),
+                    ..ChunkerOptions::default()
+                },
+            );
+
+            for chunk in chunks {
+                if chunk.len() > store.max_chunk_size {
+                    violations.push(ChunkViolation::OversizedChunk {
+                        store_id: store.id.clone(),
+                        file: file.clone(),
+                        chunk_size: chunk.len(),
+                        max_chunk_size: store.max_chunk_size,
+                    });
+                }
+            }
+        }
+    }
+
+    violations
+}
@@ -1,8 +1,31 @@
 use anyhow::{Context, Result};
 use clap::{Arg, ArgAction, Command};
 use colored::*;
+use ignore::WalkBuilder;
+use notify::{RecursiveMode, Watcher};
+use std::collections::{HashSet, VecDeque};
 use std::process::Command as ProcessCommand;
-use walkdir::WalkDir;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::utils::validation::formatter_config::{FormatterFileConfig, ProseWrap};
+use crate::utils::validation::report::{FileStatus, OutputFormat, Reporter};
+
+/// How long to wait after the first file-change event before running a
+/// pass, so a burst of saves (e.g. an editor writing a swap file, then the
+/// real file) only triggers one re-check instead of one per event.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Lines of unchanged context kept around each changed line when rendering
+/// a diff, Deno-fmt style.
+const DIFF_CONTEXT: usize = 3;
+
+/// Above this many lines per side, skip the line diff and just report that
+/// the file differs - mirrors `format_json`'s `MAX_DIFF_LINES` guard, since
+/// a near-total rewrite makes a line-by-line diff expensive without being
+/// any more useful than "this file would change".
+const MAX_DIFF_LINES: usize = 5_000;
 
 /// Configuration for Markdown formatting
 #[derive(Debug)]
@@ -10,114 +33,539 @@ struct FormatterConfig {
     verbose: bool,
     check_only: bool,
     target_path: String,
+    watch: bool,
+    diff: bool,
+    /// Resolved from `.heraldfmt.toml` (CLI flags override the file's
+    /// values); see [`crate::utils::validation::formatter_config`].
+    print_width: u32,
+    prose_wrap: ProseWrap,
+    file_config: FormatterFileConfig,
+    /// Number of files to hand to prettier concurrently.
+    jobs: usize,
 }
 
-/// Format Markdown files using prettier
-fn format_markdown_files(config: &FormatterConfig) -> Result<bool> {
-    println!("Formatting Markdown files in {}", config.target_path);
-
-    if config.verbose {
-        println!("{} {}", "[INFO]".blue().bold(), "Finding Markdown files...");
-    }
-
-    // Find all .md files, excluding node_modules and target directories
+/// Finds every file under `target_path` matching `config`'s `include`/
+/// `exclude` globs (relative to `target_path`) and ending in `.md`.
+///
+/// Built on the `ignore` crate rather than a bare recursive walk (the same
+/// choice rustc's `tidy` tool makes), so `.gitignore`/`.ignore` rules are
+/// honored automatically - a vendored dependency tree never gets statted
+/// at all even if it's missing from `exclude`. `exclude` globs still prune
+/// whole directories before the walker descends into them, for cases
+/// `.gitignore` doesn't cover.
+fn discover_md_files(config: &FormatterConfig) -> Vec<String> {
     let mut md_files = Vec::new();
 
-    for entry in WalkDir::new(&config.target_path)
-        .into_iter()
-        .filter_entry(|e| {
-            let path = e.path().to_string_lossy();
-            !path.contains("/node_modules/") && !path.contains("/target/")
+    let target_path = config.target_path.clone();
+    let file_config = config.file_config.clone();
+    let walker = WalkBuilder::new(&target_path)
+        .filter_entry(move |entry| {
+            if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return true;
+            }
+            let relative = entry
+                .path()
+                .strip_prefix(&target_path)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .into_owned();
+            !file_config.is_excluded_dir(&relative)
         })
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() && entry.path().to_string_lossy().ends_with(".md") {
+        .build();
+
+    let target_path = &config.target_path;
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|ft| ft.is_file())
+            || !entry.path().to_string_lossy().ends_with(".md")
+        {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(target_path)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .into_owned();
+        if config.file_config.path_included(&relative) {
             md_files.push(entry.path().to_string_lossy().into_owned());
         }
     }
 
+    md_files
+}
+
+/// Asks `git diff --name-only --cached` for the staged file set and
+/// returns just the `.md` paths matching `config`'s `include`/`exclude`
+/// globs, so `--staged` can serve as a fast pre-commit hook that only
+/// checks what's about to be committed instead of walking the tree.
+fn discover_staged_md_files(config: &FormatterConfig) -> Result<Vec<String>> {
+    let output = ProcessCommand::new("git")
+        .args(["diff", "--name-only", "--cached"])
+        .current_dir(&config.target_path)
+        .output()
+        .context("Failed to run git diff --name-only --cached")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff --name-only --cached failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let mut md_files = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if line.is_empty() || !line.ends_with(".md") {
+            continue;
+        }
+        if !config.file_config.path_included(line) {
+            continue;
+        }
+        let path = std::path::Path::new(&config.target_path).join(line);
+        if path.is_file() {
+            md_files.push(path.to_string_lossy().into_owned());
+        }
+    }
+
+    Ok(md_files)
+}
+
+/// One file's outcome from a worker in [`run_pool`]: what to [`Reporter::record`]
+/// once the pool has finished, plus whether it counts as a pass for the
+/// overall batch result.
+struct FileOutcome {
+    path: String,
+    status: FileStatus,
+    message: String,
+    ok: bool,
+}
+
+/// Runs `work` over `files` using up to `jobs` worker threads pulling from
+/// a shared queue, the same bounded-pool shape `format_json --jobs` uses.
+/// `work` only reads (`Reporter::info`/`success`/`warning`/`error` take
+/// `&self`); per-file records are collected here and applied to `reporter`
+/// afterward, since [`Reporter::record`] itself needs `&mut self`.
+fn run_pool(
+    files: &[String],
+    jobs: usize,
+    work: impl Fn(&str) -> FileOutcome + Sync,
+) -> Vec<FileOutcome> {
+    let jobs = jobs.max(1);
+    let queue = Mutex::new(VecDeque::from(files.to_vec()));
+    let results = Mutex::new(Vec::with_capacity(files.len()));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let file = match queue.lock().unwrap().pop_front() {
+                    Some(file) => file,
+                    None => break,
+                };
+                let outcome = work(&file);
+                results.lock().unwrap().push(outcome);
+            });
+        }
+    });
+
+    results.into_inner().unwrap()
+}
+
+/// Runs prettier over `md_files` per `config`, reporting success/failure the
+/// same way regardless of whether the caller discovered every Markdown file
+/// under `config.target_path` or only a changed subset (as `--watch` does).
+///
+/// Each file is handed to its own prettier invocation across `config.jobs`
+/// worker threads (see [`run_pool`]) rather than one batched invocation
+/// covering every file, so a large tree isn't bottlenecked on a single
+/// subprocess.
+fn format_markdown_files(
+    config: &FormatterConfig,
+    md_files: &[String],
+    reporter: &mut Reporter,
+) -> Result<bool> {
     if md_files.is_empty() {
-        println!("{} {}", "[INFO]".blue().bold(), "No Markdown files found.");
+        reporter.info("No Markdown files found.");
         return Ok(true);
     }
 
     if config.verbose {
-        println!(
-            "{} {}",
-            "[INFO]".blue().bold(),
-            format!("Found {} Markdown files", md_files.len())
-        );
+        reporter.info(&format!("Found {} Markdown files", md_files.len()));
+    }
+
+    if config.check_only && config.diff {
+        return diff_markdown_files(config, md_files, reporter);
+    }
+
+    if config.verbose {
+        reporter.info("Running prettier...");
     }
 
-    // Prepare prettier arguments
+    let outcomes = run_pool(md_files, config.jobs, |file| format_one_file(config, file));
+
+    let mut all_ok = true;
+    for outcome in outcomes {
+        if !outcome.ok {
+            all_ok = false;
+        }
+        match outcome.status {
+            FileStatus::Ok => {}
+            FileStatus::Reformatted => reporter.success(&format!("{}: {}", outcome.path, outcome.message)),
+            FileStatus::NeedsFormat => reporter.warning(&format!("{}: {}", outcome.path, outcome.message)),
+            FileStatus::Error => reporter.error(&format!("{}: {}", outcome.path, outcome.message)),
+        }
+        reporter.record(outcome.path, outcome.status, outcome.message);
+    }
+
+    if all_ok {
+        if config.check_only {
+            reporter.success("Markdown files check passed!");
+        } else {
+            reporter.success("Markdown formatting complete!");
+        }
+    } else if config.check_only {
+        reporter.warning("Some Markdown files need formatting.");
+    } else {
+        reporter.error("Failed to format some Markdown files.");
+    }
+
+    Ok(all_ok)
+}
+
+/// Runs prettier against one file in `--check`/`--write` mode (matching
+/// `config.check_only`) and classifies the result, for use as the
+/// per-file unit of work in [`run_pool`].
+fn format_one_file(config: &FormatterConfig, file: &str) -> FileOutcome {
     let mut prettier_args = vec![
         "--parser".to_string(),
         "markdown".to_string(),
         "--print-width".to_string(),
-        "80".to_string(),
+        config.print_width.to_string(),
         "--prose-wrap".to_string(),
-        "always".to_string(),
+        config.prose_wrap.as_str().to_string(),
         "--log-level".to_string(),
         "warn".to_string(),
     ];
-
     if config.check_only {
         prettier_args.push("--check".to_string());
     } else {
         prettier_args.push("--write".to_string());
     }
+    prettier_args.push(file.to_string());
 
-    // Add files to the arguments
-    prettier_args.extend(md_files);
+    let output = match ProcessCommand::new("prettier").args(&prettier_args).output() {
+        Ok(output) => output,
+        Err(e) => {
+            return FileOutcome {
+                path: file.to_string(),
+                status: FileStatus::Error,
+                message: format!("failed to execute prettier: {e}"),
+                ok: false,
+            }
+        }
+    };
 
-    // Run prettier
-    if config.verbose {
-        println!("{} {}", "[INFO]".blue().bold(), "Running prettier...");
+    if output.status.success() {
+        let (status, message) = if config.check_only {
+            (FileStatus::Ok, "already formatted".to_string())
+        } else {
+            (FileStatus::Reformatted, "formatted".to_string())
+        };
+        FileOutcome {
+            path: file.to_string(),
+            status,
+            message,
+            ok: true,
+        }
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        let status = if config.check_only {
+            FileStatus::NeedsFormat
+        } else {
+            FileStatus::Error
+        };
+        FileOutcome {
+            path: file.to_string(),
+            status,
+            message: if stderr.is_empty() {
+                "would be reformatted".to_string()
+            } else {
+                stderr
+            },
+            ok: false,
+        }
     }
+}
 
-    let output = ProcessCommand::new("prettier")
-        .args(&prettier_args)
-        .output()
-        .context("Failed to execute prettier. Is it installed?")?;
+/// Formats each of `md_files` individually via prettier's default
+/// print-to-stdout mode (neither `--write` nor `--check`), so the candidate
+/// output is computed in memory without touching disk, and prints a
+/// colored unified diff against the original for every file that would
+/// change. Returns whether every file already matched.
+///
+/// The per-file prettier invocation and diff computation run across
+/// `config.jobs` worker threads via [`run_pool`]; results are then sorted
+/// back into `md_files`' order before printing, so parallelizing the work
+/// doesn't scramble the output a human is reading top to bottom.
+fn diff_markdown_files(
+    config: &FormatterConfig,
+    md_files: &[String],
+    reporter: &mut Reporter,
+) -> Result<bool> {
+    let outcomes = run_pool(md_files, config.jobs, |file| diff_one_file(config, file));
 
-    let success = output.status.success();
+    let order: std::collections::HashMap<&str, usize> = md_files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.as_str(), i))
+        .collect();
+    let mut outcomes = outcomes;
+    outcomes.sort_by_key(|o| order.get(o.path.as_str()).copied().unwrap_or(usize::MAX));
 
-    if success {
-        if !config.check_only {
-            println!(
-                "{} {}",
-                "[SUCCESS]".green().bold(),
-                "Markdown formatting complete!"
-            );
-        } else {
-            println!(
-                "{} {}",
-                "[SUCCESS]".green().bold(),
-                "Markdown files check passed!"
-            );
+    let mut all_match = true;
+    for outcome in outcomes {
+        if !outcome.ok {
+            all_match = false;
+        }
+        match outcome.status {
+            FileStatus::Ok => {}
+            FileStatus::NeedsFormat => {
+                reporter.warning(&format!("{} would be reformatted:", outcome.path));
+                reporter.raw(&outcome.message);
+            }
+            FileStatus::Error => {
+                reporter.error(&format!("prettier failed on {}", outcome.path));
+                reporter.raw(&outcome.message);
+            }
+            FileStatus::Reformatted => unreachable!("diff mode never writes files"),
         }
+        let recorded_message = match outcome.status {
+            FileStatus::NeedsFormat => "would be reformatted".to_string(),
+            FileStatus::Ok => "already formatted".to_string(),
+            _ => outcome.message,
+        };
+        reporter.record(outcome.path, outcome.status, recorded_message);
+    }
+
+    if all_match {
+        reporter.success("Markdown files check passed!");
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if config.check_only {
-            println!(
-                "{} {}",
-                "[WARNING]".yellow().bold(),
-                "Some Markdown files need formatting."
-            );
-            if config.verbose {
-                println!("{}", stderr);
+        reporter.warning("Some Markdown files need formatting.");
+    }
+
+    Ok(all_match)
+}
+
+/// Computes what prettier's default (print-to-stdout) mode would produce
+/// for `file` and diffs it against the file on disk, for use as the
+/// per-file unit of work in [`run_pool`]. `FileOutcome::message` carries
+/// the rendered diff (for `NeedsFormat`) or prettier's stderr (for
+/// `Error`) rather than a short status line, since the diff/stderr *is*
+/// the useful output here.
+fn diff_one_file(config: &FormatterConfig, file: &str) -> FileOutcome {
+    let original = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            return FileOutcome {
+                path: file.to_string(),
+                status: FileStatus::Error,
+                message: format!("failed to read {file}: {e}"),
+                ok: false,
+            }
+        }
+    };
+
+    // Neither --write nor --check: prettier's default behavior is to
+    // print the formatted file to stdout, which is exactly the candidate
+    // text we want without touching the file on disk.
+    let prettier_args = [
+        "--parser",
+        "markdown",
+        "--print-width",
+        config.print_width.to_string().as_str(),
+        "--prose-wrap",
+        config.prose_wrap.as_str(),
+        "--log-level",
+        "warn",
+        file,
+    ];
+    let output = match ProcessCommand::new("prettier").args(prettier_args).output() {
+        Ok(output) => output,
+        Err(e) => {
+            return FileOutcome {
+                path: file.to_string(),
+                status: FileStatus::Error,
+                message: format!("failed to execute prettier: {e}"),
+                ok: false,
+            }
+        }
+    };
+
+    if !output.status.success() {
+        return FileOutcome {
+            path: file.to_string(),
+            status: FileStatus::Error,
+            message: String::from_utf8_lossy(&output.stderr).into_owned(),
+            ok: false,
+        };
+    }
+
+    let formatted = String::from_utf8_lossy(&output.stdout).into_owned();
+    if formatted == original {
+        return FileOutcome {
+            path: file.to_string(),
+            status: FileStatus::Ok,
+            message: "already formatted".to_string(),
+            ok: true,
+        };
+    }
+
+    FileOutcome {
+        path: file.to_string(),
+        status: FileStatus::NeedsFormat,
+        message: render_unified_diff(&original, &formatted),
+        ok: false,
+    }
+}
+
+/// Renders a `-`/`+` unified diff between `old` and `new`, with 1-based
+/// line numbers on each side and up to [`DIFF_CONTEXT`] lines of unchanged
+/// context around every run of changes.
+fn render_unified_diff(old: &str, new: &str) -> String {
+    let old_lines = old.lines().count();
+    let new_lines = new.lines().count();
+    if old_lines > MAX_DIFF_LINES || new_lines > MAX_DIFF_LINES {
+        return format!(
+            "  ({old_lines} -> {new_lines} lines, too large to diff in detail)\n"
+        );
+    }
+
+    let ops = diff::lines(old, new);
+
+    let mut old_line = 0usize;
+    let mut new_line = 0usize;
+    let mut rows = Vec::with_capacity(ops.len());
+    for op in &ops {
+        match op {
+            diff::Result::Left(text) => {
+                old_line += 1;
+                rows.push((Some(old_line), None, '-', *text));
+            }
+            diff::Result::Right(text) => {
+                new_line += 1;
+                rows.push((None, Some(new_line), '+', *text));
+            }
+            diff::Result::Both(text, _) => {
+                old_line += 1;
+                new_line += 1;
+                rows.push((Some(old_line), Some(new_line), ' ', *text));
+            }
+        }
+    }
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if row.2 != ' ' {
+            let start = i.saturating_sub(DIFF_CONTEXT);
+            let end = (i + DIFF_CONTEXT + 1).min(rows.len());
+            match hunks.last_mut() {
+                Some((_, last_end)) if start <= *last_end => *last_end = end,
+                _ => hunks.push((start, end)),
             }
-        } else {
-            println!(
-                "{} {}",
-                "[ERROR]".red().bold(),
-                "Failed to format Markdown files."
-            );
-            println!("{}", stderr);
         }
     }
 
-    Ok(success)
+    let mut out = String::new();
+    for (start, end) in hunks {
+        for (old_no, new_no, marker, text) in &rows[start..end] {
+            let old_col = old_no.map_or_else(String::new, |n| n.to_string());
+            let new_col = new_no.map_or_else(String::new, |n| n.to_string());
+            let line = format!("{old_col:>5} {new_col:>5} {marker} {text}");
+            let line = match marker {
+                '-' => line.red().to_string(),
+                '+' => line.green().to_string(),
+                _ => line,
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Watches `config.target_path` for changes and re-formats only the
+/// Markdown files affected by each batch of events, instead of exiting
+/// after one pass. Runs until interrupted (Ctrl-C).
+fn watch_markdown_files(config: &FormatterConfig, format: OutputFormat) -> Result<()> {
+    Reporter::new(format).info(&format!(
+        "Watching {} for changes (Ctrl-C to stop)...",
+        config.target_path
+    ));
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(
+            std::path::Path::new(&config.target_path),
+            RecursiveMode::Recursive,
+        )
+        .context("Failed to start watching target path")?;
+
+    loop {
+        // Block for the first event in this batch, then drain whatever
+        // else arrives within WATCH_DEBOUNCE before acting on the batch.
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => break, // watcher dropped, channel closed
+        };
+
+        let mut changed = HashSet::new();
+        collect_md_paths(config, &first, &mut changed);
+        let deadline = std::time::Instant::now() + WATCH_DEBOUNCE;
+        while let Ok(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(remaining) {
+                Ok(event) => collect_md_paths(config, &event, &mut changed),
+                Err(_) => break,
+            }
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+        let mut reporter = Reporter::new(format);
+        reporter.info("File change detected, re-checking...");
+        let files: Vec<String> = changed.into_iter().collect();
+        if let Err(e) = format_markdown_files(config, &files, &mut reporter) {
+            reporter.error(&format!("Re-check failed: {e}"));
+        }
+        reporter.finish();
+    }
+
+    Ok(())
+}
+
+/// Adds every `.md` path touched by `event` that matches `config`'s
+/// `include`/`exclude` globs to `changed`.
+fn collect_md_paths(config: &FormatterConfig, event: &notify::Event, changed: &mut HashSet<String>) {
+    for path in &event.paths {
+        let path_str = path.to_string_lossy();
+        if !path_str.ends_with(".md") || !path.is_file() {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(&config.target_path)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned();
+        if config.file_config.path_included(&relative) {
+            changed.insert(path_str.into_owned());
+        }
+    }
 }
 
 fn main() -> Result<()> {
@@ -135,21 +583,107 @@ fn main() -> Result<()> {
                 .help("Check if files are formatted correctly without modifying them")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .help("Stay resident and re-format changed Markdown files as they're saved")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("diff")
+                .long("diff")
+                .help("With --check, show a colored diff of what would change instead of just pass/fail")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("print-width")
+                .long("print-width")
+                .value_name("N")
+                .help("Override the .heraldfmt.toml print_width"),
+        )
+        .arg(
+            Arg::new("prose-wrap")
+                .long("prose-wrap")
+                .value_name("always|never|preserve")
+                .help("Override the .heraldfmt.toml prose_wrap"),
+        )
         .arg(
             Arg::new("path")
                 .help("Path to check (defaults to current directory)")
                 .default_value("."),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("human|json")
+                .help("Output format: colored human-readable lines, or one JSON report on stdout")
+                .default_value("human"),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .value_name("N")
+                .help("Number of files to format concurrently")
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("staged")
+                .long("staged")
+                .help("Only check/format files staged in git (via `git diff --name-only --cached`), for use as a pre-commit hook")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
+    let output_format = OutputFormat::parse(matches.get_one::<String>("format").unwrap())?;
+    let target_path = matches.get_one::<String>("path").unwrap().to_string();
+    let (file_config, _config_dir) = FormatterFileConfig::load(std::path::Path::new(&target_path))
+        .context("Failed to load .heraldfmt.toml")?;
+
+    let print_width = match matches.get_one::<String>("print-width") {
+        Some(value) => value.parse().context("Invalid --print-width, expected a number")?,
+        None => file_config.print_width,
+    };
+    let prose_wrap = match matches.get_one::<String>("prose-wrap") {
+        Some(value) => ProseWrap::parse(value)?,
+        None => file_config.prose_wrap,
+    };
+    let jobs: usize = matches
+        .get_one::<String>("jobs")
+        .unwrap()
+        .parse()
+        .context("Invalid --jobs value, expected a positive integer")?;
+    let staged = matches.get_flag("staged");
+
     let config = FormatterConfig {
         verbose: matches.get_flag("verbose"),
         check_only: matches.get_flag("check"),
-        target_path: matches.get_one::<String>("path").unwrap().to_string(),
+        target_path,
+        watch: matches.get_flag("watch"),
+        diff: matches.get_flag("diff"),
+        print_width,
+        prose_wrap,
+        file_config,
+        jobs,
+    };
+
+    if config.watch {
+        return watch_markdown_files(&config, output_format);
+    }
+
+    let mut reporter = Reporter::new(output_format);
+    reporter.info(&format!("Formatting Markdown files in {}", config.target_path));
+    if config.verbose {
+        reporter.info("Finding Markdown files...");
+    }
+    let md_files = if staged {
+        discover_staged_md_files(&config)?
+    } else {
+        discover_md_files(&config)
     };
 
-    match format_markdown_files(&config)? {
-        true => Ok(()),
-        false => std::process::exit(1),
+    format_markdown_files(&config, &md_files, &mut reporter)?;
+    match reporter.finish() {
+        0 => Ok(()),
+        _ => std::process::exit(1),
     }
 }
@@ -0,0 +1,146 @@
+//! Shared `.heraldfmt.toml` configuration for the formatting/check tools
+//! (`format_md`, `check_json`), so prose width, line width, path globs,
+//! and the JSON registry location can be tuned per-repo without editing
+//! source - mirroring how Deno's `FmtOptionsConfig` and `just`'s settings
+//! decouple behavior from the binary.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::naming::glob_match;
+
+/// How prose should be wrapped, mirroring prettier's own `--prose-wrap`
+/// values exactly so the config field can be passed straight through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProseWrap {
+    Always,
+    Never,
+    Preserve,
+}
+
+impl ProseWrap {
+    /// Parses a `--prose-wrap` CLI value, accepting the same three
+    /// strings the config file and prettier itself use.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            "preserve" => Ok(Self::Preserve),
+            other => anyhow::bail!("Unknown prose-wrap '{other}', expected always|never|preserve"),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::Preserve => "preserve",
+        }
+    }
+}
+
+impl Default for ProseWrap {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
+fn default_print_width() -> u32 {
+    80
+}
+
+fn default_include() -> Vec<String> {
+    vec!["**/*".to_string()]
+}
+
+fn default_exclude() -> Vec<String> {
+    vec![
+        "node_modules/**".to_string(),
+        "**/node_modules/**".to_string(),
+        "target/**".to_string(),
+        "**/target/**".to_string(),
+    ]
+}
+
+/// `.heraldfmt.toml` contents, loaded from the project root by walking
+/// upward from wherever the tool was invoked, the same way `check_json`
+/// already walks up looking for the vector store registry. Any field the
+/// file doesn't set falls back to HARALD's hardcoded defaults, and a CLI
+/// flag wins over both.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct FormatterFileConfig {
+    pub print_width: u32,
+    pub prose_wrap: ProseWrap,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Path to the JSON vector store registry, relative to wherever
+    /// `.heraldfmt.toml` lives. `None` (the default, when unset) leaves
+    /// `check_json`'s own directory search in charge.
+    pub registry: Option<String>,
+}
+
+impl Default for FormatterFileConfig {
+    fn default() -> Self {
+        Self {
+            print_width: default_print_width(),
+            prose_wrap: ProseWrap::default(),
+            include: default_include(),
+            exclude: default_exclude(),
+            registry: None,
+        }
+    }
+}
+
+impl FormatterFileConfig {
+    const FILE_NAME: &'static str = ".heraldfmt.toml";
+
+    /// Loads `.heraldfmt.toml`, walking upward from `start` until one is
+    /// found, falling back to all-default values (anchored to `start`) if
+    /// none exists anywhere above it. The second return value is the
+    /// directory the config was loaded from (or `start` on the fallback
+    /// path), which path-valued fields like `registry` are relative to.
+    pub fn load(start: &Path) -> Result<(Self, PathBuf)> {
+        let mut dir = Some(start);
+        while let Some(d) = dir {
+            let path = d.join(Self::FILE_NAME);
+            if path.exists() {
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {:?}", path))?;
+                let config: Self = toml::from_str(&content)
+                    .with_context(|| format!("Invalid formatter config: {:?}", path))?;
+                return Ok((config, d.to_path_buf()));
+            }
+            dir = d.parent();
+        }
+        Ok((Self::default(), start.to_path_buf()))
+    }
+
+    /// Whether `path` (relative to the directory `include`/`exclude` are
+    /// anchored to) should be formatted: matches some `include` glob and
+    /// no `exclude` glob.
+    pub fn path_included(&self, path: &str) -> bool {
+        let included = self.include.iter().any(|g| glob_match(g, path));
+        included && !self.is_excluded(path)
+    }
+
+    /// Whether `path` matches some `exclude` glob, on its own - used to
+    /// prune whole directories (e.g. `node_modules`) out of a walk before
+    /// descending into them, rather than only filtering the files inside
+    /// afterward.
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.exclude.iter().any(|g| glob_match(g, path))
+    }
+
+    /// Whether the directory at relative path `path` should be pruned from
+    /// a walk: checked with a trailing separator appended, so a directory
+    /// exclude glob like `node_modules/**` matches the directory itself
+    /// and not just the files under it.
+    pub fn is_excluded_dir(&self, path: &str) -> bool {
+        if path.is_empty() {
+            return false;
+        }
+        self.is_excluded(&format!("{path}/"))
+    }
+}
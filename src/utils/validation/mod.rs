@@ -3,6 +3,9 @@
 //! This module provides various validation utilities.
 
 pub mod check_json;
+pub mod chunk_invariants;
 pub mod format_md;
+pub mod formatter_config;
 pub mod naming;
+pub mod report;
 pub mod validate_naming;
@@ -6,8 +6,141 @@
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{FnArg, ItemConst, ItemEnum, ItemFn, ItemStatic, ItemStruct, Local, Pat};
 use walkdir::WalkDir;
 
+/// The full case family `heck` exposes. Used both for HARALD's built-in
+/// per-file-type defaults and as the case a `.harald-naming.toml` rule
+/// can declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum NamingCase {
+    #[serde(rename = "kebab-case")]
+    KebabCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    #[serde(alias = "TitleCase", rename = "UpperCamelCase")]
+    UpperCamelCase,
+    #[serde(rename = "camelCase")]
+    LowerCamelCase,
+}
+
+impl NamingCase {
+    fn is_valid(self, s: &str) -> bool {
+        match self {
+            Self::KebabCase => is_valid_kebab_case(s),
+            Self::SnakeCase => is_valid_snake_case(s),
+            Self::ScreamingSnakeCase => is_valid_screaming_snake_case(s),
+            Self::UpperCamelCase => is_valid_title_case(s),
+            Self::LowerCamelCase => is_valid_lower_camel_case(s),
+        }
+    }
+
+    fn convert(self, s: &str) -> String {
+        match self {
+            Self::KebabCase => to_kebab_case(s),
+            Self::SnakeCase => to_snake_case(s),
+            Self::ScreamingSnakeCase => to_screaming_snake_case(s),
+            Self::UpperCamelCase => to_title_case(s),
+            Self::LowerCamelCase => to_lower_camel_case(s),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::KebabCase => "kebab-case",
+            Self::SnakeCase => "snake_case",
+            Self::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            Self::UpperCamelCase => "UpperCamelCase",
+            Self::LowerCamelCase => "camelCase",
+        }
+    }
+}
+
+/// One entry in a `.harald-naming.toml` rule file: paths matching
+/// `pattern` (relative to the directory the config was loaded from) are
+/// expected to use `case`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct NamingRule {
+    pub pattern: String,
+    pub case: NamingCase,
+}
+
+/// User-declared naming rules, loaded from an optional
+/// `.harald-naming.toml` at the root of the validated tree. Lets
+/// projects with mixed conventions override HARALD's built-in per-file-
+/// type defaults without forking the validator.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct NamingRuleConfig {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<NamingRule>,
+}
+
+impl NamingRuleConfig {
+    const FILE_NAME: &'static str = ".harald-naming.toml";
+
+    /// Loads `.harald-naming.toml` from `dir` if present, returning an
+    /// empty (no-rule) config otherwise so callers can always fall back
+    /// to the built-in defaults.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(Self::FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid naming rule config: {:?}", path))
+    }
+
+    /// Returns the case the first matching rule declares for `path`
+    /// (relative to the directory the config was loaded from), or `None`
+    /// if no rule matches.
+    fn case_for(&self, path: &Path) -> Option<NamingCase> {
+        let path_str = path.to_string_lossy();
+        self.rules
+            .iter()
+            .find(|rule| glob_match(&rule.pattern, &path_str))
+            .map(|rule| rule.case)
+    }
+}
+
+/// Minimal glob matcher: `*` matches any run of characters (including
+/// path separators); a pattern with no `*` matches as a path prefix.
+/// Shared with [`super::formatter_config`]'s `include`/`exclude` globs so
+/// both config files agree on what a glob means.
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+    if !pattern.contains('*') {
+        return path.starts_with(pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut rest = path;
+
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        match rest.find(part) {
+            Some(idx) => {
+                if i == 0 && idx != 0 {
+                    return false;
+                }
+                rest = &rest[idx + part.len()..];
+            }
+            None => return false,
+        }
+    }
+
+    parts
+        .last()
+        .map_or(true, |last| last.is_empty() || path.ends_with(last))
+}
+
 /// Types of naming validation issues
 #[derive(Debug, Clone)]
 pub enum IssueType {
@@ -15,6 +148,9 @@ pub enum IssueType {
     RustFileNaming,
     MarkdownFileNaming,
     JsonFileNaming,
+    /// An identifier declared *inside* a `.rs` file (not the file name
+    /// itself) uses the wrong case.
+    RustIdentifierNaming,
 }
 
 /// A naming convention issue found during validation
@@ -25,6 +161,20 @@ pub struct NamingIssue {
     pub current_name: String,
     pub suggested_name: String,
     pub description: String,
+    /// For `IssueType::RustIdentifierNaming` issues, the in-place rewrite
+    /// that fixes them. `None` for file/directory rename issues, which
+    /// are fixed by renaming `path` instead.
+    pub replacement: Option<Replacement>,
+}
+
+/// An in-file identifier rename: replace the byte range `span` of the
+/// declaring file's source with `suggested_text`.
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pub current_name: String,
+    pub suggested_text: String,
+    pub expected_case: NamingCase,
+    pub span: (usize, usize),
 }
 
 /// Configuration for naming validation
@@ -32,6 +182,9 @@ pub struct NamingIssue {
 pub struct ValidationConfig {
     pub target_path: PathBuf,
     pub fix_issues: bool,
+    /// When set alongside `fix_issues`, plans and reports fixes without
+    /// touching disk.
+    pub dry_run: bool,
     pub verbose: bool,
 }
 
@@ -40,6 +193,7 @@ impl Default for ValidationConfig {
         Self {
             target_path: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
             fix_issues: false,
+            dry_run: false,
             verbose: false,
         }
     }
@@ -51,6 +205,12 @@ pub struct ValidationResult {
     pub issues: Vec<NamingIssue>,
     pub fixed_count: usize,
     pub error_count: usize,
+    /// Human-readable descriptions of fixes that were skipped (target
+    /// collisions) or failed outright, in addition to `error_count`.
+    pub conflicts: Vec<String>,
+    /// `"old -> new"`-style descriptions of every fix, populated whether
+    /// or not the fixes were actually applied (see `ValidationConfig::dry_run`).
+    pub planned_fixes: Vec<String>,
 }
 
 /// Main validation function - validates all naming conventions
@@ -58,19 +218,63 @@ pub fn validate_naming_conventions(config: &ValidationConfig) -> Result<Validati
     let mut all_issues = Vec::new();
     let mut fixed_count = 0;
     let mut error_count = 0;
+    let mut conflicts = Vec::new();
+    let mut planned_fixes = Vec::new();
+
+    let rules = NamingRuleConfig::load(&config.target_path)?;
 
     // Collect all validation issues
-    all_issues.extend(validate_directory_names(config)?);
-    all_issues.extend(validate_rust_file_names(config)?);
-    all_issues.extend(validate_markdown_file_names(config)?);
-    all_issues.extend(validate_json_file_names(config)?);
+    all_issues.extend(validate_directory_names(config, &rules)?);
+    all_issues.extend(validate_rust_file_names(config, &rules)?);
+    all_issues.extend(validate_markdown_file_names(config, &rules)?);
+    all_issues.extend(validate_json_file_names(config, &rules)?);
+    all_issues.extend(validate_rust_identifier_names(config)?);
 
     // Apply fixes if requested
     if config.fix_issues {
-        for issue in &all_issues {
-            match apply_fix(issue) {
-                Ok(()) => fixed_count += 1,
-                Err(_) => error_count += 1,
+        let (renames, rename_conflicts) = plan_renames(&all_issues);
+        conflicts.extend(rename_conflicts);
+
+        for plan in &renames {
+            planned_fixes.push(format!("{} -> {}", plan.old.display(), plan.new.display()));
+        }
+        for issue in all_issues.iter().filter(|i| i.replacement.is_some()) {
+            if let Some(replacement) = &issue.replacement {
+                planned_fixes.push(format!(
+                    "{}: '{}' -> '{}'",
+                    issue.path.display(),
+                    replacement.current_name,
+                    replacement.suggested_text
+                ));
+            }
+        }
+
+        if !config.dry_run {
+            for plan in &renames {
+                match apply_rename(plan) {
+                    Ok(()) => {
+                        fixed_count += 1;
+                        match rewrite_references(&config.target_path, &plan.old, &plan.new) {
+                            Ok(_) => {}
+                            Err(e) => conflicts.push(e.to_string()),
+                        }
+                    }
+                    Err(e) => {
+                        error_count += 1;
+                        conflicts.push(e.to_string());
+                    }
+                }
+            }
+
+            for issue in all_issues.iter().filter(|i| i.replacement.is_some()) {
+                let replacement = issue.replacement.as_ref().expect("filtered by is_some");
+                match apply_identifier_fix(issue, replacement) {
+                    Ok(()) => fixed_count += 1,
+                    Err(e) => {
+                        error_count += 1;
+                        conflicts.push(e.to_string());
+                    }
+                }
             }
         }
     }
@@ -79,11 +283,275 @@ pub fn validate_naming_conventions(config: &ValidationConfig) -> Result<Validati
         issues: all_issues,
         fixed_count,
         error_count,
+        conflicts,
+        planned_fixes,
     })
 }
 
+/// A single planned file/directory rename, prior to being applied.
+#[derive(Debug, Clone)]
+struct RenamePlan {
+    old: PathBuf,
+    new: PathBuf,
+}
+
+/// Computes every file/directory rename implied by `issues` (those with
+/// no in-file `replacement` - identifier fixes never touch a path) and
+/// detects unsafe collisions before anything is written: two issues
+/// renaming to the same target, or a target that already exists on disk
+/// and isn't simply a case-only rename of the same path (those need a
+/// two-step rename on case-insensitive filesystems - see `apply_rename`).
+/// Colliding renames are dropped from the plan and reported instead.
+fn plan_renames(issues: &[NamingIssue]) -> (Vec<RenamePlan>, Vec<String>) {
+    let mut planned = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut claimed_targets: std::collections::HashMap<PathBuf, PathBuf> =
+        std::collections::HashMap::new();
+
+    for issue in issues {
+        if issue.replacement.is_some() {
+            continue;
+        }
+
+        let old = issue.path.clone();
+        let new = old
+            .with_file_name(&issue.suggested_name)
+            .with_extension(old.extension().unwrap_or_default());
+
+        if let Some(other_old) = claimed_targets.get(&new) {
+            conflicts.push(format!(
+                "Skipping rename {:?} -> {:?}: target collides with the rename of {:?}",
+                old, new, other_old
+            ));
+            continue;
+        }
+
+        let is_case_only_rename =
+            old != new && old.to_string_lossy().to_lowercase() == new.to_string_lossy().to_lowercase();
+
+        if !is_case_only_rename && new.exists() {
+            conflicts.push(format!(
+                "Skipping rename {:?} -> {:?}: target already exists",
+                old, new
+            ));
+            continue;
+        }
+
+        claimed_targets.insert(new.clone(), old.clone());
+        planned.push(RenamePlan { old, new });
+    }
+
+    (planned, conflicts)
+}
+
+/// Performs a single planned rename. Case-only renames (e.g.
+/// `foo.rs` -> `Foo.rs`) go through a temporary name first, since on a
+/// case-insensitive filesystem `old` and `new` refer to the same inode
+/// and a direct rename is a no-op rather than the change we want.
+fn apply_rename(plan: &RenamePlan) -> Result<()> {
+    let is_case_only_rename = plan.old != plan.new
+        && plan.old.to_string_lossy().to_lowercase() == plan.new.to_string_lossy().to_lowercase();
+
+    if is_case_only_rename {
+        let temp_name = format!(
+            ".{}.harald-rename-tmp",
+            plan.old.file_name().and_then(|n| n.to_str()).unwrap_or("tmp")
+        );
+        let temp = plan.old.with_file_name(temp_name);
+        std::fs::rename(&plan.old, &temp)
+            .with_context(|| format!("Failed to rename {:?} to {:?}", plan.old, temp))?;
+        std::fs::rename(&temp, &plan.new)
+            .with_context(|| format!("Failed to rename {:?} to {:?}", temp, plan.new))?;
+    } else {
+        std::fs::rename(&plan.old, &plan.new)
+            .with_context(|| format!("Failed to rename {:?} to {:?}", plan.old, plan.new))?;
+    }
+
+    Ok(())
+}
+
+/// After a rename succeeds, rewrites references to it across the scanned
+/// tree: relative Markdown links (`[text](old.md)`) and Rust `mod`/`use`
+/// paths naming the old file stem. Returns the number of files updated.
+fn rewrite_references(root: &Path, old: &Path, new: &Path) -> Result<usize> {
+    let old_ext = old.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let old_stem = old.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let new_stem = new.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let old_filename = old.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let new_filename = new.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+    if old_stem == new_stem {
+        return Ok(0);
+    }
+
+    let mut rewritten = 0;
+
+    for entry in WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+
+        let updated = if ext == "rs" && old_ext == "rs" {
+            rewrite_rust_references(&content, old_stem, new_stem)
+        } else if ext == "md" {
+            rewrite_markdown_links(&content, old_filename, new_filename)
+        } else {
+            None
+        };
+
+        if let Some(updated) = updated {
+            std::fs::write(path, updated)
+                .with_context(|| format!("Failed to update references in {:?}", path))?;
+            rewritten += 1;
+        }
+    }
+
+    Ok(rewritten)
+}
+
+/// Replaces whole-identifier occurrences of `old_stem` with `new_stem`,
+/// but only inside `mod old_stem;` declarations and `use` paths naming
+/// it - never inside string literals, comments, or unrelated
+/// identifiers (functions, variables, struct fields) that merely happen
+/// to share the name. Scoped line-by-line rather than clobbering every
+/// token in the file: a line (or a `use { ... };` block spanning
+/// several lines) only gets rewritten when it starts with `mod ` or
+/// `use ` (after stripping a `pub`/`pub(crate)`/`pub(super)` visibility
+/// prefix, if any).
+pub(crate) fn rewrite_rust_references(content: &str, old_stem: &str, new_stem: &str) -> Option<String> {
+    if !content.contains(old_stem) {
+        return None;
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut changed = false;
+    let mut in_use_stmt = false;
+
+    for line in content.split_inclusive('\n') {
+        let stripped = strip_visibility(line.trim_start());
+        let is_mod_decl = !in_use_stmt && stripped.starts_with("mod ") && line.trim_end().ends_with(';');
+        let is_use_start = !in_use_stmt && stripped.starts_with("use ");
+
+        if in_use_stmt || is_mod_decl || is_use_start {
+            let (rewritten, line_changed) = rewrite_identifier_occurrences(line, old_stem, new_stem);
+            result.push_str(&rewritten);
+            changed |= line_changed;
+
+            if is_use_start || in_use_stmt {
+                in_use_stmt = !line.trim_end().ends_with(';');
+            }
+        } else {
+            result.push_str(line);
+        }
+    }
+
+    changed.then_some(result)
+}
+
+/// Strips a leading `pub`, `pub(crate)`, `pub(super)`, or `pub(in ...)`
+/// visibility modifier (and the whitespace after it) from an item line,
+/// so callers can match on the item keyword that follows (`mod`/`use`)
+/// regardless of visibility.
+fn strip_visibility(trimmed_line: &str) -> &str {
+    let Some(rest) = trimmed_line.strip_prefix("pub") else {
+        return trimmed_line;
+    };
+    let rest = rest.trim_start();
+    if let Some(after_paren) = rest.strip_prefix('(') {
+        if let Some(close) = after_paren.find(')') {
+            return after_paren[close + 1..].trim_start();
+        }
+    }
+    rest
+}
+
+/// Replaces whole-identifier occurrences of `old_stem` with `new_stem`
+/// within `text`, without clobbering identifiers that merely contain it
+/// as a substring. Used by [`rewrite_rust_references`] on just the
+/// `mod`/`use` lines it's scoped to.
+fn rewrite_identifier_occurrences(text: &str, old_stem: &str, new_stem: &str) -> (String, bool) {
+    let chars: Vec<char> = text.chars().collect();
+    let old_chars: Vec<char> = old_stem.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    let mut changed = false;
+
+    while i < chars.len() {
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+        let matches_here = chars[i..].starts_with(old_chars.as_slice());
+        let before_ok = i == 0 || !is_ident_char(chars[i - 1]);
+        let after_idx = i + old_chars.len();
+        let after_ok = after_idx >= chars.len() || !is_ident_char(chars[after_idx]);
+
+        if matches_here && before_ok && after_ok {
+            result.push_str(new_stem);
+            i = after_idx;
+            changed = true;
+            continue;
+        }
+
+        result.push(chars[i]);
+        i += 1;
+    }
+
+    (result, changed)
+}
+
+/// Replaces the filename inside relative Markdown link targets
+/// (`[text](dir/old.md)` -> `[text](dir/new.md)`) when the link's final
+/// path segment matches `old_filename`, ignoring any `#fragment`.
+fn rewrite_markdown_links(content: &str, old_filename: &str, new_filename: &str) -> Option<String> {
+    if !content.contains(old_filename) {
+        return None;
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    let mut changed = false;
+
+    while let Some(start) = rest.find("](") {
+        let (before, after_marker) = rest.split_at(start);
+        result.push_str(before);
+        result.push_str("](");
+        let after = &after_marker[2..];
+
+        match after.find(')') {
+            Some(end) => {
+                let target = &after[..end];
+                let path_part = target.split(['#', '?']).next().unwrap_or(target);
+                if path_part.rsplit('/').next() == Some(old_filename) {
+                    result.push_str(&target.replace(old_filename, new_filename));
+                    changed = true;
+                } else {
+                    result.push_str(target);
+                }
+                result.push(')');
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(after);
+                rest = "";
+                break;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    changed.then_some(result)
+}
+
 /// Validate directory naming conventions
-pub fn validate_directory_names(config: &ValidationConfig) -> Result<Vec<NamingIssue>> {
+pub fn validate_directory_names(
+    config: &ValidationConfig,
+    rules: &NamingRuleConfig,
+) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
     let excluded = ["node_modules", "target", ".git", ".vscode", "build", "dist"];
 
@@ -104,14 +572,22 @@ pub fn validate_directory_names(config: &ValidationConfig) -> Result<Vec<NamingI
             continue;
         }
 
-        // Check for kebab-case compliance
-        if !is_valid_kebab_case(dir_name) && !is_special_directory(dir_name) {
+        let expected_case = relative_to(config, path)
+            .and_then(|rel| rules.case_for(&rel))
+            .unwrap_or(NamingCase::KebabCase);
+
+        if !expected_case.is_valid(dir_name) && !is_special_directory(dir_name) {
             issues.push(NamingIssue {
                 path: path.to_path_buf(),
                 issue_type: IssueType::DirectoryNaming,
                 current_name: dir_name.to_string(),
-                suggested_name: to_kebab_case(dir_name),
-                description: format!("Directory '{}' should use kebab-case", dir_name),
+                suggested_name: expected_case.convert(dir_name),
+                description: format!(
+                    "Directory '{}' should use {}",
+                    dir_name,
+                    expected_case.label()
+                ),
+                replacement: None,
             });
         }
     }
@@ -120,7 +596,10 @@ pub fn validate_directory_names(config: &ValidationConfig) -> Result<Vec<NamingI
 }
 
 /// Validate Rust file naming conventions
-pub fn validate_rust_file_names(config: &ValidationConfig) -> Result<Vec<NamingIssue>> {
+pub fn validate_rust_file_names(
+    config: &ValidationConfig,
+    rules: &NamingRuleConfig,
+) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
 
     for entry in WalkDir::new(&config.target_path)
@@ -139,13 +618,22 @@ pub fn validate_rust_file_names(config: &ValidationConfig) -> Result<Vec<NamingI
             continue;
         }
 
-        if !is_valid_snake_case(file_stem) {
+        let expected_case = relative_to(config, path)
+            .and_then(|rel| rules.case_for(&rel))
+            .unwrap_or(NamingCase::SnakeCase);
+
+        if !expected_case.is_valid(file_stem) {
             issues.push(NamingIssue {
                 path: path.to_path_buf(),
                 issue_type: IssueType::RustFileNaming,
                 current_name: file_stem.to_string(),
-                suggested_name: to_snake_case(file_stem),
-                description: format!("Rust file '{}' should use snake_case", file_stem),
+                suggested_name: expected_case.convert(file_stem),
+                description: format!(
+                    "Rust file '{}' should use {}",
+                    file_stem,
+                    expected_case.label()
+                ),
+                replacement: None,
             });
         }
     }
@@ -153,8 +641,11 @@ pub fn validate_rust_file_names(config: &ValidationConfig) -> Result<Vec<NamingI
     Ok(issues)
 }
 
-/// Validate Markdown file naming conventions  
-pub fn validate_markdown_file_names(config: &ValidationConfig) -> Result<Vec<NamingIssue>> {
+/// Validate Markdown file naming conventions
+pub fn validate_markdown_file_names(
+    config: &ValidationConfig,
+    rules: &NamingRuleConfig,
+) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
 
     for entry in WalkDir::new(&config.target_path)
@@ -170,34 +661,27 @@ pub fn validate_markdown_file_names(config: &ValidationConfig) -> Result<Vec<Nam
 
         // Check naming convention based on context
         let expected_case = if is_entity_file(path) {
-            "TitleCase"
+            NamingCase::UpperCamelCase
         } else if is_standard_doc(file_stem) {
-            "UPPERCASE"
+            NamingCase::ScreamingSnakeCase
         } else {
-            "kebab-case"
-        };
-
-        let is_valid = match expected_case {
-            "TitleCase" => is_valid_title_case(file_stem),
-            "UPPERCASE" => file_stem.chars().all(|c| c.is_uppercase() || c == '-' || c == '_'),
-            "kebab-case" => is_valid_kebab_case(file_stem),
-            _ => false,
+            relative_to(config, path)
+                .and_then(|rel| rules.case_for(&rel))
+                .unwrap_or(NamingCase::KebabCase)
         };
 
-        if !is_valid {
-            let suggested = match expected_case {
-                "TitleCase" => to_title_case(file_stem),
-                "UPPERCASE" => file_stem.to_uppercase(),
-                "kebab-case" => to_kebab_case(file_stem),
-                _ => file_stem.to_string(),
-            };
-
+        if !expected_case.is_valid(file_stem) {
             issues.push(NamingIssue {
                 path: path.to_path_buf(),
                 issue_type: IssueType::MarkdownFileNaming,
                 current_name: file_stem.to_string(),
-                suggested_name: suggested,
-                description: format!("Markdown file '{}' should use {}", file_stem, expected_case),
+                suggested_name: expected_case.convert(file_stem),
+                description: format!(
+                    "Markdown file '{}' should use {}",
+                    file_stem,
+                    expected_case.label()
+                ),
+                replacement: None,
             });
         }
     }
@@ -206,7 +690,10 @@ pub fn validate_markdown_file_names(config: &ValidationConfig) -> Result<Vec<Nam
 }
 
 /// Validate JSON file naming conventions
-pub fn validate_json_file_names(config: &ValidationConfig) -> Result<Vec<NamingIssue>> {
+pub fn validate_json_file_names(
+    config: &ValidationConfig,
+    rules: &NamingRuleConfig,
+) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
 
     for entry in WalkDir::new(&config.target_path)
@@ -221,34 +708,27 @@ pub fn validate_json_file_names(config: &ValidationConfig) -> Result<Vec<NamingI
             .unwrap_or("");
 
         let expected_case = if is_entity_or_archetype_file(path) {
-            "TitleCase"
+            NamingCase::UpperCamelCase
         } else if is_config_file(path) {
-            "kebab-case"
+            NamingCase::KebabCase
         } else {
-            "snake_case"
-        };
-
-        let is_valid = match expected_case {
-            "TitleCase" => is_valid_title_case(file_stem),
-            "kebab-case" => is_valid_kebab_case(file_stem),
-            "snake_case" => is_valid_snake_case(file_stem),
-            _ => false,
+            relative_to(config, path)
+                .and_then(|rel| rules.case_for(&rel))
+                .unwrap_or(NamingCase::SnakeCase)
         };
 
-        if !is_valid {
-            let suggested = match expected_case {
-                "TitleCase" => to_title_case(file_stem),
-                "kebab-case" => to_kebab_case(file_stem),
-                "snake_case" => to_snake_case(file_stem),
-                _ => file_stem.to_string(),
-            };
-
+        if !expected_case.is_valid(file_stem) {
             issues.push(NamingIssue {
                 path: path.to_path_buf(),
                 issue_type: IssueType::JsonFileNaming,
                 current_name: file_stem.to_string(),
-                suggested_name: suggested,
-                description: format!("JSON file '{}' should use {}", file_stem, expected_case),
+                suggested_name: expected_case.convert(file_stem),
+                description: format!(
+                    "JSON file '{}' should use {}",
+                    file_stem,
+                    expected_case.label()
+                ),
+                replacement: None,
             });
         }
     }
@@ -256,6 +736,148 @@ pub fn validate_json_file_names(config: &ValidationConfig) -> Result<Vec<NamingI
     Ok(issues)
 }
 
+/// Path of `path` relative to `config.target_path`, for matching against
+/// `.harald-naming.toml` rule patterns. Returns `None` if `path` isn't
+/// actually under the target (shouldn't happen given `WalkDir::new`).
+fn relative_to(config: &ValidationConfig, path: &Path) -> Option<PathBuf> {
+    path.strip_prefix(&config.target_path).ok().map(Path::to_path_buf)
+}
+
+/// Validates that identifiers *declared inside* `.rs` files - not just
+/// their file names - follow Rust's conventional casing, mirroring
+/// rust-analyzer's declaration checker: functions, arguments, and local
+/// bindings in `snake_case`; structs, enums, and enum variants in
+/// `UpperCamelCase`; consts and statics in `SCREAMING_SNAKE_CASE`.
+pub fn validate_rust_identifier_names(config: &ValidationConfig) -> Result<Vec<NamingIssue>> {
+    let mut issues = Vec::new();
+
+    for entry in WalkDir::new(&config.target_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().map_or(false, |ext| ext == "rs"))
+    {
+        let path = entry.path();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+
+        // A source file that doesn't parse (e.g. mid-edit) is skipped
+        // rather than failing the whole run; it's reported elsewhere.
+        let Ok(file) = syn::parse_file(&content) else {
+            continue;
+        };
+
+        let mut visitor = IdentifierVisitor {
+            path,
+            content: &content,
+            issues: Vec::new(),
+        };
+        visitor.visit_file(&file);
+        issues.extend(visitor.issues);
+    }
+
+    Ok(issues)
+}
+
+/// Walks a parsed `.rs` file's declarations, recording a [`NamingIssue`]
+/// for each identifier whose case doesn't match its construct's
+/// convention.
+struct IdentifierVisitor<'a> {
+    path: &'a Path,
+    content: &'a str,
+    issues: Vec<NamingIssue>,
+}
+
+impl IdentifierVisitor<'_> {
+    fn check(&mut self, ident: &syn::Ident, expected_case: NamingCase, kind: &str) {
+        let name = ident.to_string();
+        if expected_case.is_valid(&name) {
+            return;
+        }
+
+        let suggested = expected_case.convert(&name);
+        let start = ident.span().start();
+        let end = ident.span().end();
+        let span = (
+            line_col_to_byte_offset(self.content, start.line, start.column),
+            line_col_to_byte_offset(self.content, end.line, end.column),
+        );
+
+        self.issues.push(NamingIssue {
+            path: self.path.to_path_buf(),
+            issue_type: IssueType::RustIdentifierNaming,
+            current_name: name.clone(),
+            suggested_name: suggested.clone(),
+            description: format!("{} '{}' should use {}", kind, name, expected_case.label()),
+            replacement: Some(Replacement {
+                current_name: name,
+                suggested_text: suggested,
+                expected_case,
+                span,
+            }),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for IdentifierVisitor<'_> {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.check(&node.sig.ident, NamingCase::SnakeCase, "Function");
+        for arg in &node.sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                if let Pat::Ident(pat_ident) = pat_type.pat.as_ref() {
+                    self.check(&pat_ident.ident, NamingCase::SnakeCase, "Argument");
+                }
+            }
+        }
+        visit::visit_item_fn(self, node);
+    }
+
+    fn visit_local(&mut self, node: &'ast Local) {
+        if let Pat::Ident(pat_ident) = &node.pat {
+            self.check(&pat_ident.ident, NamingCase::SnakeCase, "Local binding");
+        }
+        visit::visit_local(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.check(&node.ident, NamingCase::UpperCamelCase, "Struct");
+        visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
+        self.check(&node.ident, NamingCase::UpperCamelCase, "Enum");
+        for variant in &node.variants {
+            self.check(&variant.ident, NamingCase::UpperCamelCase, "Enum variant");
+        }
+        visit::visit_item_enum(self, node);
+    }
+
+    fn visit_item_const(&mut self, node: &'ast ItemConst) {
+        self.check(&node.ident, NamingCase::ScreamingSnakeCase, "Const");
+        visit::visit_item_const(self, node);
+    }
+
+    fn visit_item_static(&mut self, node: &'ast ItemStatic) {
+        self.check(&node.ident, NamingCase::ScreamingSnakeCase, "Static");
+        visit::visit_item_static(self, node);
+    }
+}
+
+/// Converts a `proc_macro2::LineColumn` (1-indexed line, 0-indexed char
+/// column) into a byte offset into `content`, so an identifier's span can
+/// be used to slice-and-splice the source directly.
+fn line_col_to_byte_offset(content: &str, line: usize, column: usize) -> usize {
+    let mut offset = 0;
+    for (i, text_line) in content.split('\n').enumerate() {
+        if i + 1 == line {
+            let col_offset: usize = text_line.chars().take(column).map(char::len_utf8).sum();
+            return offset + col_offset;
+        }
+        offset += text_line.len() + 1;
+    }
+    offset
+}
+
 // Helper functions for case validation and conversion
 
 fn is_valid_kebab_case(s: &str) -> bool {
@@ -267,51 +889,108 @@ fn is_valid_snake_case(s: &str) -> bool {
 }
 
 fn is_valid_title_case(s: &str) -> bool {
-    !s.is_empty() && s.chars().next().unwrap().is_uppercase() 
+    !s.is_empty() && s.chars().next().unwrap().is_uppercase()
         && s.chars().skip(1).all(|c| c.is_alphanumeric())
 }
 
+fn is_valid_screaming_snake_case(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_uppercase() || c.is_numeric() || c == '_')
+}
+
+fn is_valid_lower_camel_case(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars().next().unwrap().is_lowercase()
+        && s.chars().all(|c| c.is_alphanumeric())
+}
+
+/// Splits an identifier into its constituent words, the way the `heck`
+/// crate does: a boundary is inserted on a lowercase→uppercase
+/// transition (`fooBar` -> `foo`,`Bar`), before the last letter of a
+/// trailing acronym run that's followed by a lowercase letter
+/// (`HTTPServer` -> `HTTP`,`Server`), at any non-alphanumeric separator,
+/// and between a letter and an adjacent digit group.
+fn tokenize(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(prev) = current.chars().last() {
+            let acronym_end = prev.is_uppercase()
+                && c.is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+            let boundary = (prev.is_lowercase() && c.is_uppercase())
+                || acronym_end
+                || (prev.is_alphabetic() && c.is_numeric())
+                || (prev.is_numeric() && c.is_alphabetic());
+
+            if boundary {
+                tokens.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Capitalizes a token's first letter and lowercases the rest, e.g.
+/// `"HTTP"` -> `"Http"`.
+fn capitalize(token: &str) -> String {
+    let mut chars = token.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
+
 fn to_kebab_case(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
+    tokenize(s)
+        .iter()
+        .map(|t| t.to_lowercase())
         .collect::<Vec<_>>()
         .join("-")
 }
 
 fn to_snake_case(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '_' })
-        .collect::<String>()
-        .split('_')
-        .filter(|s| !s.is_empty())
+    tokenize(s)
+        .iter()
+        .map(|t| t.to_lowercase())
         .collect::<Vec<_>>()
         .join("_")
 }
 
 fn to_title_case(s: &str) -> String {
-    let mut result = String::new();
-    let mut capitalize_next = true;
-    
-    for c in s.chars() {
-        if c.is_alphabetic() {
-            if capitalize_next {
-                result.push(c.to_uppercase().next().unwrap());
-                capitalize_next = false;
-            } else {
-                result.push(c.to_lowercase().next().unwrap());
-            }
-        } else if c.is_numeric() {
-            result.push(c);
-        }
-        // Skip non-alphanumeric characters
-    }
-    
-    result
+    tokenize(s).iter().map(|t| capitalize(t)).collect()
+}
+
+fn to_screaming_snake_case(s: &str) -> String {
+    tokenize(s)
+        .iter()
+        .map(|t| t.to_uppercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn to_lower_camel_case(s: &str) -> String {
+    tokenize(s)
+        .iter()
+        .enumerate()
+        .map(|(i, t)| if i == 0 { t.to_lowercase() } else { capitalize(t) })
+        .collect()
 }
 
 fn is_special_directory(name: &str) -> bool {
@@ -340,14 +1019,30 @@ fn is_config_file(path: &Path) -> bool {
         .any(|p| p.file_name().map_or(false, |n| n == "config"))
 }
 
-fn apply_fix(issue: &NamingIssue) -> Result<()> {
-    let old_path = &issue.path;
-    let new_path = old_path.with_file_name(&issue.suggested_name)
-        .with_extension(old_path.extension().unwrap_or_default());
-    
-    std::fs::rename(old_path, &new_path)
-        .with_context(|| format!("Failed to rename {:?} to {:?}", old_path, new_path))?;
-    
+/// Rewrites an identifier at its declaration span in place, rather than
+/// renaming the file it lives in.
+fn apply_identifier_fix(issue: &NamingIssue, replacement: &Replacement) -> Result<()> {
+    let content = std::fs::read_to_string(&issue.path)
+        .with_context(|| format!("Failed to read {:?}", issue.path))?;
+
+    let (start, end) = replacement.span;
+    if start > end || end > content.len() {
+        anyhow::bail!(
+            "Stale span {:?} for identifier '{}' in {:?}",
+            replacement.span,
+            replacement.current_name,
+            issue.path
+        );
+    }
+
+    let mut updated = String::with_capacity(content.len());
+    updated.push_str(&content[..start]);
+    updated.push_str(&replacement.suggested_text);
+    updated.push_str(&content[end..]);
+
+    std::fs::write(&issue.path, updated)
+        .with_context(|| format!("Failed to write {:?}", issue.path))?;
+
     Ok(())
 }
 
@@ -373,4 +1068,51 @@ mod tests {
         assert_eq!(to_snake_case("HelloWorld"), "hello_world");
         assert_eq!(to_title_case("hello-world"), "HelloWorld");
     }
+
+    #[test]
+    fn test_case_conversion_acronyms_and_digits() {
+        assert_eq!(to_kebab_case("HTTPServer"), "http-server");
+        assert_eq!(to_snake_case("AIEntityLoader"), "ai_entity_loader");
+        assert_eq!(to_title_case("harald_phi4"), "HaraldPhi4");
+    }
+
+    #[test]
+    fn test_rewrite_rust_references_updates_mod_and_use() {
+        let content = "mod old_mod;\nuse old_mod::Thing;\n";
+        let updated = rewrite_rust_references(content, "old_mod", "new_mod").unwrap();
+        assert_eq!(updated, "mod new_mod;\nuse new_mod::Thing;\n");
+    }
+
+    #[test]
+    fn test_rewrite_rust_references_updates_multiline_use_group() {
+        let content = "use old_mod::{Thing, OtherThing};\n";
+        let updated = rewrite_rust_references(content, "old_mod", "new_mod").unwrap();
+        assert_eq!(updated, "use new_mod::{Thing, OtherThing};\n");
+    }
+
+    #[test]
+    fn test_rewrite_rust_references_updates_visibility_qualified_items() {
+        let content = "pub mod old_mod;\npub(crate) use old_mod::Thing;\n";
+        let updated = rewrite_rust_references(content, "old_mod", "new_mod").unwrap();
+        assert_eq!(updated, "pub mod new_mod;\npub(crate) use new_mod::Thing;\n");
+    }
+
+    #[test]
+    fn test_rewrite_rust_references_ignores_unrelated_identifiers() {
+        // `old_mod` here names a local variable, a string literal, and a
+        // comment - none of which are `mod`/`use` references and must be
+        // left untouched.
+        let content = "fn old_mod() {\n    let old_mod = 1;\n    // old_mod is not a module\n    println!(\"old_mod\");\n}\n";
+        assert!(rewrite_rust_references(content, "old_mod", "new_mod").is_none());
+    }
+
+    #[test]
+    fn test_rewrite_rust_references_only_touches_mod_use_lines_in_mixed_file() {
+        let content = "use old_mod::Thing;\n\nfn old_mod() {\n    old_mod::helper();\n}\n";
+        let updated = rewrite_rust_references(content, "old_mod", "new_mod").unwrap();
+        assert_eq!(
+            updated,
+            "use new_mod::Thing;\n\nfn old_mod() {\n    old_mod::helper();\n}\n"
+        );
+    }
 }
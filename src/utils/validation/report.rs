@@ -0,0 +1,179 @@
+//! Machine-readable reporting shared by the `format_md` and `check_json`
+//! CLIs, so CI pipelines can ask for `--format=json` and parse one report
+//! instead of scraping colored `[INFO]`/`[SUCCESS]`/`[ERROR]` lines -
+//! mirroring rustc's `--error-format=json`.
+
+use colored::*;
+use serde::Serialize;
+
+/// Whether a tool's output goes to a human (colored lines, as before) or
+/// to a single JSON report on stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    /// Parses a `--format` CLI value.
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!("Unknown format '{other}', expected human|json"),
+        }
+    }
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+/// Outcome of checking/formatting a single unit of work (usually a file,
+/// though `check_json` - which delegates per-file checking to
+/// `format_json` and only sees an overall pass/fail - reports per stage
+/// instead; see its own `run_checks`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileStatus {
+    Ok,
+    Reformatted,
+    NeedsFormat,
+    Error,
+}
+
+/// One result, as it appears in a JSON report's `results` array.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileResult {
+    pub path: String,
+    pub status: FileStatus,
+    pub message: String,
+}
+
+/// Counts over a report's `results`, plus the process exit code the
+/// caller should use.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSummary {
+    pub total: usize,
+    pub ok: usize,
+    pub reformatted: usize,
+    pub needs_format: usize,
+    pub errors: usize,
+    pub exit_code: i32,
+}
+
+/// A full JSON report: every recorded result plus the summary.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub results: Vec<FileResult>,
+    pub summary: ReportSummary,
+}
+
+/// Routes every `[INFO]`/`[SUCCESS]`/`[WARNING]`/`[ERROR]` line either
+/// straight to the terminal, as before, or nowhere - accumulating
+/// [`FileResult`]s instead, printed as one [`Report`] by [`Reporter::finish`]
+/// so a `--format=json` caller gets a single parseable value instead of
+/// colored lines mixed with whatever it's trying to scrape.
+#[derive(Debug, Default)]
+pub struct Reporter {
+    json: bool,
+    results: Vec<FileResult>,
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            json: format == OutputFormat::Json,
+            results: Vec::new(),
+        }
+    }
+
+    pub fn info(&self, message: &str) {
+        if !self.json {
+            println!("{} {}", "[INFO]".blue().bold(), message);
+        }
+    }
+
+    pub fn success(&self, message: &str) {
+        if !self.json {
+            println!("{} {}", "[SUCCESS]".green().bold(), message);
+        }
+    }
+
+    pub fn warning(&self, message: &str) {
+        if !self.json {
+            println!("{} {}", "[WARNING]".yellow().bold(), message);
+        }
+    }
+
+    pub fn error(&self, message: &str) {
+        if !self.json {
+            eprintln!("{} {}", "[ERROR]".red().bold(), message);
+        }
+    }
+
+    /// Prints `text` verbatim (no `[LEVEL]` prefix) unless in JSON mode -
+    /// for detail too free-form to fit a log line, like a rendered diff or
+    /// raw prettier stderr.
+    pub fn raw(&self, text: &str) {
+        if !self.json {
+            print!("{text}");
+        }
+    }
+
+    /// Records one result for the eventual JSON report. Has no effect on
+    /// human output, which logs its own per-file lines through
+    /// [`Reporter::warning`]/[`Reporter::error`] at the call site instead.
+    pub fn record(
+        &mut self,
+        path: impl Into<String>,
+        status: FileStatus,
+        message: impl Into<String>,
+    ) {
+        self.results.push(FileResult {
+            path: path.into(),
+            status,
+            message: message.into(),
+        });
+    }
+
+    /// Prints the accumulated [`Report`] to stdout (`--format=json` only)
+    /// and returns the process exit code: 0 if every recorded result is
+    /// `Ok`/`Reformatted`, 1 if anything is `NeedsFormat`/`Error`.
+    pub fn finish(&self) -> i32 {
+        let mut summary = ReportSummary {
+            total: self.results.len(),
+            ok: 0,
+            reformatted: 0,
+            needs_format: 0,
+            errors: 0,
+            exit_code: 0,
+        };
+        for result in &self.results {
+            match result.status {
+                FileStatus::Ok => summary.ok += 1,
+                FileStatus::Reformatted => summary.reformatted += 1,
+                FileStatus::NeedsFormat => summary.needs_format += 1,
+                FileStatus::Error => summary.errors += 1,
+            }
+        }
+        if summary.needs_format > 0 || summary.errors > 0 {
+            summary.exit_code = 1;
+        }
+
+        if self.json {
+            let report = Report {
+                results: self.results.clone(),
+                summary: summary.clone(),
+            };
+            match serde_json::to_string_pretty(&report) {
+                Ok(text) => println!("{text}"),
+                Err(e) => eprintln!("Failed to serialize report: {e}"),
+            }
+        }
+
+        summary.exit_code
+    }
+}
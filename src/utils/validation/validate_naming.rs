@@ -1,26 +1,237 @@
 use anyhow::{Context, Result};
 use clap::{Arg, ArgAction, Command};
 use colored::*;
+use heck::{ToKebabCase, ToSnakeCase, ToUpperCamelCase};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+use super::naming::glob_match;
+
+/// Case convention a file or directory name should follow. Centralizes
+/// what each validator used to reinvent with a `.chars().enumerate()`
+/// loop, which mishandled acronym runs and digit transitions (`HTTPServer`
+/// became `h-t-t-p-server` instead of `http-server`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaseStyle {
+    KebabCase,
+    SnakeCase,
+    TitleCase,
+    Lowercase,
+}
+
+/// Converts `name` to `style` using `heck`'s word-boundary-aware
+/// splitting, so acronyms and digit groups land on the right side of a
+/// word boundary instead of being split mid-run.
+fn suggest(name: &str, style: CaseStyle) -> String {
+    match style {
+        CaseStyle::KebabCase => name.to_kebab_case(),
+        CaseStyle::SnakeCase => name.to_snake_case(),
+        CaseStyle::TitleCase => name.to_upper_camel_case(),
+        CaseStyle::Lowercase => name.to_lowercase(),
+    }
+}
+
+/// True if `name` already conforms to `style`, i.e. converting it would be
+/// a no-op.
+fn matches(name: &str, style: CaseStyle) -> bool {
+    suggest(name, style) == name
+}
+
+/// Like `matches`, but ignores leading/trailing underscores (e.g.
+/// `_private` shouldn't fail `KebabCase` just for the underscore), as
+/// used when checking one `.`-separated part of a compound filename.
+fn part_matches(part: &str, style: CaseStyle) -> bool {
+    let trimmed = part.trim_matches('_');
+    trimmed.is_empty() || matches(trimmed, style)
+}
+
+/// Like `suggest`, but preserves a part's leading/trailing underscores
+/// around the restyled core, so fixing `_Foo` yields `_foo` rather than
+/// losing the underscore.
+fn restyle_part(part: &str, style: CaseStyle) -> String {
+    let core = part.trim_matches('_');
+    let leading = &part[..part.len() - part.trim_start_matches('_').len()];
+    let trailing = &part[part.trim_end_matches('_').len()..];
+    format!("{leading}{}{trailing}", suggest(core, style))
+}
+
+impl CaseStyle {
+    fn label(self) -> &'static str {
+        match self {
+            Self::KebabCase => "kebab-case",
+            Self::SnakeCase => "snake_case",
+            Self::TitleCase => "TitleCase",
+            Self::Lowercase => "lowercase",
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CaseStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "kebab-case" => Ok(Self::KebabCase),
+            "snake_case" => Ok(Self::SnakeCase),
+            "TitleCase" => Ok(Self::TitleCase),
+            "lowercase" => Ok(Self::Lowercase),
+            other => Err(serde::de::Error::custom(format!("unknown case style '{}'", other))),
+        }
+    }
+}
+
+/// How blocking a naming violation is. Lets a `naming-rules.toml` rule
+/// declare that a convention is advisory (`warning`/`weak-warning`)
+/// without the process exiting non-zero, while the default stays
+/// `error` to preserve this tool's historical all-issues-fail behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Severity {
+    #[default]
+    Error,
+    Warning,
+    WeakWarning,
+}
+
+/// Output mode for `--format`: colored `[LEVEL]` lines for a human, or one
+/// JSON object per issue (mirroring rustc's `--error-format=json`) for a
+/// linter/editor to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!("Unknown format '{other}', expected text|json"),
+        }
+    }
+}
+
+/// One entry in `naming-rules.toml`: files with the given `extension`
+/// whose relative path matches `path_glob` (see
+/// [`super::naming::glob_match`]) are expected to use `case`. Rules are
+/// checked in file order, first match wins, which lets a project-specific
+/// `naming-rules.toml` list a narrower override before HARALD's built-in
+/// defaults (see `default_rules`).
+#[derive(Debug, Clone, serde::Deserialize)]
+struct NamingRule {
+    path_glob: String,
+    extension: String,
+    case: CaseStyle,
+    #[serde(default)]
+    severity: Severity,
+}
+
+/// Contents of an optional `naming-rules.toml` at the root of the
+/// validated tree: project-specific rules, checked before the built-in
+/// defaults, plus glob patterns (matched the same way as `path_glob`)
+/// that exempt a path from every naming check.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct NamingRulesFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<NamingRule>,
+    #[serde(default)]
+    ignore_names: Vec<String>,
+}
+
+impl NamingRulesFile {
+    const FILE_NAME: &'static str = "naming-rules.toml";
+
+    /// Loads `naming-rules.toml` from `dir` if present, returning an empty
+    /// config otherwise so callers can always fall back to the built-in
+    /// defaults.
+    fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(Self::FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid naming rules config: {:?}", path))
+    }
+}
+
+/// HARALD's built-in naming conventions, expressed as rules so
+/// `validate_against_rules` doesn't need any type-specific logic of its
+/// own. Appended after any `naming-rules.toml` rules, so a project config
+/// can override a narrower path before falling back to these.
+fn default_rules() -> Vec<NamingRule> {
+    let rule = |path_glob: &str, extension: &str, case: CaseStyle| NamingRule {
+        path_glob: path_glob.into(),
+        extension: extension.into(),
+        case,
+        severity: Severity::Error,
+    };
+    vec![
+        rule("*/ai-entities/*", "md", CaseStyle::Lowercase),
+        rule("*", "md", CaseStyle::KebabCase),
+        rule("*personality-archetypes*", "json", CaseStyle::TitleCase),
+        rule("*Registry*", "json", CaseStyle::TitleCase),
+        rule("*/data/schemas/*", "json", CaseStyle::KebabCase),
+        rule("*/data/vector*", "json", CaseStyle::KebabCase),
+        rule("*-config.json", "json", CaseStyle::KebabCase),
+        rule("*/memory-schemas/*", "json", CaseStyle::KebabCase),
+        rule("*", "rs", CaseStyle::SnakeCase),
+    ]
+}
+
 /// Configuration for validation
 #[derive(Debug)]
 struct ValidatorConfig {
     fix_mode: bool,
     verbose: bool,
     target_path: PathBuf,
+    /// Project rules (from `naming-rules.toml`, if present) followed by
+    /// `default_rules()`.
+    rules: Vec<NamingRule>,
+    /// Glob patterns (relative to `target_path`) that exempt a matching
+    /// path from every naming check.
+    ignore: Vec<String>,
+    format: OutputFormat,
 }
 
 /// Validation issue type
 #[derive(Debug)]
 struct NamingIssue {
     path: PathBuf,
-    issue_type: String,
+    /// Stable identifier for the kind of violation (e.g.
+    /// `"file-name-case"`), so a linter/editor can key off it instead of
+    /// parsing `message`.
+    code: &'static str,
+    severity: Severity,
+    message: String,
     suggested_fix: String,
 }
 
+/// `NamingIssue` as it appears in `--format json`: one object per issue,
+/// printed on its own line, mirroring rustc's `--error-format=json`.
+#[derive(Debug, serde::Serialize)]
+struct IssueRecord<'a> {
+    path: String,
+    code: &'static str,
+    severity: Severity,
+    message: &'a str,
+    suggested_fix: &'a str,
+}
+
+impl NamingIssue {
+    fn to_record(&self) -> IssueRecord<'_> {
+        IssueRecord {
+            path: self.path.display().to_string(),
+            code: self.code,
+            severity: self.severity,
+            message: &self.message,
+            suggested_fix: &self.suggested_fix,
+        }
+    }
+}
+
 /// Log utilities with colored output
 fn log_info(message: &str) {
     println!("{} {}", "[INFO]".blue().bold(), message);
@@ -41,8 +252,9 @@ fn log_error(message: &str) {
 /// Directory name validation
 fn validate_directory_names(config: &ValidatorConfig) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
+    let text = config.format == OutputFormat::Text;
 
-    if config.verbose {
+    if config.verbose && text {
         log_info(&format!(
             "Validating directory names in {:?}",
             config.target_path
@@ -68,286 +280,171 @@ fn validate_directory_names(config: &ValidatorConfig) -> Result<Vec<NamingIssue>
             continue;
         }
 
-        let dirname = entry.file_name().to_string_lossy();
-
-        // Check for snake_case instead of kebab-case
-        if dirname.contains('_') {
-            let new_name = dirname.replace('_', "-");
-            issues.push(NamingIssue {
-                path: entry.path().to_path_buf(),
-                issue_type: "Directory uses snake_case instead of kebab-case".to_string(),
-                suggested_fix: new_name,
-            });
+        let rel = entry.path().strip_prefix(&config.target_path).unwrap_or(entry.path());
+        if config.ignore.iter().any(|pattern| glob_match(pattern, &rel.to_string_lossy())) {
+            continue;
         }
 
-        // Check for PascalCase (except in ai-entities)
-        if dirname.chars().any(char::is_uppercase)
-            && !entry.path().to_string_lossy().contains("/ai-entities")
+        let dirname = entry.file_name().to_string_lossy();
+
+        // ai-entities is exempt from kebab-case (its own TitleCase
+        // convention is covered by the JSON rules in `default_rules`).
+        if !entry.path().to_string_lossy().contains("/ai-entities")
+            && !matches(&dirname, CaseStyle::KebabCase)
         {
-            let new_name = dirname
-                .chars()
-                .enumerate()
-                .map(|(i, c)| {
-                    if i > 0 && c.is_uppercase() {
-                        format!("-{}", c.to_lowercase())
-                    } else {
-                        c.to_lowercase().to_string()
-                    }
-                })
-                .collect::<String>();
             issues.push(NamingIssue {
                 path: entry.path().to_path_buf(),
-                issue_type: "Directory uses PascalCase instead of kebab-case".to_string(),
-                suggested_fix: new_name.trim_start_matches('-').to_string(),
+                code: "directory-name-case",
+                severity: Severity::Error,
+                message: "Directory name should use kebab-case".to_string(),
+                suggested_fix: suggest(&dirname, CaseStyle::KebabCase),
             });
         }
     }
 
-    if issues.is_empty() {
-        if config.verbose {
-            log_success("All directory names follow conventions");
+    if text {
+        if issues.is_empty() {
+            if config.verbose {
+                log_success("All directory names follow conventions");
+            }
+        } else {
+            log_warning(&format!("Found {} directory naming issues", issues.len()));
         }
-    } else {
-        log_warning(&format!("Found {} directory naming issues", issues.len()));
     }
 
     Ok(issues)
 }
 
-/// Rust file name validation
-fn validate_rust_file_names(config: &ValidatorConfig) -> Result<Vec<NamingIssue>> {
+/// Validates every file against `config.rules` (project rules from
+/// `naming-rules.toml`, then `default_rules()`), first match by extension
+/// and `path_glob` wins. Replaces the old one-function-per-file-type
+/// validators, so a team can retarget or narrow a convention by editing
+/// `naming-rules.toml` instead of this file.
+fn validate_against_rules(config: &ValidatorConfig) -> Result<Vec<NamingIssue>> {
     let mut issues = Vec::new();
+    let text = config.format == OutputFormat::Text;
 
-    if config.verbose {
-        log_info(&format!(
-            "Validating Rust file names in {:?}",
-            config.target_path
-        ));
+    if config.verbose && text {
+        log_info(&format!("Validating file names in {:?}", config.target_path));
     }
 
     for entry in WalkDir::new(&config.target_path)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
     {
-        if !entry.file_type().is_file() || !entry.path().to_string_lossy().ends_with(".rs") {
-            continue;
-        }
-
-        let filename = entry
-            .path()
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy();
+        let path = entry.path();
+        let rel = path.strip_prefix(&config.target_path).unwrap_or(path).to_string_lossy().to_string();
 
-        // Skip special files
-        if filename == "main" || filename == "lib" {
+        if config.ignore.iter().any(|pattern| glob_match(pattern, &rel)) {
             continue;
         }
 
-        // Check for kebab-case instead of snake_case
-        if filename.contains('-') {
-            let new_name = filename.replace('-', "_");
-            issues.push(NamingIssue {
-                path: entry.path().to_path_buf(),
-                issue_type: "Rust file uses kebab-case instead of snake_case".to_string(),
-                suggested_fix: format!("{}.rs", new_name),
-            });
-        }
-
-        // Check for PascalCase/camelCase
-        if filename.chars().any(char::is_uppercase) {
-            let new_name = filename
-                .chars()
-                .enumerate()
-                .map(|(i, c)| {
-                    if i > 0 && c.is_uppercase() {
-                        format!("_{}", c.to_lowercase())
-                    } else {
-                        c.to_lowercase().to_string()
-                    }
-                })
-                .collect::<String>();
-            issues.push(NamingIssue {
-                path: entry.path().to_path_buf(),
-                issue_type: "Rust file uses PascalCase/camelCase instead of snake_case".to_string(),
-                suggested_fix: format!("{}.rs", new_name.trim_start_matches('_')),
-            });
-        }
-    }
-
-    if issues.is_empty() {
-        if config.verbose {
-            log_success("All Rust file names follow conventions");
-        }
-    } else {
-        log_warning(&format!("Found {} Rust naming issues", issues.len()));
-    }
-
-    Ok(issues)
-}
-
-/// Markdown file name validation
-fn validate_markdown_file_names(config: &ValidatorConfig) -> Result<Vec<NamingIssue>> {
-    let mut issues = Vec::new();
-
-    if config.verbose {
-        log_info(&format!(
-            "Validating Markdown file names in {:?}",
-            config.target_path
-        ));
-    }
-
-    for entry in WalkDir::new(&config.target_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if !entry.file_type().is_file() || !entry.path().to_string_lossy().ends_with(".md") {
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
             continue;
-        }
-
-        let filename = entry
-            .path()
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy();
+        };
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
 
-        // Skip special files (all uppercase)
-        if filename.chars().all(|c| c.is_uppercase() || c == '_') {
+        // Special files every convention exempts, regardless of rule.
+        if ext == "rs" && (stem == "main" || stem == "lib") {
             continue;
         }
-
-        // Handle entity files differently
-        if entry.path().to_string_lossy().contains("/ai-entities/")
-            && !filename.contains('-')
-            && !filename.contains('_')
-        {
-            // Entity files should be lowercase
-            if filename.chars().any(char::is_uppercase) {
-                issues.push(NamingIssue {
-                    path: entry.path().to_path_buf(),
-                    issue_type: "Entity markdown file should use lowercase".to_string(),
-                    suggested_fix: format!("{}.md", filename.to_lowercase()),
-                });
-            }
-        } else {
-            // Regular documentation should use kebab-case
-            if filename.contains('_') {
-                let new_name = filename.replace('_', "-");
-                issues.push(NamingIssue {
-                    path: entry.path().to_path_buf(),
-                    issue_type: "Markdown file uses snake_case instead of kebab-case".to_string(),
-                    suggested_fix: format!("{}.md", new_name),
-                });
-            }
-        }
-    }
-
-    if issues.is_empty() {
-        if config.verbose {
-            log_success("All Markdown file names follow conventions");
+        if ext == "md" && stem.chars().all(|c| c.is_uppercase() || c == '_') {
+            continue;
         }
-    } else {
-        log_warning(&format!("Found {} Markdown naming issues", issues.len()));
-    }
-
-    Ok(issues)
-}
-
-/// JSON file name validation
-fn validate_json_file_names(config: &ValidatorConfig) -> Result<Vec<NamingIssue>> {
-    let mut issues = Vec::new();
-
-    if config.verbose {
-        log_info(&format!(
-            "Validating JSON file names in {:?}",
-            config.target_path
-        ));
-    }
-
-    for entry in WalkDir::new(&config.target_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if !entry.file_type().is_file() || !entry.path().to_string_lossy().ends_with(".json") {
+        if ext == "json" && stem.starts_with('.') {
             continue;
         }
 
-        let filename = entry
-            .path()
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy();
-
-        // Skip dot files
-        if filename.starts_with('.') {
+        let Some(rule) = config.rules.iter().find(|r| r.extension == ext && glob_match(&r.path_glob, &rel)) else {
             continue;
-        }
+        };
+
+        // A compound filename like `some-config.schema.json` or
+        // `Foo.test.rs` has sub-extension parts between the base name and
+        // the real extension; each is checked independently so a typo in
+        // one part doesn't also misreport the others.
+        let parts: Vec<&str> = stem.split('.').collect();
+        let bad_parts: Vec<usize> = parts
+            .iter()
+            .enumerate()
+            .filter(|(_, part)| !part_matches(part, rule.case))
+            .map(|(i, _)| i)
+            .collect();
+
+        if !bad_parts.is_empty() {
+            let suggested_fix = format!(
+                "{}.{}",
+                parts
+                    .iter()
+                    .map(|part| restyle_part(part, rule.case))
+                    .collect::<Vec<_>>()
+                    .join("."),
+                ext
+            );
 
-        let path_str = entry.path().to_string_lossy();
-
-        // Entity and personality files should use TitleCase
-        if path_str.contains("/personality-archetypes/") || filename.contains("Registry") {
-            if !filename
-                .chars()
-                .next()
-                .map(|c| c.is_uppercase())
-                .unwrap_or(false)
-            {
-                let new_name = filename
-                    .chars()
-                    .enumerate()
-                    .map(|(i, c)| {
-                        if i == 0 {
-                            c.to_uppercase().next().unwrap_or(c)
-                        } else {
-                            c
-                        }
-                    })
-                    .collect::<String>();
+            for &i in &bad_parts {
+                let message = if parts.len() == 1 {
+                    format!("'.{}' file should use {}", ext, rule.case.label())
+                } else {
+                    format!("part '{}' of '.{}' file should use {}", parts[i], ext, rule.case.label())
+                };
                 issues.push(NamingIssue {
-                    path: entry.path().to_path_buf(),
-                    issue_type: "Personality/entity JSON file should use TitleCase".to_string(),
-                    suggested_fix: format!("{}.json", new_name),
-                });
-            }
-        // Schema files should use kebab-case
-        } else if path_str.contains("/data/schemas/")
-            || path_str.contains("/data/vector")
-            || path_str.ends_with("-config.json")
-            || path_str.contains("/memory-schemas/")
-        {
-            if filename.contains('_') {
-                let new_name = filename.replace('_', "-");
-                issues.push(NamingIssue {
-                    path: entry.path().to_path_buf(),
-                    issue_type: "Schema/config JSON file should use kebab-case".to_string(),
-                    suggested_fix: format!("{}.json", new_name),
+                    path: path.to_path_buf(),
+                    code: "file-name-case",
+                    severity: rule.severity,
+                    message,
+                    suggested_fix: suggested_fix.clone(),
                 });
             }
         }
     }
 
-    if issues.is_empty() {
-        if config.verbose {
-            log_success("All JSON file names follow conventions");
+    if text {
+        if issues.is_empty() {
+            if config.verbose {
+                log_success("All file names follow conventions");
+            }
+        } else {
+            log_warning(&format!("Found {} file naming issues", issues.len()));
         }
-    } else {
-        log_warning(&format!("Found {} JSON naming issues", issues.len()));
     }
 
     Ok(issues)
 }
 
-/// Process and optionally fix naming issues
+/// Process and optionally fix naming issues. In `--format json`, fixing
+/// interactively doesn't make sense (there's no human at the other end of
+/// stdin, and a prompt would corrupt the JSON stream), so `--fix` is
+/// ignored there.
 fn process_issues(config: &ValidatorConfig, issues: &[NamingIssue]) -> Result<()> {
-    if issues.is_empty() {
+    if issues.is_empty() || config.format != OutputFormat::Text {
         return Ok(());
     }
 
     for issue in issues {
-        log_warning(&format!("{}: {}", issue.issue_type, issue.path.display()));
+        let log = match issue.severity {
+            Severity::Error => log_error,
+            Severity::Warning => log_warning,
+            Severity::WeakWarning => log_info,
+        };
+        log(&format!("{}: {}", issue.message, issue.path.display()));
         if config.fix_mode {
+            let new_path = issue.path.with_file_name(&issue.suggested_fix);
+            let is_rust = issue.path.extension().and_then(|e| e.to_str()) == Some("rs");
+            let edits = if is_rust {
+                plan_rust_reference_edits(config, &issue.path, &new_path)?
+            } else {
+                Vec::new()
+            };
+
             println!("  Suggested fix: {}", issue.suggested_fix);
+            if !edits.is_empty() {
+                println!("  Also rewrites references in {} file(s):", edits.len());
+                for edit in &edits {
+                    println!("    {}", edit.path.display());
+                }
+            }
             print!("  Apply this fix? [y/N] ");
             std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -355,8 +452,11 @@ fn process_issues(config: &ValidatorConfig, issues: &[NamingIssue]) -> Result<()
             std::io::stdin().read_line(&mut response)?;
 
             if response.trim().eq_ignore_ascii_case("y") {
-                let new_path = issue.path.with_file_name(&issue.suggested_fix);
-                fs::rename(&issue.path, &new_path)?;
+                if is_rust {
+                    apply_rust_rename_with_references(&issue.path, &new_path, &edits)?;
+                } else {
+                    fs::rename(&issue.path, &new_path)?;
+                }
                 log_success(&format!("Renamed to {}", new_path.display()));
             }
         }
@@ -365,29 +465,104 @@ fn process_issues(config: &ValidatorConfig, issues: &[NamingIssue]) -> Result<()
     Ok(())
 }
 
-/// Main validation function
+/// A pending rewrite of `mod`/`use` references to a `.rs` file being
+/// renamed, discovered before the rename happens so `process_issues` can
+/// show it in the fix preview.
+struct ReferenceEdit {
+    path: PathBuf,
+    original: String,
+    updated: String,
+}
+
+/// Scans every `.rs` file under `config.target_path` for references to
+/// `old_path`'s module name, returning the edit each one needs to instead
+/// name `new_path`'s. Doesn't touch disk - callers apply the edits
+/// alongside the rename (see `apply_rust_rename_with_references`).
+fn plan_rust_reference_edits(
+    config: &ValidatorConfig,
+    old_path: &Path,
+    new_path: &Path,
+) -> Result<Vec<ReferenceEdit>> {
+    let old_stem = old_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let new_stem = new_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    if old_stem == new_stem {
+        return Ok(Vec::new());
+    }
+
+    let mut edits = Vec::new();
+    for entry in WalkDir::new(&config.target_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        .filter(|e| e.path() != old_path)
+    {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if let Some(updated) = super::naming::rewrite_rust_references(&content, old_stem, new_stem) {
+            edits.push(ReferenceEdit {
+                path: entry.path().to_path_buf(),
+                original: content,
+                updated,
+            });
+        }
+    }
+
+    Ok(edits)
+}
+
+/// Renames `old` to `new` and applies `edits`. If any edit fails to
+/// write, every edit already applied is restored to its original
+/// content and the rename is undone, so the tree never ends up with the
+/// file moved but references left dangling.
+fn apply_rust_rename_with_references(old: &Path, new: &Path, edits: &[ReferenceEdit]) -> Result<()> {
+    fs::rename(old, new).with_context(|| format!("Failed to rename {:?} to {:?}", old, new))?;
+
+    for (applied, edit) in edits.iter().enumerate() {
+        if let Err(e) = fs::write(&edit.path, &edit.updated) {
+            for rollback in &edits[..applied] {
+                let _ = fs::write(&rollback.path, &rollback.original);
+            }
+            let _ = fs::rename(new, old);
+            return Err(e).with_context(|| format!("Failed to update references in {:?}", edit.path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Main validation function. The exit code (returned as `success`)
+/// reflects only `Severity::Error` issues, so a `naming-rules.toml` rule
+/// marked `warning`/`weak-warning` can surface without failing CI.
 fn run_validations(config: &ValidatorConfig) -> Result<bool> {
-    println!("Validating naming conventions in {:?}", config.target_path);
-    println!("========================================");
+    let text = config.format == OutputFormat::Text;
 
-    let mut success = true;
+    if text {
+        println!("Validating naming conventions in {:?}", config.target_path);
+        println!("========================================");
+    }
 
     // Run all validations
     let dir_issues = validate_directory_names(config)?;
-    let rust_issues = validate_rust_file_names(config)?;
-    let md_issues = validate_markdown_file_names(config)?;
-    let json_issues = validate_json_file_names(config)?;
+    let file_issues = validate_against_rules(config)?;
 
     // Process issues
     process_issues(config, &dir_issues)?;
-    process_issues(config, &rust_issues)?;
-    process_issues(config, &md_issues)?;
-    process_issues(config, &json_issues)?;
+    process_issues(config, &file_issues)?;
 
-    let total_issues = dir_issues.len() + rust_issues.len() + md_issues.len() + json_issues.len();
+    let issues: Vec<&NamingIssue> = dir_issues.iter().chain(file_issues.iter()).collect();
+    let success = !issues.iter().any(|issue| issue.severity == Severity::Error);
+
+    if config.format == OutputFormat::Json {
+        for issue in &issues {
+            println!("{}", serde_json::to_string(&issue.to_record())?);
+        }
+        return Ok(success);
+    }
 
     println!("========================================");
-    if total_issues == 0 {
+    if issues.is_empty() {
         log_success("All naming conventions validated successfully!");
         if config.verbose {
             println!();
@@ -402,11 +577,10 @@ fn run_validations(config: &ValidatorConfig) -> Result<bool> {
             println!("              TitleCase for entities/personalities (e.g., Heralds.json)");
         }
     } else {
-        log_warning(&format!("Found {} naming convention issues", total_issues));
+        log_warning(&format!("Found {} naming convention issues", issues.len()));
         println!();
         println!("For more information on naming conventions, see:");
         println!("docs/naming-conventions.md");
-        success = false;
     }
 
     Ok(success)
@@ -427,6 +601,12 @@ fn main() -> Result<()> {
                 .help("Show detailed information about checks")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .help("Output format: text or json")
+                .default_value("text"),
+        )
         .arg(
             Arg::new("path")
                 .help("Path to check (defaults to current directory)")
@@ -434,13 +614,22 @@ fn main() -> Result<()> {
         )
         .get_matches();
 
+    let format = OutputFormat::parse(matches.get_one::<String>("format").unwrap())?;
+
     // Simply use the path argument as provided
     let target_path = PathBuf::from(matches.get_one::<String>("path").unwrap());
 
+    let rules_file = NamingRulesFile::load(&target_path)?;
+    let mut rules = rules_file.rules;
+    rules.extend(default_rules());
+
     let config = ValidatorConfig {
         fix_mode: matches.get_flag("fix"),
         verbose: matches.get_flag("verbose"),
         target_path,
+        rules,
+        ignore: rules_file.ignore_names,
+        format,
     };
 
     match run_validations(&config)? {